@@ -101,8 +101,8 @@ impl KeyBinds {
                     }
                 }
             }
-            MenuMode::Albums => {
-                // Check albums-specific bindings first
+            MenuMode::Albums | MenuMode::Search | MenuMode::Playlists => {
+                // Check albums-specific bindings first (Search results and Playlists use the same layout)
                 if let Some(action) = self.albums_map.get(&key_tuple) {
                     // Handle panel-specific logic for albums mode
                     match (action, panel_focus) {
@@ -120,6 +120,16 @@ impl KeyBinds {
                         _ => return Some(action.clone()),
                     }
                 }
+                // Playlist entries are removed and reordered with the queue bindings
+                if *mode == MenuMode::Playlists
+                    && let Some(
+                        action @ (MPDAction::RemoveFromQueue
+                        | MPDAction::MoveUpInQueue
+                        | MPDAction::MoveDownInQueue),
+                    ) = self.queue_map.get(&key_tuple)
+                {
+                    return Some(action.clone());
+                }
                 // Fall back to artists_map for navigation bindings
                 if let Some(action) = self.artists_map.get(&key_tuple) {
                     match (action, panel_focus) {
@@ -138,6 +148,18 @@ impl KeyBinds {
                     }
                 }
             }
+            MenuMode::Browse => {
+                // Directory browser uses the artists navigation bindings:
+                // left goes up a directory, right opens the selected entry
+                if let Some(action) = self.artists_map.get(&key_tuple) {
+                    match action {
+                        MPDAction::SwitchPanelRight | MPDAction::ToggleAlbumExpansion => {
+                            return Some(MPDAction::PlaySelected);
+                        }
+                        _ => return Some(action.clone()),
+                    }
+                }
+            }
         }
 
         // Check if this key could start a sequential binding
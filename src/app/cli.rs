@@ -1,5 +1,6 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "zarumet")]
@@ -8,11 +9,11 @@ use std::path::PathBuf;
 #[command(about = "A TUI MPD client with album art", long_about = None)]
 pub struct Args {
     /// Path to config file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub config: Option<PathBuf>,
 
     /// MPD server address (overrides config)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub address: Option<String>,
 
     /// Set bit-perfect mode (PipeWire sample rate matching): "on" or "off"
@@ -22,6 +23,110 @@ pub struct Args {
     /// Generate default config file at the specified path (or ./config.toml if not specified)
     #[arg(long, value_name = "PATH", num_args = 0..=1, require_equals = false, default_missing_value = ".")]
     pub generate_config: Option<PathBuf>,
+
+    /// Delete the library snapshots that make startup fast and exit
+    #[arg(long)]
+    pub purge_library_cache: bool,
+
+    /// Run a single command against MPD and exit instead of starting the TUI
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Headless commands
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Start or resume playback
+    Play,
+    /// Pause playback
+    Pause,
+    /// Toggle between play and pause
+    Toggle,
+    /// Stop playback
+    Stop,
+    /// Play the next song in the queue
+    Next,
+    /// Play the previous song in the queue
+    #[command(alias = "previous")]
+    Prev,
+    /// Change the volume: "up"/"down" (by volume_increment), "50", "+10" or "-10"
+    Volume {
+        #[arg(allow_hyphen_values = true, value_parser = parse_volume)]
+        change: VolumeChange,
+    },
+    /// Seek in the current song: "90", "1:30", "+10" or "-10"
+    Seek {
+        #[arg(allow_hyphen_values = true, value_parser = parse_seek)]
+        target: SeekTarget,
+    },
+    /// Toggle random mode, or set it with "on"/"off"
+    Random {
+        #[arg(value_parser = parse_on_off)]
+        state: Option<bool>,
+    },
+    /// Toggle repeat mode, or set it with "on"/"off"
+    Repeat {
+        #[arg(value_parser = parse_on_off)]
+        state: Option<bool>,
+    },
+    /// Add songs or directories to the queue (URIs are read from stdin if none are given)
+    Add { uris: Vec<String> },
+    /// Clear the queue
+    Clear,
+    /// Print the current song and player status
+    Status {
+        /// Template with {artist}, {title}, {album}, {file}, {state}, {elapsed}, {duration},
+        /// {progress}, {position}, {queue_length}, {volume}, {repeat}, {random}, {single}, {consume}
+        #[arg(short, long)]
+        format: Option<String>,
+        /// Print the status as JSON
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+    },
+    /// Stay connected and print a line whenever the player state changes (for status bars)
+    Watch {
+        /// Template for the line, with the same placeholders as `status --format`
+        #[arg(short, long, default_value = "{artist} - {title}")]
+        format: String,
+        /// Template for the tooltip of the JSON output
+        #[arg(
+            long,
+            default_value = "{title}\\n{artist}\\n{album}\\n{elapsed}/{duration}"
+        )]
+        tooltip: String,
+        /// Print waybar/i3blocks JSON ({"text", "tooltip", "class", ...}) instead of plain text
+        #[arg(long)]
+        json: bool,
+        /// Also refresh every SECONDS while playing, so {elapsed} keeps moving
+        #[arg(short, long, value_name = "SECONDS")]
+        interval: Option<u64>,
+    },
+    /// Send a command to the running TUI over its control socket
+    ///
+    /// For example "toggle_bit_perfect" or "switch_to_artists". "commands" lists them all,
+    /// "status" prints the player and UI state and "subscribe" prints events until the TUI exits.
+    Ctl { command: String },
+}
+
+/// Argument of the `volume` command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeChange {
+    /// Up by `volume_increment`
+    Up,
+    /// Down by `volume_increment`
+    Down,
+    /// Absolute volume (0-100)
+    Set(u8),
+    /// Relative change in percent
+    Relative(i16),
+}
+
+/// Argument of the `seek` command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekTarget {
+    Absolute(Duration),
+    Forward(Duration),
+    Backward(Duration),
 }
 
 /// Parse "on" or "off" string to boolean
@@ -32,3 +137,109 @@ fn parse_on_off(s: &str) -> Result<bool, String> {
         _ => Err(format!("Invalid value '{}': expected 'on' or 'off'", s)),
     }
 }
+
+fn parse_volume(s: &str) -> Result<VolumeChange, String> {
+    let invalid = || format!("Invalid volume '{}': expected up, down, 0-100, +N or -N", s);
+    match s.to_lowercase().as_str() {
+        "up" => Ok(VolumeChange::Up),
+        "down" => Ok(VolumeChange::Down),
+        _ if s.starts_with(['+', '-']) => s
+            .parse::<i16>()
+            .map(VolumeChange::Relative)
+            .map_err(|_| invalid()),
+        _ => match s.parse::<u8>() {
+            Ok(volume) if volume <= 100 => Ok(VolumeChange::Set(volume)),
+            _ => Err(invalid()),
+        },
+    }
+}
+
+/// Parse "[+|-][[H:]M:]S[.frac]"
+fn parse_seek(s: &str) -> Result<SeekTarget, String> {
+    let invalid = || {
+        format!(
+            "Invalid seek position '{}': expected 90, 1:30, +10 or -10",
+            s
+        )
+    };
+    let (sign, time) = match s.chars().next() {
+        Some(sign @ ('+' | '-')) => (Some(sign), &s[1..]),
+        _ => (None, s),
+    };
+
+    let mut seconds = 0.0;
+    for part in time.split(':') {
+        let value: f64 = part.parse().map_err(|_| invalid())?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(invalid());
+        }
+        seconds = seconds * 60.0 + value;
+    }
+    let duration = Duration::from_secs_f64(seconds);
+
+    Ok(match sign {
+        Some('+') => SeekTarget::Forward(duration),
+        Some(_) => SeekTarget::Backward(duration),
+        None => SeekTarget::Absolute(duration),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_volume() {
+        assert_eq!(parse_volume("up"), Ok(VolumeChange::Up));
+        assert_eq!(parse_volume("Down"), Ok(VolumeChange::Down));
+        assert_eq!(parse_volume("40"), Ok(VolumeChange::Set(40)));
+        assert_eq!(parse_volume("+5"), Ok(VolumeChange::Relative(5)));
+        assert_eq!(parse_volume("-10"), Ok(VolumeChange::Relative(-10)));
+        assert!(parse_volume("101").is_err());
+        assert!(parse_volume("loud").is_err());
+    }
+
+    #[test]
+    fn test_parse_seek() {
+        let secs = Duration::from_secs;
+        assert_eq!(parse_seek("90"), Ok(SeekTarget::Absolute(secs(90))));
+        assert_eq!(parse_seek("1:30"), Ok(SeekTarget::Absolute(secs(90))));
+        assert_eq!(parse_seek("1:00:00"), Ok(SeekTarget::Absolute(secs(3600))));
+        assert_eq!(parse_seek("+10"), Ok(SeekTarget::Forward(secs(10))));
+        assert_eq!(parse_seek("-0:15"), Ok(SeekTarget::Backward(secs(15))));
+        assert!(parse_seek("1:xx").is_err());
+        assert!(parse_seek("").is_err());
+    }
+
+    #[test]
+    fn test_subcommands_parse() {
+        let args = Args::try_parse_from(["zarumet", "-a", "host:6600", "volume", "-5"]).unwrap();
+        assert_eq!(args.address.as_deref(), Some("host:6600"));
+        assert!(matches!(
+            args.command,
+            Some(Command::Volume {
+                change: VolumeChange::Relative(-5)
+            })
+        ));
+
+        let args = Args::try_parse_from(["zarumet", "status", "--json"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Status {
+                format: None,
+                json: true
+            })
+        ));
+        assert!(Args::try_parse_from(["zarumet", "status", "--json", "-f", "{title}"]).is_err());
+
+        let args = Args::try_parse_from(["zarumet", "watch", "--json", "-i", "1"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Watch {
+                json: true,
+                interval: Some(1),
+                ..
+            })
+        ));
+    }
+}
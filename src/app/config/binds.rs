@@ -44,6 +44,12 @@ pub struct BindsConfig {
     pub switch_to_artists: Vec<String>,
     #[serde(default = "BindsConfig::default_switch_to_albums")]
     pub switch_to_albums: Vec<String>,
+    #[serde(default = "BindsConfig::default_switch_to_search")]
+    pub switch_to_search: Vec<String>,
+    #[serde(default = "BindsConfig::default_switch_to_playlists")]
+    pub switch_to_playlists: Vec<String>,
+    #[serde(default = "BindsConfig::default_switch_to_browse")]
+    pub switch_to_browse: Vec<String>,
     #[serde(default = "BindsConfig::default_seek_forward")]
     pub seek_forward: Vec<String>,
     #[serde(default = "BindsConfig::default_seek_backward")]
@@ -76,8 +82,44 @@ pub struct BindsConfig {
     pub go_to_top: Vec<String>,
     #[serde(default = "BindsConfig::default_go_to_bottom")]
     pub go_to_bottom: Vec<String>,
+    #[serde(default = "BindsConfig::default_go_to_current")]
+    pub go_to_current: Vec<String>,
     #[serde(default = "BindsConfig::default_toggle_bit_perfect")]
     pub toggle_bit_perfect: Vec<String>,
+    #[serde(default = "BindsConfig::default_toggle_lyrics")]
+    pub toggle_lyrics: Vec<String>,
+    #[serde(default = "BindsConfig::default_filter")]
+    pub filter: Vec<String>,
+    #[serde(default = "BindsConfig::default_filter_next")]
+    pub filter_next: Vec<String>,
+    #[serde(default = "BindsConfig::default_filter_previous")]
+    pub filter_previous: Vec<String>,
+    #[serde(default = "BindsConfig::default_load_playlist")]
+    pub load_playlist: Vec<String>,
+    #[serde(default = "BindsConfig::default_save_queue_as_playlist")]
+    pub save_queue_as_playlist: Vec<String>,
+    #[serde(default = "BindsConfig::default_rename_playlist")]
+    pub rename_playlist: Vec<String>,
+    #[serde(default = "BindsConfig::default_add_to_playlist")]
+    pub add_to_playlist: Vec<String>,
+    #[serde(default = "BindsConfig::default_toggle_mark")]
+    pub toggle_mark: Vec<String>,
+    #[serde(default = "BindsConfig::default_visual_mode")]
+    pub visual_mode: Vec<String>,
+    #[serde(default = "BindsConfig::default_clear_marks")]
+    pub clear_marks: Vec<String>,
+    #[serde(default = "BindsConfig::default_add_next")]
+    pub add_next: Vec<String>,
+    #[serde(default = "BindsConfig::default_raise_priority")]
+    pub raise_priority: Vec<String>,
+    #[serde(default = "BindsConfig::default_lower_priority")]
+    pub lower_priority: Vec<String>,
+    #[serde(default = "BindsConfig::default_undo")]
+    pub undo: Vec<String>,
+    #[serde(default = "BindsConfig::default_redo")]
+    pub redo: Vec<String>,
+    #[serde(default = "BindsConfig::default_cycle_tag_view")]
+    pub cycle_tag_view: Vec<String>,
 }
 
 impl BindsConfig {
@@ -96,7 +138,7 @@ impl BindsConfig {
         ]
     }
     fn default_toggle_play_pause() -> Vec<String> {
-        vec!["space".to_string(), "p".to_string()]
+        vec!["p".to_string()]
     }
     fn default_volume_up() -> Vec<String> {
         vec!["=".to_string()]
@@ -144,7 +186,7 @@ impl BindsConfig {
         ]
     }
     fn default_refresh() -> Vec<String> {
-        vec!["u".to_string()]
+        vec!["shift-u".to_string()]
     }
     fn default_switch_to_queue_menu() -> Vec<String> {
         vec!["1".to_string()]
@@ -155,6 +197,15 @@ impl BindsConfig {
     fn default_switch_to_albums() -> Vec<String> {
         vec!["3".to_string()]
     }
+    fn default_switch_to_search() -> Vec<String> {
+        vec!["4".to_string()]
+    }
+    fn default_switch_to_playlists() -> Vec<String> {
+        vec!["5".to_string()]
+    }
+    fn default_switch_to_browse() -> Vec<String> {
+        vec!["6".to_string()]
+    }
     fn default_seek_forward() -> Vec<String> {
         vec!["shift-l".to_string(), "shift-right".to_string()]
     }
@@ -215,9 +266,63 @@ impl BindsConfig {
     fn default_go_to_bottom() -> Vec<String> {
         vec!["shift-g".to_string()]
     }
+    fn default_go_to_current() -> Vec<String> {
+        vec!["g c".to_string()]
+    }
     fn default_toggle_bit_perfect() -> Vec<String> {
         vec!["b".to_string()]
     }
+    fn default_toggle_lyrics() -> Vec<String> {
+        vec!["y".to_string()]
+    }
+    fn default_filter() -> Vec<String> {
+        vec!["/".to_string()]
+    }
+    fn default_filter_next() -> Vec<String> {
+        vec!["n".to_string()]
+    }
+    fn default_filter_previous() -> Vec<String> {
+        vec!["shift-n".to_string()]
+    }
+    fn default_load_playlist() -> Vec<String> {
+        vec!["o".to_string()]
+    }
+    fn default_save_queue_as_playlist() -> Vec<String> {
+        vec!["shift-s".to_string()]
+    }
+    fn default_rename_playlist() -> Vec<String> {
+        vec!["shift-r".to_string()]
+    }
+    fn default_add_to_playlist() -> Vec<String> {
+        vec!["shift-a".to_string()]
+    }
+    fn default_toggle_mark() -> Vec<String> {
+        vec!["space".to_string()]
+    }
+    fn default_visual_mode() -> Vec<String> {
+        vec!["v".to_string()]
+    }
+    fn default_clear_marks() -> Vec<String> {
+        vec!["shift-v".to_string()]
+    }
+    fn default_add_next() -> Vec<String> {
+        vec!["shift-p".to_string()]
+    }
+    fn default_raise_priority() -> Vec<String> {
+        vec!["]".to_string()]
+    }
+    fn default_lower_priority() -> Vec<String> {
+        vec!["[".to_string()]
+    }
+    fn default_undo() -> Vec<String> {
+        vec!["u".to_string()]
+    }
+    fn default_redo() -> Vec<String> {
+        vec!["ctrl-r".to_string()]
+    }
+    fn default_cycle_tag_view() -> Vec<String> {
+        vec!["t".to_string()]
+    }
 
     pub fn parse_keybinding(
         &self,
@@ -513,6 +618,24 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.switch_to_search,
+            crate::app::mpd_handler::MPDAction::SwitchToSearch,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.switch_to_playlists,
+            crate::app::mpd_handler::MPDAction::SwitchToPlaylists,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.switch_to_browse,
+            crate::app::mpd_handler::MPDAction::SwitchToBrowse,
+            single_map,
+            sequential_bindings,
+        );
         self.add_enhanced_binding_for_action(
             &self.seek_forward,
             crate::app::mpd_handler::MPDAction::SeekForward,
@@ -531,6 +654,114 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.toggle_lyrics,
+            crate::app::mpd_handler::MPDAction::ToggleLyrics,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.go_to_current,
+            crate::app::mpd_handler::MPDAction::GoToCurrent,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.filter,
+            crate::app::mpd_handler::MPDAction::Filter,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.filter_next,
+            crate::app::mpd_handler::MPDAction::FilterNext,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.filter_previous,
+            crate::app::mpd_handler::MPDAction::FilterPrevious,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.load_playlist,
+            crate::app::mpd_handler::MPDAction::LoadPlaylist,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.save_queue_as_playlist,
+            crate::app::mpd_handler::MPDAction::SaveQueueAsPlaylist,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.rename_playlist,
+            crate::app::mpd_handler::MPDAction::RenamePlaylist,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_to_playlist,
+            crate::app::mpd_handler::MPDAction::AddToPlaylist,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.toggle_mark,
+            crate::app::mpd_handler::MPDAction::ToggleMark,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.visual_mode,
+            crate::app::mpd_handler::MPDAction::VisualMode,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.clear_marks,
+            crate::app::mpd_handler::MPDAction::ClearMarks,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_next,
+            crate::app::mpd_handler::MPDAction::AddNext,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.raise_priority,
+            crate::app::mpd_handler::MPDAction::RaisePriority,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.lower_priority,
+            crate::app::mpd_handler::MPDAction::LowerPriority,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.undo,
+            crate::app::mpd_handler::MPDAction::Undo,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.redo,
+            crate::app::mpd_handler::MPDAction::Redo,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.cycle_tag_view,
+            crate::app::mpd_handler::MPDAction::CycleTagView,
+            single_map,
+            sequential_bindings,
+        );
     }
 
     /// Helper method to add bindings that may be sequential
@@ -811,6 +1042,9 @@ impl Default for BindsConfig {
             switch_to_queue_menu: Self::default_switch_to_queue_menu(),
             switch_to_artists: Self::default_switch_to_artists(),
             switch_to_albums: Self::default_switch_to_albums(),
+            switch_to_search: Self::default_switch_to_search(),
+            switch_to_playlists: Self::default_switch_to_playlists(),
+            switch_to_browse: Self::default_switch_to_browse(),
             seek_forward: Self::default_seek_forward(),
             seek_backward: Self::default_seek_backward(),
             play_selected: Self::default_play_selected(),
@@ -827,7 +1061,25 @@ impl Default for BindsConfig {
             scroll_down: Self::default_scroll_down_enhanced(),
             go_to_top: Self::default_go_to_top(),
             go_to_bottom: Self::default_go_to_bottom(),
+            go_to_current: Self::default_go_to_current(),
             toggle_bit_perfect: Self::default_toggle_bit_perfect(),
+            toggle_lyrics: Self::default_toggle_lyrics(),
+            filter: Self::default_filter(),
+            filter_next: Self::default_filter_next(),
+            filter_previous: Self::default_filter_previous(),
+            load_playlist: Self::default_load_playlist(),
+            save_queue_as_playlist: Self::default_save_queue_as_playlist(),
+            rename_playlist: Self::default_rename_playlist(),
+            add_to_playlist: Self::default_add_to_playlist(),
+            toggle_mark: Self::default_toggle_mark(),
+            visual_mode: Self::default_visual_mode(),
+            clear_marks: Self::default_clear_marks(),
+            add_next: Self::default_add_next(),
+            raise_priority: Self::default_raise_priority(),
+            lower_priority: Self::default_lower_priority(),
+            undo: Self::default_undo(),
+            redo: Self::default_redo(),
+            cycle_tag_view: Self::default_cycle_tag_view(),
         }
     }
 }
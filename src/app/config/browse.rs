@@ -0,0 +1,122 @@
+use mpd_client::tag::Tag;
+use serde::{Deserialize, Serialize};
+
+/// A tag hierarchy for the Artists view, e.g. Genre → AlbumArtist → Album
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TagView {
+    /// Shown in the Artists panel title
+    pub name: String,
+    /// MPD tags, one per level. The last one groups the songs in the Albums panel.
+    pub tags: Vec<String>,
+}
+
+impl TagView {
+    fn new(name: &str, tags: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    /// The view's tags as MPD tags
+    pub fn mpd_tags(&self) -> Vec<Tag> {
+        self.tags
+            .iter()
+            .filter_map(|tag| Tag::try_from(tag.as_str()).ok())
+            .collect()
+    }
+
+    /// Whether the view is the AlbumArtist → Album library
+    pub fn is_default(&self) -> bool {
+        matches!(self.mpd_tags()[..], [Tag::AlbumArtist, Tag::Album])
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BrowseConfig {
+    /// Tag hierarchies the Artists view switches between, the first one shown at startup
+    #[serde(default = "BrowseConfig::default_views")]
+    pub views: Vec<TagView>,
+}
+
+impl BrowseConfig {
+    fn default_views() -> Vec<TagView> {
+        vec![
+            TagView::new("Artists", &["AlbumArtist", "Album"]),
+            TagView::new("Genre", &["Genre", "AlbumArtist", "Album"]),
+            TagView::new("Composer", &["Composer", "Work"]),
+            TagView::new("Year", &["Date", "Album"]),
+        ]
+    }
+
+    /// Drop views without tags or with tag names MPD can't accept, returning a warning
+    /// for each
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        self.views.retain(|view| {
+            if view.tags.is_empty() {
+                warnings.push(format!("[browse] view '{}' has no tags", view.name));
+                return false;
+            }
+            match view.tags.iter().find_map(|tag| {
+                Tag::try_from(tag.as_str())
+                    .err()
+                    .map(|e| format!("[browse] view '{}': tag '{}': {}", view.name, tag, e))
+            }) {
+                Some(warning) => {
+                    warnings.push(warning);
+                    false
+                }
+                None => true,
+            }
+        });
+        warnings
+    }
+}
+
+impl Default for BrowseConfig {
+    fn default() -> Self {
+        Self {
+            views: Self::default_views(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_views_from_toml() {
+        let config: BrowseConfig = toml::from_str(
+            r#"
+            [[views]]
+            name = "Conductor"
+            tags = ["conductor", "Album"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.views.len(), 1);
+        assert_eq!(config.views[0].mpd_tags(), [Tag::Conductor, Tag::Album]);
+        assert!(!config.views[0].is_default());
+
+        // Missing section keeps the default views
+        let config: BrowseConfig = toml::from_str("").unwrap();
+        assert_eq!(config.views, BrowseConfig::default_views());
+        assert!(config.views[0].is_default());
+    }
+
+    #[test]
+    fn test_invalid_views_are_dropped() {
+        let mut config = BrowseConfig {
+            views: vec![
+                TagView::new("Empty", &[]),
+                TagView::new("Bad", &["Genre", "Album Artist"]),
+                TagView::new("Good", &["Composer", "Work"]),
+            ],
+        };
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(config.views, [TagView::new("Good", &["Composer", "Work"])]);
+    }
+}
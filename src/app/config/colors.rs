@@ -1,353 +1,149 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use ratatui::style::{Color, Style};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+use crate::app::config::theme::{self, ColorRole, ColorSpec, Palette};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ColorsConfig {
-    #[serde(default = "ColorsConfig::default_border")]
-    pub border: String,
-    #[serde(default = "ColorsConfig::default_song_title")]
-    pub song_title: String,
-    #[serde(default = "ColorsConfig::default_album")]
-    pub album: String,
-    #[serde(default = "ColorsConfig::default_artist")]
-    pub artist: String,
-    #[serde(default = "ColorsConfig::default_border_title")]
-    pub border_title: String,
-    #[serde(default = "ColorsConfig::default_progress_filled")]
-    pub progress_filled: String,
-    #[serde(default = "ColorsConfig::default_progress_empty")]
-    pub progress_empty: String,
-    #[serde(default = "ColorsConfig::default_paused")]
-    pub paused: String,
-    #[serde(default = "ColorsConfig::default_playing")]
-    pub playing: String,
-    #[serde(default = "ColorsConfig::default_stopped")]
-    pub stopped: String,
-    #[serde(default = "ColorsConfig::default_time_separator")]
-    pub time_separator: String,
-    #[serde(default = "ColorsConfig::default_time_duration")]
-    pub time_duration: String,
-    #[serde(default = "ColorsConfig::default_time_elapsed")]
-    pub time_elapsed: String,
-    #[serde(default = "ColorsConfig::default_queue_selected_highlight")]
-    pub queue_selected_highlight: String,
-    #[serde(default = "ColorsConfig::default_queue_selected_text")]
-    pub queue_selected_text: String,
-    #[serde(default = "ColorsConfig::default_queue_album")]
-    pub queue_album: String,
-    #[serde(default = "ColorsConfig::default_queue_song_title")]
-    pub queue_song_title: String,
-    #[serde(default = "ColorsConfig::default_queue_artist")]
-    pub queue_artist: String,
-    #[serde(default = "ColorsConfig::default_queue_position")]
-    pub queue_position: String,
-    #[serde(default = "ColorsConfig::default_queue_duration")]
-    pub queue_duration: String,
-    #[serde(default = "ColorsConfig::default_top_accent")]
-    pub top_accent: String,
-    #[serde(default = "ColorsConfig::default_volume")]
-    pub volume: String,
-    #[serde(default = "ColorsConfig::default_volume_empty")]
-    pub volume_empty: String,
-    #[serde(default = "ColorsConfig::default_mode")]
-    pub mode: String,
-    #[serde(default = "ColorsConfig::default_track_duration")]
-    pub track_duration: String,
+    /// Bundled preset or `<name>.toml` in the `themes` directory next to the config file
+    #[serde(default = "ColorsConfig::default_theme")]
+    pub theme: String,
+    /// Take the colors from the cover of the current song instead, keeping the theme's
+    /// modifiers. The theme is used for songs without a cover.
+    #[serde(default)]
+    pub dynamic: bool,
+    /// Colors set in `[colors]`, applied on top of the theme
+    #[serde(flatten)]
+    pub overrides: BTreeMap<String, String>,
+    /// Colors of the theme with the overrides applied
+    #[serde(skip)]
+    theme_palette: Palette,
+    /// Colors in use, which differ from the theme's while the dynamic theme is on
+    #[serde(skip)]
+    palette: Palette,
 }
 
 impl ColorsConfig {
-    /// Parse a hex color string like "#FF5500" into RGB values
-    pub fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
-        let hex = hex.trim_start_matches('#');
-        if hex.len() != 6 {
-            return None;
+    /// Load the theme and apply the overrides, returning warnings about anything that
+    /// couldn't be used
+    pub fn resolve(&mut self, themes_dir: Option<&Path>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut palette = Palette::default();
+        match theme::load_theme(&self.theme, themes_dir) {
+            Ok(entries) => {
+                let source = format!("theme {}", self.theme);
+                warnings.extend(palette.apply(&entries, &source));
+            }
+            Err(e) => warnings.push(e),
         }
-        let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-        let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-        let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-        Some((r, g, b))
-    }
-
-    pub fn time_elapsed(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.time_elapsed)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn time_duration(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.time_duration)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn time_separator(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.time_separator)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn paused(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.paused)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn playing(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.playing)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn stopped(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.stopped)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn album_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.album)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
-    }
-
-    pub fn progress_filled_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.progress_filled)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Green)
-    }
-
-    pub fn progress_empty_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.progress_empty)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Black)
-    }
-
-    pub fn border_title_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.border_title)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
+        // Unknown keys in [colors] are already reported when the config is loaded
+        let overrides: BTreeMap<String, String> = self
+            .overrides
+            .iter()
+            .filter(|(key, _)| ColorRole::from_key(key).is_some())
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        warnings.extend(palette.apply(&overrides, "[colors]"));
+        self.theme_palette = palette.clone();
+        self.palette = palette;
+        warnings
     }
 
-    pub fn border_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.border)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
+    pub fn theme_palette(&self) -> &Palette {
+        &self.theme_palette
     }
 
-    pub fn artist_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.artist)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Cyan)
+    pub fn palette(&self) -> &Palette {
+        &self.palette
     }
 
-    pub fn song_title_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.song_title)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Yellow)
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
     }
 
-    pub fn queue_album_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_album)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Green)
+    pub fn spec(&self, role: ColorRole) -> ColorSpec {
+        self.palette.get(role)
     }
 
-    pub fn queue_artist_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_artist)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Cyan)
+    pub fn color(&self, role: ColorRole) -> Color {
+        self.spec(role).color
     }
 
-    pub fn queue_song_title_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_song_title)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Yellow)
+    /// Color of `role` with its modifiers, for text
+    pub fn style(&self, role: ColorRole) -> Style {
+        self.spec(role).style()
     }
 
-    pub fn queue_selected_text_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_selected_text)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::White)
+    pub fn border_color(&self) -> Color {
+        self.color(ColorRole::Border)
     }
 
-    pub fn queue_selected_highlight_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_selected_highlight)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Black)
+    pub fn queue_selected_text_color(&self) -> Color {
+        self.color(ColorRole::QueueSelectedText)
     }
 
-    pub fn queue_position_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_position)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Magenta)
-    }
-
-    pub fn queue_duration_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.queue_duration)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Magenta)
-    }
-
-    pub fn top_accent_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.top_accent)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Blue)
-    }
-
-    pub fn volume_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.volume)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Yellow)
-    }
-
-    pub fn volume_empty_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.volume_empty)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Black)
-    }
-
-    pub fn mode_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.mode)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Green)
-    }
-
-    pub fn track_duration_color(&self) -> ratatui::style::Color {
-        Self::parse_hex(&self.track_duration)
-            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
-            .unwrap_or(ratatui::style::Color::Red)
+    pub fn queue_selected_highlight_color(&self) -> Color {
+        self.color(ColorRole::QueueSelectedHighlight)
     }
 }
 
 impl ColorsConfig {
-    fn default_border() -> String {
-        "#fae280".to_string()
-    }
-
-    fn default_song_title() -> String {
-        "#fae280".to_string()
-    }
-
-    fn default_album() -> String {
-        "#26a0a1".to_string()
-    }
-
-    fn default_artist() -> String {
-        "#d67751".to_string()
-    }
-
-    fn default_border_title() -> String {
-        "#8193af".to_string()
-    }
-
-    fn default_progress_filled() -> String {
-        "#26a0a1".to_string()
-    }
-
-    fn default_progress_empty() -> String {
-        "#1b1d0e".to_string()
-    }
-
-    fn default_paused() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_playing() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_stopped() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_time_separator() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_time_duration() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_time_elapsed() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_queue_selected_highlight() -> String {
-        "#b18a4a".to_string()
-    }
-
-    fn default_queue_selected_text() -> String {
-        "#1b1d0e".to_string()
-    }
-
-    fn default_queue_album() -> String {
-        "#26a0a1".to_string()
-    }
-
-    fn default_queue_artist() -> String {
-        "#d67751".to_string()
-    }
-
-    fn default_queue_song_title() -> String {
-        "#fae280".to_string()
-    }
-
-    fn default_queue_position() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_queue_duration() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_volume() -> String {
-        "#26a0a1".to_string()
-    }
-
-    fn default_top_accent() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_track_duration() -> String {
-        "#e16a7c".to_string()
-    }
-
-    fn default_volume_empty() -> String {
-        "#1b1d0e".to_string()
-    }
-
-    fn default_mode() -> String {
-        "#fae280".to_string()
+    fn default_theme() -> String {
+        "default".to_string()
     }
 }
 
 impl Default for ColorsConfig {
     fn default() -> Self {
         Self {
-            album: Self::default_album(),
-            artist: Self::default_artist(),
-            song_title: Self::default_song_title(),
-            border: Self::default_border(),
-            border_title: Self::default_border_title(),
-            playing: Self::default_playing(),
-            paused: Self::default_paused(),
-            stopped: Self::default_stopped(),
-            progress_filled: Self::default_progress_filled(),
-            progress_empty: Self::default_progress_empty(),
-            time_elapsed: Self::default_time_elapsed(),
-            time_separator: Self::default_time_separator(),
-            time_duration: Self::default_time_duration(),
-            queue_selected_highlight: Self::default_queue_selected_highlight(),
-            queue_selected_text: Self::default_queue_selected_text(),
-            queue_album: Self::default_queue_album(),
-            queue_artist: Self::default_queue_artist(),
-            queue_song_title: Self::default_queue_song_title(),
-            queue_position: Self::default_queue_position(),
-            queue_duration: Self::default_queue_duration(),
-            top_accent: Self::default_top_accent(),
-            volume: Self::default_volume(),
-            volume_empty: Self::default_volume_empty(),
-            mode: Self::default_mode(),
-            track_duration: Self::default_track_duration(),
+            theme: Self::default_theme(),
+            dynamic: false,
+            overrides: BTreeMap::new(),
+            theme_palette: Palette::default(),
+            palette: Palette::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    #[test]
+    fn test_overrides_apply_on_top_of_theme() {
+        let mut colors: ColorsConfig = toml::from_str(
+            r##"
+            theme = "nord"
+            album = "#ff0000 bold"
+            artist = "not a color"
+            "##,
+        )
+        .unwrap();
+        let warnings = colors.resolve(None);
+
+        assert_eq!(colors.color(ColorRole::Album), Color::Rgb(0xff, 0, 0));
+        assert_eq!(colors.spec(ColorRole::Album).modifiers, Modifier::BOLD);
+        // Invalid overrides keep the theme's color
+        assert_eq!(
+            colors.color(ColorRole::Artist),
+            Color::Rgb(0xd0, 0x87, 0x70)
+        );
+        assert_eq!(colors.border_color(), Color::Rgb(0x81, 0xa1, 0xc1));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_theme_falls_back_to_default() {
+        let mut colors = ColorsConfig {
+            theme: "missing".to_string(),
+            ..ColorsConfig::default()
+        };
+        let warnings = colors.resolve(None);
+        assert_eq!(warnings, ["Unknown theme: missing"]);
+        assert_eq!(colors.border_color(), Color::Rgb(0xfa, 0xe2, 0x80));
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ControlConfig {
+    /// Listen on `$XDG_RUNTIME_DIR/zarumet/control.sock` while the TUI is running
+    #[serde(default = "ControlConfig::default_enabled")]
+    pub enabled: bool,
+}
+
+impl ControlConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+        }
+    }
+}
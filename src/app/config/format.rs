@@ -1,8 +1,14 @@
 use crate::app::config::binds::BindsConfig;
+use crate::app::config::browse::BrowseConfig;
 use crate::app::config::colors::ColorsConfig;
+use crate::app::config::control::ControlConfig;
 use crate::app::config::logging::LoggingConfig;
+use crate::app::config::lyrics::LyricsConfig;
 use crate::app::config::mpd::MpdConfig;
+use crate::app::config::notifications::NotificationsConfig;
 use crate::app::config::pipewire::PipewireConfig;
+use crate::app::config::progress::ProgressConfig;
+use crate::app::config::scrobbling::ScrobblingConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -18,6 +24,18 @@ pub struct Config {
     pub pipewire: PipewireConfig,
     #[serde(default)]
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub scrobbling: ScrobblingConfig,
+    #[serde(default)]
+    pub lyrics: LyricsConfig,
+    #[serde(default)]
+    pub control: ControlConfig,
+    #[serde(default)]
+    pub progress: ProgressConfig,
+    #[serde(default)]
+    pub browse: BrowseConfig,
 }
 
 /// Calculate Levenshtein distance between two strings
@@ -139,9 +157,9 @@ impl Config {
         let contents = std::fs::read_to_string(&config_path)?;
 
         // Check for unknown config options before parsing
-        let warnings = Self::check_unknown_fields(&contents);
+        let mut warnings = Self::check_unknown_fields(&contents);
 
-        let config: Config = toml::from_str(&contents).unwrap_or_else(|e| {
+        let mut config: Config = toml::from_str(&contents).unwrap_or_else(|e| {
             // This warning will be lost since logger isn't initialized yet,
             // but at least we log in debug mode
             if cfg!(debug_assertions) {
@@ -149,6 +167,12 @@ impl Config {
             }
             Config::default()
         });
+
+        // Theme files live in a `themes` directory next to the config file
+        let themes_dir = config_path.parent().map(|parent| parent.join("themes"));
+        warnings.extend(config.colors.resolve(themes_dir.as_deref()));
+        warnings.extend(config.progress.validate());
+        warnings.extend(config.browse.validate());
         Ok((config, warnings))
     }
 
@@ -157,12 +181,32 @@ impl Config {
         let mut warnings = Vec::new();
 
         // Known top-level sections
-        const KNOWN_SECTIONS: &[&str] = &["mpd", "colors", "binds", "pipewire", "logging"];
+        const KNOWN_SECTIONS: &[&str] = &[
+            "mpd",
+            "colors",
+            "binds",
+            "pipewire",
+            "logging",
+            "notifications",
+            "scrobbling",
+            "lyrics",
+            "control",
+            "progress",
+            "browse",
+        ];
 
         // Known fields per section
-        const KNOWN_MPD_FIELDS: &[&str] = &["address", "volume_increment", "volume_increment_fine"];
+        const KNOWN_MPD_FIELDS: &[&str] = &[
+            "address",
+            "volume_increment",
+            "volume_increment_fine",
+            "password",
+            "password_file",
+        ];
 
         const KNOWN_COLORS_FIELDS: &[&str] = &[
+            "theme",
+            "dynamic",
             "border",
             "song_title",
             "album",
@@ -211,6 +255,9 @@ impl Config {
             "switch_to_queue_menu",
             "switch_to_artists",
             "switch_to_albums",
+            "switch_to_search",
+            "switch_to_playlists",
+            "switch_to_browse",
             "seek_forward",
             "seek_backward",
             "scroll_up",
@@ -227,7 +274,25 @@ impl Config {
             "scroll_down_big",
             "go_to_top",
             "go_to_bottom",
+            "go_to_current",
             "toggle_bit_perfect",
+            "toggle_lyrics",
+            "filter",
+            "filter_next",
+            "filter_previous",
+            "load_playlist",
+            "save_queue_as_playlist",
+            "rename_playlist",
+            "add_to_playlist",
+            "toggle_mark",
+            "visual_mode",
+            "clear_marks",
+            "add_next",
+            "raise_priority",
+            "lower_priority",
+            "undo",
+            "redo",
+            "cycle_tag_view",
         ];
 
         const KNOWN_PIPEWIRE_FIELDS: &[&str] = &["bit_perfect_enabled"];
@@ -243,6 +308,34 @@ impl Config {
             "custom_log_path",
         ];
 
+        const KNOWN_NOTIFICATIONS_FIELDS: &[&str] = &[
+            "enabled",
+            "show_cover",
+            "suppress_when_focused",
+            "timeout_ms",
+        ];
+
+        const KNOWN_SCROBBLING_FIELDS: &[&str] = &["enabled", "lastfm", "listenbrainz"];
+
+        const KNOWN_LASTFM_FIELDS: &[&str] = &[
+            "api_key",
+            "api_secret",
+            "session_key",
+            "username",
+            "password",
+            "endpoint",
+        ];
+
+        const KNOWN_LISTENBRAINZ_FIELDS: &[&str] = &["token", "endpoint"];
+
+        const KNOWN_LYRICS_FIELDS: &[&str] = &["enabled", "music_directory"];
+
+        const KNOWN_CONTROL_FIELDS: &[&str] = &["enabled"];
+
+        const KNOWN_PROGRESS_FIELDS: &[&str] = &["frame_rate"];
+
+        const KNOWN_BROWSE_FIELDS: &[&str] = &["views"];
+
         // Parse as generic TOML table
         let table: Result<toml::Table, _> = toml::from_str(contents);
         let table = match table {
@@ -310,6 +403,83 @@ impl Config {
             }
         }
 
+        if let Some(toml::Value::Table(notifications)) = table.get("notifications") {
+            for key in notifications.keys() {
+                if !KNOWN_NOTIFICATIONS_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_NOTIFICATIONS_FIELDS);
+                    let msg = format_unknown_warning("[notifications]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(scrobbling)) = table.get("scrobbling") {
+            for key in scrobbling.keys() {
+                if !KNOWN_SCROBBLING_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_SCROBBLING_FIELDS);
+                    let msg = format_unknown_warning("[scrobbling]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+
+            let services = [
+                ("lastfm", KNOWN_LASTFM_FIELDS),
+                ("listenbrainz", KNOWN_LISTENBRAINZ_FIELDS),
+            ];
+            for (service, known) in services {
+                if let Some(toml::Value::Table(fields)) = scrobbling.get(service) {
+                    let section = format!("[scrobbling.{}]", service);
+                    for key in fields.keys() {
+                        if !known.contains(&key.as_str()) {
+                            let suggestion = find_similar(key, known);
+                            let msg = format_unknown_warning(&section, key, suggestion.as_deref());
+                            warnings.push(msg);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(lyrics)) = table.get("lyrics") {
+            for key in lyrics.keys() {
+                if !KNOWN_LYRICS_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_LYRICS_FIELDS);
+                    let msg = format_unknown_warning("[lyrics]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(control)) = table.get("control") {
+            for key in control.keys() {
+                if !KNOWN_CONTROL_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_CONTROL_FIELDS);
+                    let msg = format_unknown_warning("[control]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(progress)) = table.get("progress") {
+            for key in progress.keys() {
+                if !KNOWN_PROGRESS_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_PROGRESS_FIELDS);
+                    let msg = format_unknown_warning("[progress]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(browse)) = table.get("browse") {
+            for key in browse.keys() {
+                if !KNOWN_BROWSE_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_BROWSE_FIELDS);
+                    let msg = format_unknown_warning("[browse]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
         warnings
     }
 
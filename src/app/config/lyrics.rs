@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LyricsConfig {
+    /// Show the lyrics pane next to the cover art on startup
+    #[serde(default = "LyricsConfig::default_enabled")]
+    pub enabled: bool,
+    /// MPD's music directory, used to find `.lrc` files next to the songs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_directory: Option<PathBuf>,
+}
+
+impl LyricsConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+
+    /// Music directory with a leading `~/` expanded
+    pub fn music_directory(&self) -> Option<PathBuf> {
+        self.music_directory.as_deref().map(super::mpd::expand_home)
+    }
+}
+
+impl Default for LyricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            music_directory: None,
+        }
+    }
+}
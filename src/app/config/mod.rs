@@ -1,9 +1,16 @@
 pub mod binds;
+pub mod browse;
 pub mod colors;
+pub mod control;
 pub mod format;
 pub mod logging;
+pub mod lyrics;
 pub mod mpd;
+pub mod notifications;
 pub mod pipewire;
+pub mod progress;
+pub mod scrobbling;
+pub mod theme;
 
 pub use format::Config;
 pub use logging::LoggingConfig;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MpdConfig {
@@ -8,6 +9,12 @@ pub struct MpdConfig {
     pub volume_increment: u32,
     #[serde(default = "MpdConfig::default_volume_increment_fine")]
     pub volume_increment_fine: u32,
+    /// Password sent to MPD after connecting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// File containing the MPD password (first line is used)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_file: Option<PathBuf>,
 }
 
 impl MpdConfig {
@@ -20,6 +27,53 @@ impl MpdConfig {
     fn default_volume_increment_fine() -> u32 {
         1
     }
+
+    /// Address to connect to, with any `password@` prefix stripped
+    pub fn host(&self) -> &str {
+        split_address(&self.address).0
+    }
+
+    /// Resolve the password to send after connecting.
+    /// Priority: `password`, then `password_file`, then a `password@host` address.
+    pub fn resolve_password(&self) -> color_eyre::Result<Option<String>> {
+        if let Some(ref password) = self.password {
+            return Ok(Some(password.clone()));
+        }
+
+        if let Some(ref path) = self.password_file {
+            let path = expand_home(path);
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                color_eyre::eyre::eyre!(
+                    "Failed to read MPD password file {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            let password = contents.lines().next().unwrap_or("").trim().to_string();
+            return Ok(Some(password));
+        }
+
+        Ok(split_address(&self.address).1.map(str::to_string))
+    }
+}
+
+/// Split an `MPD_HOST` style address (`password@host`) into host and password
+fn split_address(address: &str) -> (&str, Option<&str>) {
+    match address.rsplit_once('@') {
+        // A leading '@' is an abstract socket name, not an empty password
+        Some((password, host)) if !password.is_empty() => (host, Some(password)),
+        _ => (address, None),
+    }
+}
+
+/// Expand a leading `~/` to the user's home directory
+pub(super) fn expand_home(path: &std::path::Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+    path.to_path_buf()
 }
 
 impl Default for MpdConfig {
@@ -28,6 +82,50 @@ impl Default for MpdConfig {
             address: Self::default_address(),
             volume_increment: Self::default_volume_increment(),
             volume_increment_fine: Self::default_volume_increment_fine(),
+            password: None,
+            password_file: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_address_plain() {
+        assert_eq!(split_address("localhost:6600"), ("localhost:6600", None));
+        assert_eq!(split_address("/run/mpd/socket"), ("/run/mpd/socket", None));
+    }
+
+    #[test]
+    fn test_split_address_with_password() {
+        assert_eq!(
+            split_address("secret@localhost:6600"),
+            ("localhost:6600", Some("secret"))
+        );
+        assert_eq!(
+            split_address("p@ss@/run/mpd/socket"),
+            ("/run/mpd/socket", Some("p@ss"))
+        );
+    }
+
+    #[test]
+    fn test_split_address_abstract_socket() {
+        assert_eq!(split_address("@mpd"), ("@mpd", None));
+    }
+
+    #[test]
+    fn test_password_field_takes_priority() {
+        let config = MpdConfig {
+            address: "fromhost@localhost:6600".to_string(),
+            password: Some("fromfield".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config.host(), "localhost:6600");
+        assert_eq!(
+            config.resolve_password().unwrap().as_deref(),
+            Some("fromfield")
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotificationsConfig {
+    /// Show a desktop notification when the track changes
+    #[serde(default = "NotificationsConfig::default_enabled")]
+    pub enabled: bool,
+    /// Include the cover art in the notification
+    #[serde(default = "NotificationsConfig::default_show_cover")]
+    pub show_cover: bool,
+    /// Skip notifications while the terminal running Zarumet is focused
+    #[serde(default = "NotificationsConfig::default_suppress_when_focused")]
+    pub suppress_when_focused: bool,
+    /// How long the notification stays up in milliseconds (-1 uses the server default)
+    #[serde(default = "NotificationsConfig::default_timeout_ms")]
+    pub timeout_ms: i32,
+}
+
+impl NotificationsConfig {
+    fn default_enabled() -> bool {
+        false
+    }
+
+    fn default_show_cover() -> bool {
+        true
+    }
+
+    fn default_suppress_when_focused() -> bool {
+        true
+    }
+
+    fn default_timeout_ms() -> i32 {
+        5000
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            show_cover: Self::default_show_cover(),
+            suppress_when_focused: Self::default_suppress_when_focused(),
+            timeout_ms: Self::default_timeout_ms(),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Frame rates the progress bar can be drawn at
+const FRAME_RATES: std::ops::RangeInclusive<u32> = 1..=60;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProgressConfig {
+    /// How many times per second the progress bar moves while playing
+    #[serde(default = "ProgressConfig::default_frame_rate")]
+    pub frame_rate: u32,
+}
+
+impl ProgressConfig {
+    fn default_frame_rate() -> u32 {
+        10
+    }
+
+    /// Time between two frames of the progress bar
+    pub fn frame_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.frame_rate
+    }
+
+    /// Bring `frame_rate` into range, returning a warning if it wasn't
+    pub fn validate(&mut self) -> Vec<String> {
+        if FRAME_RATES.contains(&self.frame_rate) {
+            return Vec::new();
+        }
+        let clamped = self
+            .frame_rate
+            .clamp(*FRAME_RATES.start(), *FRAME_RATES.end());
+        let warning = format!(
+            "[progress] frame_rate {} is out of range ({}-{}), using {}",
+            self.frame_rate,
+            FRAME_RATES.start(),
+            FRAME_RATES.end(),
+            clamped
+        );
+        self.frame_rate = clamped;
+        vec![warning]
+    }
+}
+
+impl Default for ProgressConfig {
+    fn default() -> Self {
+        Self {
+            frame_rate: Self::default_frame_rate(),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ScrobblingConfig {
+    /// Submit listens to the services configured below
+    #[serde(default = "ScrobblingConfig::default_enabled")]
+    pub enabled: bool,
+    /// Last.fm account (`[scrobbling.lastfm]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastfm: Option<LastFmConfig>,
+    /// ListenBrainz account (`[scrobbling.listenbrainz]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listenbrainz: Option<ListenBrainzConfig>,
+}
+
+impl ScrobblingConfig {
+    fn default_enabled() -> bool {
+        false
+    }
+}
+
+impl Default for ScrobblingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            lastfm: None,
+            listenbrainz: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LastFmConfig {
+    /// API key of a Last.fm API account
+    pub api_key: String,
+    /// Shared secret of the API account, used to sign requests
+    pub api_secret: String,
+    /// Session key; if unset one is requested with `username` and `password` at startup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// API root (override to test against a local server)
+    #[serde(default = "LastFmConfig::default_endpoint")]
+    pub endpoint: String,
+}
+
+impl LastFmConfig {
+    fn default_endpoint() -> String {
+        "https://ws.audioscrobbler.com/2.0/".to_string()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ListenBrainzConfig {
+    /// User token from the ListenBrainz settings page
+    pub token: String,
+    /// API root (override for a self-hosted instance or a local test server)
+    #[serde(default = "ListenBrainzConfig::default_endpoint")]
+    pub endpoint: String,
+}
+
+impl ListenBrainzConfig {
+    fn default_endpoint() -> String {
+        "https://api.listenbrainz.org".to_string()
+    }
+}
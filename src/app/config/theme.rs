@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+
+/// Themes shipped with Zarumet, selectable by name without a theme file
+pub const PRESETS: &[(&str, &str)] = &[
+    ("default", include_str!("themes/default.toml")),
+    ("terminal", include_str!("themes/terminal.toml")),
+    ("gruvbox", include_str!("themes/gruvbox.toml")),
+    ("nord", include_str!("themes/nord.toml")),
+    (
+        "catppuccin-mocha",
+        include_str!("themes/catppuccin-mocha.toml"),
+    ),
+];
+
+/// UI element that can be given its own color, in the order of [`ColorRole::ALL`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorRole {
+    Border,
+    SongTitle,
+    Album,
+    Artist,
+    BorderTitle,
+    ProgressFilled,
+    ProgressEmpty,
+    Paused,
+    Playing,
+    Stopped,
+    TimeSeparator,
+    TimeDuration,
+    TimeElapsed,
+    QueueSelectedHighlight,
+    QueueSelectedText,
+    QueueAlbum,
+    QueueSongTitle,
+    QueueArtist,
+    QueuePosition,
+    QueueDuration,
+    TopAccent,
+    Volume,
+    VolumeEmpty,
+    Mode,
+    TrackDuration,
+}
+
+impl ColorRole {
+    pub const ALL: [ColorRole; 25] = [
+        ColorRole::Border,
+        ColorRole::SongTitle,
+        ColorRole::Album,
+        ColorRole::Artist,
+        ColorRole::BorderTitle,
+        ColorRole::ProgressFilled,
+        ColorRole::ProgressEmpty,
+        ColorRole::Paused,
+        ColorRole::Playing,
+        ColorRole::Stopped,
+        ColorRole::TimeSeparator,
+        ColorRole::TimeDuration,
+        ColorRole::TimeElapsed,
+        ColorRole::QueueSelectedHighlight,
+        ColorRole::QueueSelectedText,
+        ColorRole::QueueAlbum,
+        ColorRole::QueueSongTitle,
+        ColorRole::QueueArtist,
+        ColorRole::QueuePosition,
+        ColorRole::QueueDuration,
+        ColorRole::TopAccent,
+        ColorRole::Volume,
+        ColorRole::VolumeEmpty,
+        ColorRole::Mode,
+        ColorRole::TrackDuration,
+    ];
+
+    /// Key of the role in `[colors]` and in theme files
+    pub fn key(self) -> &'static str {
+        match self {
+            ColorRole::Border => "border",
+            ColorRole::SongTitle => "song_title",
+            ColorRole::Album => "album",
+            ColorRole::Artist => "artist",
+            ColorRole::BorderTitle => "border_title",
+            ColorRole::ProgressFilled => "progress_filled",
+            ColorRole::ProgressEmpty => "progress_empty",
+            ColorRole::Paused => "paused",
+            ColorRole::Playing => "playing",
+            ColorRole::Stopped => "stopped",
+            ColorRole::TimeSeparator => "time_separator",
+            ColorRole::TimeDuration => "time_duration",
+            ColorRole::TimeElapsed => "time_elapsed",
+            ColorRole::QueueSelectedHighlight => "queue_selected_highlight",
+            ColorRole::QueueSelectedText => "queue_selected_text",
+            ColorRole::QueueAlbum => "queue_album",
+            ColorRole::QueueSongTitle => "queue_song_title",
+            ColorRole::QueueArtist => "queue_artist",
+            ColorRole::QueuePosition => "queue_position",
+            ColorRole::QueueDuration => "queue_duration",
+            ColorRole::TopAccent => "top_accent",
+            ColorRole::Volume => "volume",
+            ColorRole::VolumeEmpty => "volume_empty",
+            ColorRole::Mode => "mode",
+            ColorRole::TrackDuration => "track_duration",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ColorRole> {
+        ColorRole::ALL.into_iter().find(|role| role.key() == key)
+    }
+}
+
+/// A color with text modifiers, written like `"#fae280"`, `"light-cyan"`, `"rgb(38, 160, 161)"`,
+/// `"208"` (an index into the terminal's 256 colors) or `"yellow bold italic"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorSpec {
+    pub color: Color,
+    pub modifiers: Modifier,
+}
+
+impl ColorSpec {
+    pub fn style(&self) -> Style {
+        Style::default().fg(self.color).add_modifier(self.modifiers)
+    }
+}
+
+impl Default for ColorSpec {
+    fn default() -> Self {
+        Self {
+            color: Color::Reset,
+            modifiers: Modifier::empty(),
+        }
+    }
+}
+
+/// Parse a hex color string like "#FF5500" into RGB values
+pub fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some((r, g, b))
+}
+
+/// Parse `rgb(r, g, b)`
+fn parse_rgb(text: &str) -> Option<Color> {
+    let args = text.strip_prefix("rgb(")?.strip_suffix(')')?;
+    let mut channels = args.split(',').map(|channel| channel.trim().parse::<u8>());
+    let (Some(Ok(r)), Some(Ok(g)), Some(Ok(b)), None) = (
+        channels.next(),
+        channels.next(),
+        channels.next(),
+        channels.next(),
+    ) else {
+        return None;
+    };
+    Some(Color::Rgb(r, g, b))
+}
+
+fn parse_modifier(word: &str) -> Option<Modifier> {
+    match word {
+        "bold" => Some(Modifier::BOLD),
+        "dim" => Some(Modifier::DIM),
+        "italic" => Some(Modifier::ITALIC),
+        "underline" | "underlined" => Some(Modifier::UNDERLINED),
+        "reverse" | "reversed" => Some(Modifier::REVERSED),
+        "strikethrough" | "crossed_out" => Some(Modifier::CROSSED_OUT),
+        _ => None,
+    }
+}
+
+fn parse_color(word: &str) -> Option<Color> {
+    if word.starts_with("rgb(") {
+        return parse_rgb(word);
+    }
+    // Older configs wrote hex colors without the leading '#'
+    if let Some((r, g, b)) = parse_hex(word).filter(|_| !word.starts_with('#')) {
+        return Some(Color::Rgb(r, g, b));
+    }
+    Color::from_str(word).ok()
+}
+
+/// Split a spec into words, keeping `rgb(...)` together even when it has spaces
+fn words(spec: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut depth = 0usize;
+    for c in spec.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if c.is_whitespace() && depth == 0 {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+impl FromStr for ColorSpec {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut color = None;
+        let mut modifiers = Modifier::empty();
+        for word in words(&spec.to_lowercase()) {
+            if let Some(modifier) = parse_modifier(&word) {
+                modifiers |= modifier;
+            } else if let Some(parsed) = parse_color(&word) {
+                if color.replace(parsed).is_some() {
+                    return Err(format!("more than one color in \"{}\"", spec));
+                }
+            } else {
+                return Err(format!("unknown color or modifier \"{}\"", word));
+            }
+        }
+        let color = color.ok_or_else(|| format!("no color in \"{}\"", spec))?;
+        Ok(ColorSpec { color, modifiers })
+    }
+}
+
+/// Resolved color of every role
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette([ColorSpec; 25]);
+
+impl Palette {
+    pub fn get(&self, role: ColorRole) -> ColorSpec {
+        self.0[role as usize]
+    }
+
+    pub fn set(&mut self, role: ColorRole, spec: ColorSpec) {
+        self.0[role as usize] = spec;
+    }
+
+    /// Apply `role = "spec"` entries on top of the palette. Entries that can't be used are
+    /// skipped and described in the returned warnings, prefixed with `source`.
+    pub fn apply(&mut self, entries: &BTreeMap<String, String>, source: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (key, value) in entries {
+            let Some(role) = ColorRole::from_key(key) else {
+                warnings.push(format!("Unknown color in {}: {}", source, key));
+                continue;
+            };
+            match value.parse() {
+                Ok(spec) => self.set(role, spec),
+                Err(e) => warnings.push(format!("Invalid color for {} in {}: {}", key, source, e)),
+            }
+        }
+        warnings
+    }
+
+    /// Palette `t` of the way from `self` to `to`. Only RGB colors can be blended,
+    /// others switch halfway.
+    pub fn blend(&self, to: &Palette, t: f32) -> Palette {
+        let mut blended = to.clone();
+        for role in ColorRole::ALL {
+            let (from_spec, to_spec) = (self.get(role), to.get(role));
+            let color = match (from_spec.color, to_spec.color) {
+                (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+                    let channel =
+                        |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+                    Color::Rgb(channel(r1, r2), channel(g1, g2), channel(b1, b2))
+                }
+                (from, _) if t < 0.5 => from,
+                (_, to) => to,
+            };
+            blended.set(
+                role,
+                ColorSpec {
+                    color,
+                    modifiers: to_spec.modifiers,
+                },
+            );
+        }
+        blended
+    }
+}
+
+impl Default for Palette {
+    /// The "default" preset
+    fn default() -> Self {
+        let mut palette = Palette([ColorSpec::default(); 25]);
+        if let Ok(entries) = toml::from_str(PRESETS[0].1) {
+            palette.apply(&entries, "the default theme");
+        }
+        palette
+    }
+}
+
+/// Entries of the theme called `name`: `<themes_dir>/<name>.toml` if there is one,
+/// otherwise the bundled preset of that name
+pub fn load_theme(
+    name: &str,
+    themes_dir: Option<&Path>,
+) -> Result<BTreeMap<String, String>, String> {
+    if let Some(path) = themes_dir.map(|dir| dir.join(format!("{}.toml", name)))
+        && path.exists()
+    {
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read theme {}: {}", path.display(), e))?;
+        return toml::from_str(&contents)
+            .map_err(|e| format!("Failed to parse theme {}: {}", path.display(), e));
+    }
+
+    let (_, preset) = PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .ok_or_else(|| format!("Unknown theme: {}", name))?;
+    toml::from_str(preset).map_err(|e| format!("Failed to parse theme {}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color_specs() {
+        let spec: ColorSpec = "#fae280".parse().unwrap();
+        assert_eq!(spec.color, Color::Rgb(0xfa, 0xe2, 0x80));
+        assert!(spec.modifiers.is_empty());
+
+        assert_eq!(
+            "fae280".parse::<ColorSpec>().unwrap().color,
+            Color::Rgb(0xfa, 0xe2, 0x80)
+        );
+        assert_eq!(
+            "rgb(38, 160, 161)".parse::<ColorSpec>().unwrap().color,
+            Color::Rgb(38, 160, 161)
+        );
+        assert_eq!(
+            "LightCyan".parse::<ColorSpec>().unwrap().color,
+            Color::LightCyan
+        );
+        assert_eq!(
+            "dark-gray".parse::<ColorSpec>().unwrap().color,
+            Color::DarkGray
+        );
+        assert_eq!(
+            "208".parse::<ColorSpec>().unwrap().color,
+            Color::Indexed(208)
+        );
+
+        let spec: ColorSpec = "bold rgb( 1, 2, 3 ) underline".parse().unwrap();
+        assert_eq!(spec.color, Color::Rgb(1, 2, 3));
+        assert_eq!(spec.modifiers, Modifier::BOLD | Modifier::UNDERLINED);
+    }
+
+    #[test]
+    fn test_invalid_color_specs() {
+        assert!("".parse::<ColorSpec>().is_err());
+        assert!("bold".parse::<ColorSpec>().is_err());
+        assert!("red blue".parse::<ColorSpec>().is_err());
+        assert!("#fae28".parse::<ColorSpec>().is_err());
+        assert!("rgb(1, 2, 300)".parse::<ColorSpec>().is_err());
+        assert!("yellow blinking".parse::<ColorSpec>().is_err());
+    }
+
+    #[test]
+    fn test_presets_set_every_role() {
+        for (name, _) in PRESETS {
+            let entries = load_theme(name, None).unwrap();
+            for role in ColorRole::ALL {
+                let value = entries.get(role.key()).unwrap();
+                assert!(value.parse::<ColorSpec>().is_ok(), "{}: {}", name, value);
+            }
+            assert_eq!(entries.len(), ColorRole::ALL.len(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_blend_palettes() {
+        let from = Palette::default();
+        let mut to = Palette::default();
+        to.set(ColorRole::Border, "rgb(0, 0, 0)".parse().unwrap());
+        to.set(ColorRole::Album, "red".parse().unwrap());
+
+        let halfway = from.blend(&to, 0.5);
+        assert_eq!(
+            halfway.get(ColorRole::Border).color,
+            Color::Rgb(125, 113, 64)
+        );
+        assert_eq!(halfway.get(ColorRole::Album).color, Color::Red);
+        assert_eq!(
+            from.blend(&to, 0.25).get(ColorRole::Album),
+            from.get(ColorRole::Album)
+        );
+        assert_eq!(from.blend(&to, 1.0), to);
+    }
+
+    #[test]
+    fn test_apply_skips_invalid_entries() {
+        let mut palette = Palette::default();
+        let entries = BTreeMap::from([
+            ("album".to_string(), "red italic".to_string()),
+            ("artist".to_string(), "not a color".to_string()),
+            ("albun".to_string(), "blue".to_string()),
+        ]);
+        let warnings = palette.apply(&entries, "[colors]");
+
+        assert_eq!(
+            palette.get(ColorRole::Album),
+            ColorSpec {
+                color: Color::Red,
+                modifiers: Modifier::ITALIC
+            }
+        );
+        assert_eq!(
+            palette.get(ColorRole::Artist).color,
+            Color::Rgb(0xd6, 0x77, 0x51)
+        );
+        assert_eq!(warnings.len(), 2);
+    }
+}
@@ -3,8 +3,15 @@ use crate::app::Config;
 use crate::app::DirtyFlags;
 use crate::app::KeyBinds;
 use crate::app::cli::Args;
+use crate::app::main_loop::ConnectionStatus;
+use crate::app::mpd::playback_clock::PlaybackClock;
+use crate::app::mpd::queue_history::QueueHistory;
+use crate::app::session::Session;
+use crate::app::song::{BrowseState, LyricsState, PlaylistsState, SearchState};
+use crate::app::ui::{ListFilter, Marks};
 use crate::app::{MenuMode, PanelFocus};
 use ratatui::widgets::ListState;
+use serde::{Deserialize, Serialize};
 use std::cell::Cell;
 use std::path::PathBuf;
 
@@ -16,43 +23,45 @@ pub trait AppConstructor {
         Self: Sized;
 }
 
-/// Get the path to the state file
-/// - Linux: ~/.local/state/zarumet/state.toml (XDG_STATE_HOME)
-/// - macOS: ~/Library/Application Support/zarumet/state.toml
-/// - Windows: C:\Users\<User>\AppData\Roaming\zarumet\state.toml
-fn get_state_path() -> Option<PathBuf> {
+/// Get the directory for state files
+/// - Linux: ~/.local/state/zarumet (XDG_STATE_HOME)
+/// - macOS: ~/Library/Application Support/zarumet
+/// - Windows: C:\Users\<User>\AppData\Roaming\zarumet
+pub fn get_state_dir() -> Option<PathBuf> {
     // Use state_dir on Linux (XDG_STATE_HOME), fall back to data_dir on other platforms
     let base_dir = dirs::state_dir().or_else(dirs::data_dir)?;
-    Some(base_dir.join("zarumet").join("state.toml"))
+    Some(base_dir.join("zarumet"))
 }
 
-/// Load bit-perfect state from state file
-fn load_bit_perfect_state() -> bool {
-    let state_path = match get_state_path() {
-        Some(path) => path,
-        None => return false,
-    };
+/// Get the path to the state file (`state.toml` in [`get_state_dir`])
+fn get_state_path() -> Option<PathBuf> {
+    Some(get_state_dir()?.join("state.toml"))
+}
 
-    if !state_path.exists() {
-        return false;
-    }
+/// Contents of the state file
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedState {
+    pub bit_perfect: bool,
+    /// Where the UI was on the last quit
+    pub session: Option<Session>,
+}
 
-    let contents = match std::fs::read_to_string(&state_path) {
-        Ok(c) => c,
-        Err(_) => return false,
+/// Load the state file, falling back to defaults if it is missing or unreadable
+fn load_state() -> SavedState {
+    let Some(contents) = get_state_path().and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return SavedState::default();
     };
 
-    // Simple parsing: look for "bit_perfect = true"
-    contents
-        .lines()
-        .find(|line| line.starts_with("bit_perfect"))
-        .and_then(|line| line.split('=').nth(1))
-        .map(|val| val.trim() == "true")
-        .unwrap_or(false)
+    toml::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable state file: {}", e);
+        SavedState::default()
+    })
 }
 
-/// Save bit-perfect state to state file
-pub fn save_bit_perfect_state(enabled: bool) -> std::io::Result<()> {
+/// Save bit-perfect state and the UI session to the state file
+pub fn save_state(state: &SavedState) -> std::io::Result<()> {
     let state_path = match get_state_path() {
         Some(path) => path,
         None => return Ok(()),
@@ -63,7 +72,7 @@ pub fn save_bit_perfect_state(enabled: bool) -> std::io::Result<()> {
         std::fs::create_dir_all(parent)?;
     }
 
-    let contents = format!("bit_perfect = {}\n", enabled);
+    let contents = toml::to_string(state).map_err(std::io::Error::other)?;
     std::fs::write(&state_path, contents)
 }
 
@@ -99,12 +108,16 @@ impl App {
             sequential_bindings,
         );
 
+        let saved_state = load_state();
+
         // Determine bit-perfect state: CLI flag takes priority, then saved state
         let bit_perfect_enabled = match args.bit_perfect {
-            Some(value) => value,             // CLI explicitly set on/off
-            None => load_bit_perfect_state(), // No CLI flag, use saved state
+            Some(value) => value,            // CLI explicitly set on/off
+            None => saved_state.bit_perfect, // No CLI flag, use saved state
         };
 
+        let lyrics = LyricsState::new(config.lyrics.enabled);
+
         Ok(Self {
             running: false,
             current_song: None,
@@ -122,8 +135,10 @@ impl App {
             artists_panel_focus: PanelFocus::Artists, // Default for Artists mode
             albums_panel_focus: PanelFocus::AlbumList, // Default for Albums mode
             library: None,
+            tag_view: None,
             expanded_albums: std::collections::HashSet::new(),
             mpd_status: None,
+            playback_clock: PlaybackClock::default(),
             key_binds,
             bit_perfect_enabled,
             force_update: true, // Force initial update
@@ -133,13 +148,31 @@ impl App {
             last_sample_rate: None,
             last_playlist_version: None,
             last_song_id: None,
+            queue_moved_here: std::collections::HashSet::new(),
             dirty: DirtyFlags::new(),
             library_reload_pending: false,
-            pending_artist_index: None,
             status_message: None,
             update_in_progress: false,
             last_animation_frame: Cell::new(0),
             user_initiated_reload: false,
+            connection_status: ConnectionStatus::Connected,
+            last_reconnect_countdown: None,
+            search: SearchState {
+                input_active: true, // Start typing as soon as Search mode opens
+                ..Default::default()
+            },
+            search_panel_focus: PanelFocus::AlbumList,
+            list_filter: ListFilter::default(),
+            playlists: PlaylistsState::default(),
+            playlists_panel_focus: PanelFocus::AlbumList,
+            browse: BrowseState::default(),
+            terminal_focused: false,
+            lyrics,
+            last_click: None,
+            marks: Marks::default(),
+            queue_history: QueueHistory::default(),
+            saved_session: saved_state.session,
+            palette_fade: None,
         })
     }
 }
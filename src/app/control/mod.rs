@@ -0,0 +1,123 @@
+//! Control socket
+//!
+//! Lets scripts and window manager keybinds drive a running TUI, including UI
+//! only actions such as switching views or toggling bit-perfect mode.
+//! One command per line in, one JSON reply per line out.
+
+pub mod protocol;
+pub mod server;
+
+pub use protocol::{ControlCommand, ControlEvent, ControlState};
+pub use server::ControlServer;
+
+use crate::App;
+use crate::app::MenuMode;
+use crate::app::event_handlers::EventHandlers;
+use crate::app::headless::status::StatusReport;
+use crate::app::mpd_handler::MPDAction;
+use crate::logging::log_user_interaction;
+use color_eyre::eyre::eyre;
+use mpd_client::Client;
+use protocol::{ACTION_NAMES, error_reply, ok_reply};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+
+impl App {
+    /// Current state as reported over the control socket
+    pub fn control_state(&self) -> ControlState {
+        let player = match self.mpd_status {
+            Some(ref status) => StatusReport::new(status, self.current_song.as_ref()),
+            None => StatusReport::default(),
+        };
+        ControlState {
+            player,
+            view: match self.menu_mode {
+                MenuMode::Queue => "queue",
+                MenuMode::Artists => "artists",
+                MenuMode::Albums => "albums",
+                MenuMode::Search => "search",
+                MenuMode::Playlists => "playlists",
+                MenuMode::Browse => "browse",
+            },
+            bit_perfect: self.bit_perfect_enabled,
+            lyrics: self.lyrics.visible,
+            connected: self.connection_status.is_connected(),
+        }
+    }
+
+    /// Carry out a command from the control socket and build the reply
+    pub async fn handle_control_command(
+        &mut self,
+        command: ControlCommand,
+        client: &Client,
+    ) -> String {
+        match command {
+            ControlCommand::Status => ok_reply(Some(serde_json::json!({
+                "state": self.control_state(),
+            }))),
+            ControlCommand::Commands => {
+                let commands: Vec<&str> = ACTION_NAMES
+                    .iter()
+                    .copied()
+                    .chain(["status", "subscribe", "commands"])
+                    .collect();
+                ok_reply(Some(serde_json::json!({ "commands": commands })))
+            }
+            // Subscriptions are kept by the server itself
+            ControlCommand::Subscribe => ok_reply(None),
+            ControlCommand::Action(action) => {
+                let needs_mpd = !matches!(
+                    action,
+                    MPDAction::Quit | MPDAction::ToggleBitPerfect | MPDAction::ToggleLyrics
+                );
+                if needs_mpd && !self.connection_status.is_connected() {
+                    return error_reply("Not connected to MPD");
+                }
+
+                log_user_interaction(&action.to_string(), Some("control socket"));
+                match self.perform_action(action, client).await {
+                    Ok(()) => ok_reply(None),
+                    Err(e) => error_reply(&e.to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Send one command to the running TUI and print the reply;
+/// after `subscribe`, keep printing events until the TUI exits
+pub fn send_command(command: &str) -> color_eyre::Result<()> {
+    let path = ControlServer::socket_path()
+        .ok_or_else(|| eyre!("XDG_RUNTIME_DIR is not set, cannot find the control socket"))?;
+    let mut stream = UnixStream::connect(&path).map_err(|e| {
+        eyre!(
+            "Failed to connect to {} (is Zarumet running?): {}",
+            path.display(),
+            e
+        )
+    })?;
+    writeln!(stream, "{}", command.trim())?;
+
+    let mut lines = BufReader::new(stream).lines();
+    let reply = lines
+        .next()
+        .ok_or_else(|| eyre!("Zarumet closed the connection"))??;
+    println!("{}", reply);
+
+    let value: serde_json::Value = serde_json::from_str(&reply)?;
+    if value["ok"] != true {
+        return Err(eyre!(
+            "{}",
+            value["error"].as_str().unwrap_or("Command failed")
+        ));
+    }
+    if matches!(
+        ControlCommand::parse(command),
+        Ok(ControlCommand::Subscribe)
+    ) {
+        for line in lines {
+            println!("{}", line?);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use crate::app::headless::status::StatusReport;
+use crate::app::mpd_handler::MPDAction;
+use serde::Serialize;
+
+/// Action commands, named like their `[binds]` entries
+pub const ACTION_NAMES: &[&str] = &[
+    "toggle_play_pause",
+    "next",
+    "previous",
+    "seek_forward",
+    "seek_backward",
+    "volume_up",
+    "volume_up_fine",
+    "volume_down",
+    "volume_down_fine",
+    "toggle_mute",
+    "repeat",
+    "random",
+    "single",
+    "consume",
+    "clear_queue",
+    "switch_to_queue_menu",
+    "switch_to_artists",
+    "switch_to_albums",
+    "switch_to_search",
+    "switch_to_playlists",
+    "switch_to_browse",
+    "cycle_mode_left",
+    "cycle_mode_right",
+    "go_to_current",
+    "toggle_bit_perfect",
+    "toggle_lyrics",
+    "refresh",
+    "undo",
+    "redo",
+    "quit",
+];
+
+/// One line received on the control socket
+#[derive(Debug, Clone)]
+pub enum ControlCommand {
+    Action(MPDAction),
+    /// Reply with the current [`ControlState`]
+    Status,
+    /// Keep the connection open and receive [`ControlEvent`]s
+    Subscribe,
+    /// Reply with the list of accepted commands
+    Commands,
+}
+
+impl ControlCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        match line.trim() {
+            "" => Err("Empty command".to_string()),
+            "status" => Ok(Self::Status),
+            "subscribe" => Ok(Self::Subscribe),
+            "commands" | "help" => Ok(Self::Commands),
+            name => action_from_name(name)
+                .map(Self::Action)
+                .ok_or_else(|| format!("Unknown command '{}'", name)),
+        }
+    }
+}
+
+/// Only actions that do not depend on the selection or panel focus are exposed,
+/// since a remote command has no way to see what is selected
+fn action_from_name(name: &str) -> Option<MPDAction> {
+    Some(match name {
+        "toggle_play_pause" => MPDAction::TogglePlayPause,
+        "next" => MPDAction::Next,
+        "previous" => MPDAction::Previous,
+        "seek_forward" => MPDAction::SeekForward,
+        "seek_backward" => MPDAction::SeekBackward,
+        "volume_up" => MPDAction::VolumeUp,
+        "volume_up_fine" => MPDAction::VolumeUpFine,
+        "volume_down" => MPDAction::VolumeDown,
+        "volume_down_fine" => MPDAction::VolumeDownFine,
+        "toggle_mute" => MPDAction::ToggleMute,
+        "repeat" => MPDAction::Repeat,
+        "random" => MPDAction::Random,
+        "single" => MPDAction::Single,
+        "consume" => MPDAction::Consume,
+        "clear_queue" => MPDAction::ClearQueue,
+        "switch_to_queue_menu" => MPDAction::SwitchToQueueMenu,
+        "switch_to_artists" => MPDAction::SwitchToArtists,
+        "switch_to_albums" => MPDAction::SwitchToAlbums,
+        "switch_to_search" => MPDAction::SwitchToSearch,
+        "switch_to_playlists" => MPDAction::SwitchToPlaylists,
+        "switch_to_browse" => MPDAction::SwitchToBrowse,
+        "cycle_mode_left" => MPDAction::CycleModeLeft,
+        "cycle_mode_right" => MPDAction::CycleModeRight,
+        "go_to_current" => MPDAction::GoToCurrent,
+        "toggle_bit_perfect" => MPDAction::ToggleBitPerfect,
+        "toggle_lyrics" => MPDAction::ToggleLyrics,
+        "refresh" => MPDAction::Refresh,
+        "undo" => MPDAction::Undo,
+        "redo" => MPDAction::Redo,
+        "quit" => MPDAction::Quit,
+        _ => return None,
+    })
+}
+
+/// Player and UI state as reported by `status` and in events
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ControlState {
+    #[serde(flatten)]
+    pub player: StatusReport,
+    /// Current view: "queue", "artists", "albums", "search", "playlists" or "browse"
+    pub view: &'static str,
+    pub bit_perfect: bool,
+    pub lyrics: bool,
+    pub connected: bool,
+}
+
+/// What changed between two states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlEvent {
+    /// Another song started, or playback stopped
+    Song,
+    /// Play state, volume or playback options
+    Player,
+    View,
+    BitPerfect,
+    Lyrics,
+    Connection,
+}
+
+impl ControlEvent {
+    /// Events between two states; elapsed time alone is not an event
+    pub fn diff(old: &ControlState, new: &ControlState) -> Vec<Self> {
+        let mut events = Vec::new();
+        if old.connected != new.connected {
+            events.push(Self::Connection);
+        }
+        if old.player.file != new.player.file {
+            events.push(Self::Song);
+        }
+        let player = |state: &ControlState| StatusReport {
+            elapsed: None,
+            ..state.player.clone()
+        };
+        if old.player.file == new.player.file && player(old) != player(new) {
+            events.push(Self::Player);
+        }
+        if old.view != new.view {
+            events.push(Self::View);
+        }
+        if old.bit_perfect != new.bit_perfect {
+            events.push(Self::BitPerfect);
+        }
+        if old.lyrics != new.lyrics {
+            events.push(Self::Lyrics);
+        }
+        events
+    }
+}
+
+/// `{"ok": true, ...}` reply
+pub fn ok_reply(data: Option<serde_json::Value>) -> String {
+    let mut reply = serde_json::json!({ "ok": true });
+    if let Some(serde_json::Value::Object(data)) = data {
+        reply
+            .as_object_mut()
+            .expect("reply is an object")
+            .extend(data);
+    }
+    reply.to_string()
+}
+
+/// `{"ok": false, "error": ...}` reply
+pub fn error_reply(error: &str) -> String {
+    serde_json::json!({ "ok": false, "error": error }).to_string()
+}
+
+/// `{"event": ..., "state": {...}}` line sent to subscribers
+pub fn event_line(event: ControlEvent, state: &ControlState) -> String {
+    serde_json::json!({ "event": event, "state": state }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        for name in ACTION_NAMES {
+            assert!(
+                matches!(ControlCommand::parse(name), Ok(ControlCommand::Action(_))),
+                "{}",
+                name
+            );
+        }
+        assert!(matches!(
+            ControlCommand::parse(" status\r\n"),
+            Ok(ControlCommand::Status)
+        ));
+        assert!(matches!(
+            ControlCommand::parse("toggle_bit_perfect"),
+            Ok(ControlCommand::Action(MPDAction::ToggleBitPerfect))
+        ));
+        // Selection dependent actions are not exposed
+        assert!(ControlCommand::parse("play_selected").is_err());
+        assert!(ControlCommand::parse("").is_err());
+    }
+
+    #[test]
+    fn test_event_diff() {
+        let mut old = ControlState {
+            view: "queue",
+            connected: true,
+            player: StatusReport {
+                state: "playing",
+                file: Some("a.flac".to_string()),
+                elapsed: Some(1.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut new = old.clone();
+        new.player.elapsed = Some(2.0);
+        assert!(ControlEvent::diff(&old, &new).is_empty());
+
+        new.player.file = Some("b.flac".to_string());
+        new.view = "artists";
+        assert_eq!(
+            ControlEvent::diff(&old, &new),
+            vec![ControlEvent::Song, ControlEvent::View]
+        );
+
+        old = new.clone();
+        new.player.state = "paused";
+        new.bit_perfect = true;
+        assert_eq!(
+            ControlEvent::diff(&old, &new),
+            vec![ControlEvent::Player, ControlEvent::BitPerfect]
+        );
+
+        let line = event_line(ControlEvent::BitPerfect, &new);
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["event"], "bit_perfect");
+        assert_eq!(value["state"]["state"], "paused");
+        assert_eq!(value["state"]["bit_perfect"], true);
+    }
+
+    #[test]
+    fn test_replies() {
+        assert_eq!(ok_reply(None), r#"{"ok":true}"#);
+        let reply: serde_json::Value =
+            serde_json::from_str(&ok_reply(Some(serde_json::json!({ "view": "queue" })))).unwrap();
+        assert_eq!(reply["view"], "queue");
+        let reply: serde_json::Value = serde_json::from_str(&error_reply("nope")).unwrap();
+        assert_eq!(reply["ok"], false);
+        assert_eq!(reply["error"], "nope");
+    }
+}
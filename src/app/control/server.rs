@@ -0,0 +1,228 @@
+use super::protocol::{ControlCommand, error_reply, ok_reply};
+use std::io;
+use std::os::unix::fs::DirBuilderExt;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream, unix::OwnedWriteHalf};
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// Events a slow subscriber may fall behind by before it starts missing some
+const EVENT_BUFFER: usize = 64;
+
+/// A command from a socket client, answered by the main loop through `reply`
+#[derive(Debug)]
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub reply: oneshot::Sender<String>,
+}
+
+/// Unix socket that accepts line commands and broadcasts events to subscribers
+pub struct ControlServer {
+    path: PathBuf,
+    requests: mpsc::UnboundedReceiver<ControlRequest>,
+    events: broadcast::Sender<String>,
+    accept_task: JoinHandle<()>,
+}
+
+impl ControlServer {
+    /// `$XDG_RUNTIME_DIR/zarumet/control.sock`
+    pub fn socket_path() -> Option<PathBuf> {
+        dirs::runtime_dir().map(|dir| dir.join("zarumet").join("control.sock"))
+    }
+
+    /// Listen on the default socket path
+    pub fn start() -> io::Result<Self> {
+        let path = Self::socket_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "XDG_RUNTIME_DIR is not set"))?;
+        Self::start_at(path)
+    }
+
+    pub fn start_at(path: PathBuf) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(parent)?;
+        }
+
+        // A socket left behind by a crashed instance is replaced, a live one is not
+        if path.exists() {
+            if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("another instance is listening on {}", path.display()),
+                ));
+            }
+            std::fs::remove_file(&path)?;
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        let (request_tx, requests) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+
+        let accept_events = events.clone();
+        let accept_task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve_client(
+                            stream,
+                            request_tx.clone(),
+                            accept_events.clone(),
+                        ));
+                    }
+                    Err(e) => {
+                        log::warn!("Control socket stopped accepting connections: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        log::info!("Control socket listening on {}", path.display());
+        Ok(Self {
+            path,
+            requests,
+            events,
+            accept_task,
+        })
+    }
+
+    /// Next pending request, without waiting
+    pub fn try_recv_request(&mut self) -> Option<ControlRequest> {
+        self.requests.try_recv().ok()
+    }
+
+    /// Send a line to every subscribed client
+    pub fn broadcast(&self, line: String) {
+        // Fails only when nobody is subscribed
+        let _ = self.events.send(line);
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+enum Incoming {
+    Line(io::Result<Option<String>>),
+    Event(Result<String, broadcast::error::RecvError>),
+}
+
+/// Answer one client's commands until it disconnects
+async fn serve_client(
+    stream: UnixStream,
+    requests: mpsc::UnboundedSender<ControlRequest>,
+    events: broadcast::Sender<String>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut subscription: Option<broadcast::Receiver<String>> = None;
+
+    loop {
+        let event = async {
+            match subscription.as_mut() {
+                Some(receiver) => receiver.recv().await,
+                None => std::future::pending().await,
+            }
+        };
+        let incoming = tokio::select! {
+            line = lines.next_line() => Incoming::Line(line),
+            event = event => Incoming::Event(event),
+        };
+
+        let reply = match incoming {
+            Incoming::Line(Ok(Some(line))) => match ControlCommand::parse(&line) {
+                Ok(ControlCommand::Subscribe) => {
+                    subscription.get_or_insert_with(|| events.subscribe());
+                    ok_reply(None)
+                }
+                Ok(command) => {
+                    let (reply_tx, reply_rx) = oneshot::channel();
+                    let request = ControlRequest {
+                        command,
+                        reply: reply_tx,
+                    };
+                    if requests.send(request).is_err() {
+                        return;
+                    }
+                    reply_rx
+                        .await
+                        .unwrap_or_else(|_| error_reply("Zarumet is shutting down"))
+                }
+                Err(e) => error_reply(&e),
+            },
+            // Client closed the connection
+            Incoming::Line(Ok(None) | Err(_)) => return,
+            Incoming::Event(Ok(line)) => line,
+            Incoming::Event(Err(broadcast::error::RecvError::Lagged(missed))) => {
+                log::debug!("Control client missed {} events", missed);
+                continue;
+            }
+            Incoming::Event(Err(broadcast::error::RecvError::Closed)) => return,
+        };
+
+        if write_line(&mut writer, &reply).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn write_line(writer: &mut OwnedWriteHalf, line: &str) -> io::Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::mpd_handler::MPDAction;
+
+    #[tokio::test]
+    async fn test_requests_and_events() {
+        let path = std::env::temp_dir().join(format!("zarumet-test-{}.sock", std::process::id()));
+        let mut server = ControlServer::start_at(path.clone()).unwrap();
+        // A second instance must not take over the socket
+        assert!(ControlServer::start_at(path.clone()).is_err());
+
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        write_line(&mut writer, "toggle_bit_perfect").await.unwrap();
+        let request = loop {
+            if let Some(request) = server.try_recv_request() {
+                break request;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert!(matches!(
+            request.command,
+            ControlCommand::Action(MPDAction::ToggleBitPerfect)
+        ));
+        request.reply.send(ok_reply(None)).unwrap();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), r#"{"ok":true}"#);
+
+        write_line(&mut writer, "bogus").await.unwrap();
+        assert!(
+            lines
+                .next_line()
+                .await
+                .unwrap()
+                .unwrap()
+                .contains("Unknown command")
+        );
+
+        write_line(&mut writer, "subscribe").await.unwrap();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), r#"{"ok":true}"#);
+        server.broadcast("event".to_string());
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "event");
+
+        drop(server);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,135 @@
+//! Headless mode: run one command against MPD and exit (`zarumet next`, `zarumet status`, ...)
+
+pub mod status;
+pub mod watch;
+
+use crate::app::Config;
+use crate::app::cli::{Command, SeekTarget, VolumeChange};
+use crate::app::main_loop::connect_to_mpd;
+use crate::app::mpd_handler::{MPDAction, permission_denied_message};
+use color_eyre::eyre::eyre;
+use mpd_client::{
+    Client,
+    client::CommandError,
+    commands::{self, SeekMode},
+    protocol::command::Command as RawCommand,
+};
+use status::{DEFAULT_FORMAT, StatusReport};
+use std::io::BufRead;
+use std::time::Duration;
+use watch::WatchOptions;
+
+/// Connect with the configured address and password, then run `command`
+pub async fn run(command: Command, config: &Config) -> color_eyre::Result<()> {
+    // Watch mode keeps its own connection alive across MPD restarts
+    if let Command::Watch {
+        format,
+        tooltip,
+        json,
+        interval,
+    } = command
+    {
+        let options = WatchOptions {
+            format,
+            tooltip,
+            json,
+            interval: interval.filter(|&secs| secs > 0).map(Duration::from_secs),
+        };
+        return watch::watch(&options, config).await;
+    }
+    // Talks to the running TUI rather than to MPD
+    if let Command::Ctl { command } = command {
+        #[cfg(unix)]
+        return crate::app::control::send_command(&command);
+        #[cfg(not(unix))]
+        return Err(eyre!("The control socket is only available on Unix"));
+    }
+
+    let password = config.mpd.resolve_password()?;
+    let (client, _state_changes) = connect_to_mpd(config.mpd.host(), password.as_deref())
+        .await
+        .map_err(|e| eyre!("Failed to connect to MPD at {}: {}", config.mpd.host(), e))?;
+
+    execute(command, &client, config)
+        .await
+        .map_err(|e| match permission_denied_message(&e) {
+            Some(message) => eyre!(message),
+            None => eyre!("MPD error: {}", e),
+        })
+}
+
+async fn execute(command: Command, client: &Client, config: &Config) -> Result<(), CommandError> {
+    match command {
+        Command::Play => client.command(commands::Play::current()).await?,
+        Command::Pause => client.command(commands::SetPause(true)).await?,
+        Command::Toggle => {
+            MPDAction::TogglePlayPause
+                .execute(client, config, None)
+                .await?
+        }
+        Command::Stop => client.command(commands::Stop).await?,
+        Command::Next => MPDAction::Next.execute(client, config, None).await?,
+        Command::Prev => MPDAction::Previous.execute(client, config, None).await?,
+        Command::Volume { change } => match change {
+            VolumeChange::Up => MPDAction::VolumeUp.execute(client, config, None).await?,
+            VolumeChange::Down => MPDAction::VolumeDown.execute(client, config, None).await?,
+            VolumeChange::Set(volume) => client.command(commands::SetVolume(volume)).await?,
+            VolumeChange::Relative(delta) => {
+                let current = client.command(commands::Status).await?.volume as i16;
+                let volume = (current + delta).clamp(0, 100) as u8;
+                client.command(commands::SetVolume(volume)).await?
+            }
+        },
+        Command::Seek { target } => {
+            let mode = match target {
+                SeekTarget::Absolute(position) => SeekMode::Absolute(position),
+                SeekTarget::Forward(offset) => SeekMode::Forward(offset),
+                SeekTarget::Backward(offset) => SeekMode::Backward(offset),
+            };
+            client.command(commands::Seek(mode)).await?
+        }
+        Command::Random { state: None } => MPDAction::Random.execute(client, config, None).await?,
+        Command::Random { state: Some(on) } => client.command(commands::SetRandom(on)).await?,
+        Command::Repeat { state: None } => MPDAction::Repeat.execute(client, config, None).await?,
+        Command::Repeat { state: Some(on) } => client.command(commands::SetRepeat(on)).await?,
+        Command::Add { uris } => {
+            let uris = if uris.is_empty() {
+                // `mpc ls | zarumet add` style
+                std::io::stdin()
+                    .lock()
+                    .lines()
+                    .map_while(Result::ok)
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect()
+            } else {
+                uris
+            };
+            // `add` (unlike `addid`) also takes whole directories
+            for uri in uris {
+                client
+                    .raw_command(RawCommand::new("add").argument(uri))
+                    .await?;
+            }
+        }
+        Command::Clear => MPDAction::ClearQueue.execute(client, config, None).await?,
+        Command::Status { format, json } => {
+            let report = StatusReport::fetch(client).await?;
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string(&report).expect("status report serializes")
+                );
+            } else {
+                println!(
+                    "{}",
+                    report.render(format.as_deref().unwrap_or(DEFAULT_FORMAT))
+                );
+            }
+        }
+        Command::Watch { .. } | Command::Ctl { .. } => {
+            unreachable!("watch and ctl are handled by run()")
+        }
+    }
+    Ok(())
+}
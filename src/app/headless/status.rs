@@ -0,0 +1,202 @@
+use crate::app::SongInfo;
+use mpd_client::{
+    Client,
+    client::CommandError,
+    commands::{self, SingleMode},
+    responses::{PlayState, Status},
+};
+use serde::Serialize;
+
+/// Template used by `zarumet status` without `--format`
+pub const DEFAULT_FORMAT: &str = "{artist} - {title}\n\
+    [{state}] #{position}/{queue_length} {elapsed}/{duration} ({progress}%)\n\
+    volume: {volume}%  repeat: {repeat}  random: {random}  single: {single}  consume: {consume}";
+
+/// Snapshot of the player state for scripts and status bars
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StatusReport {
+    /// "playing", "paused" or "stopped"
+    pub state: &'static str,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub file: Option<String>,
+    /// Elapsed time in seconds
+    pub elapsed: Option<f64>,
+    /// Song length in seconds
+    pub duration: Option<f64>,
+    /// 1-based position of the current song in the queue
+    pub position: Option<usize>,
+    pub queue_length: usize,
+    pub volume: u8,
+    pub repeat: bool,
+    pub random: bool,
+    /// "on", "off" or "oneshot"
+    pub single: &'static str,
+    pub consume: bool,
+}
+
+impl StatusReport {
+    pub fn new(status: &Status, song: Option<&SongInfo>) -> Self {
+        Self {
+            state: state_name(status.state),
+            title: song.map(|s| s.title.clone()),
+            artist: song.map(|s| s.artist.clone()),
+            album: song.map(|s| s.album.clone()),
+            file: song.map(|s| s.file_path.to_string_lossy().into_owned()),
+            elapsed: status.elapsed.map(|d| d.as_secs_f64()),
+            duration: status
+                .duration
+                .or_else(|| song.and_then(|s| s.duration))
+                .map(|d| d.as_secs_f64()),
+            position: status.current_song.map(|(position, _)| position.0 + 1),
+            queue_length: status.playlist_length,
+            volume: status.volume,
+            repeat: status.repeat,
+            random: status.random,
+            single: match status.single {
+                SingleMode::Enabled => "on",
+                SingleMode::Disabled => "off",
+                SingleMode::Oneshot => "oneshot",
+            },
+            consume: status.consume,
+        }
+    }
+
+    /// Fetch the status and current song from MPD
+    pub async fn fetch(client: &Client) -> Result<Self, CommandError> {
+        let status = client.command(commands::Status).await?;
+        let song = client
+            .command(commands::CurrentSong)
+            .await?
+            .map(|song_in_queue| SongInfo::from_song(&song_in_queue.song));
+        Ok(Self::new(&status, song.as_ref()))
+    }
+
+    /// Value of a `{placeholder}`, None for unknown names
+    fn field(&self, name: &str) -> Option<String> {
+        let on_off = |value: bool| if value { "on" } else { "off" }.to_string();
+        Some(match name {
+            "state" => self.state.to_string(),
+            "title" => self.title.clone().unwrap_or_default(),
+            "artist" => self.artist.clone().unwrap_or_default(),
+            "album" => self.album.clone().unwrap_or_default(),
+            "file" => self.file.clone().unwrap_or_default(),
+            "elapsed" => format_time(self.elapsed),
+            "duration" => format_time(self.duration),
+            "progress" => match (self.elapsed, self.duration) {
+                (Some(elapsed), Some(duration)) if duration > 0.0 => {
+                    format!("{:.0}", elapsed / duration * 100.0)
+                }
+                _ => "0".to_string(),
+            },
+            "position" => self.position.map(|p| p.to_string()).unwrap_or_default(),
+            "queue_length" => self.queue_length.to_string(),
+            "volume" => self.volume.to_string(),
+            "repeat" => on_off(self.repeat),
+            "random" => on_off(self.random),
+            "single" => self.single.to_string(),
+            "consume" => on_off(self.consume),
+            _ => return None,
+        })
+    }
+
+    /// Fill in the `{placeholders}` of a template; `\n` and `\t` are unescaped,
+    /// unknown placeholders are kept as they are
+    pub fn render(&self, template: &str) -> String {
+        let template = template.replace("\\n", "\n").replace("\\t", "\t");
+        let mut output = String::with_capacity(template.len());
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find('{') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            match after
+                .find('}')
+                .and_then(|end| self.field(&after[..end]).map(|value| (end, value)))
+            {
+                Some((end, value)) => {
+                    output.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    output.push('{');
+                    rest = after;
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+fn state_name(state: PlayState) -> &'static str {
+    match state {
+        PlayState::Playing => "playing",
+        PlayState::Paused => "paused",
+        PlayState::Stopped => "stopped",
+    }
+}
+
+/// "M:SS", or "H:MM:SS" for an hour and more
+fn format_time(seconds: Option<f64>) -> String {
+    let secs = seconds.unwrap_or(0.0) as u64;
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> StatusReport {
+        StatusReport {
+            state: "playing",
+            title: Some("Song".to_string()),
+            artist: Some("Someone".to_string()),
+            album: Some("Album".to_string()),
+            file: Some("Someone/Album/01.flac".to_string()),
+            elapsed: Some(75.4),
+            duration: Some(3725.0),
+            position: Some(3),
+            queue_length: 12,
+            volume: 80,
+            repeat: true,
+            random: false,
+            single: "off",
+            consume: false,
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        let report = report();
+        assert_eq!(
+            report.render("{artist} - {title} [{elapsed}/{duration}]"),
+            "Someone - Song [1:15/1:02:05]"
+        );
+        assert_eq!(
+            report.render("#{position}/{queue_length}\\n{progress}% {repeat}/{random}"),
+            "#3/12\n2% on/off"
+        );
+        // Unknown placeholders and stray braces are left alone
+        assert_eq!(report.render("{nope} {title"), "{nope} {title");
+    }
+
+    #[test]
+    fn test_stopped_report() {
+        let report = StatusReport {
+            state: "stopped",
+            single: "off",
+            ..Default::default()
+        };
+        assert_eq!(report.render("{title}|{elapsed}|{progress}"), "|0:00|0");
+        assert_eq!(
+            serde_json::to_value(&report).unwrap()["title"],
+            serde_json::Value::Null
+        );
+    }
+}
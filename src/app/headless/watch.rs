@@ -0,0 +1,186 @@
+use super::status::StatusReport;
+use crate::app::Config;
+use crate::app::main_loop::connect_to_mpd;
+use crate::app::main_loop::reconnect::reconnect_delay;
+use mpd_client::{
+    Client,
+    client::{ConnectionEvent, ConnectionEvents, Subsystem},
+};
+use std::time::Duration;
+
+/// Options of `zarumet watch`
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Template for the text
+    pub format: String,
+    /// Template for the tooltip (JSON output only)
+    pub tooltip: String,
+    /// Print waybar / i3blocks JSON instead of plain text
+    pub json: bool,
+    /// Extra refresh interval while playing, so `{elapsed}` moves
+    pub interval: Option<Duration>,
+}
+
+/// Print a line now and after every change, reconnecting whenever the connection drops
+pub async fn watch(options: &WatchOptions, config: &Config) -> color_eyre::Result<()> {
+    let mut last_line = None;
+    let mut attempt = 0;
+    loop {
+        let password = config.mpd.resolve_password()?;
+        match connect_to_mpd(config.mpd.host(), password.as_deref()).await {
+            Ok((client, events)) => {
+                attempt = 0;
+                follow(&client, events, options, &mut last_line).await;
+            }
+            Err(e) => log::debug!("Watch: failed to connect to MPD: {}", e),
+        }
+
+        print_line(format_line(None, options), &mut last_line);
+        tokio::time::sleep(reconnect_delay(attempt)).await;
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+/// Print status lines until the connection closes
+async fn follow(
+    client: &Client,
+    mut events: ConnectionEvents,
+    options: &WatchOptions,
+    last_line: &mut Option<String>,
+) {
+    let mut refresh = options.interval.map(tokio::time::interval);
+    loop {
+        let playing = match StatusReport::fetch(client).await {
+            Ok(report) => {
+                print_line(format_line(Some(&report), options), last_line);
+                report.state == "playing"
+            }
+            Err(e) => {
+                log::debug!("Watch: failed to fetch status: {}", e);
+                return;
+            }
+        };
+
+        // Wait for something worth printing
+        loop {
+            let tick = async {
+                match refresh.as_mut() {
+                    Some(interval) if playing => interval.tick().await,
+                    _ => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(ConnectionEvent::SubsystemChange(
+                        Subsystem::Player | Subsystem::Mixer | Subsystem::Options | Subsystem::Queue,
+                    )) => break,
+                    Some(ConnectionEvent::SubsystemChange(_)) => {}
+                    Some(ConnectionEvent::ConnectionClosed(_)) | None => return,
+                },
+                _ = tick => break,
+            }
+        }
+    }
+}
+
+/// Print unless it is the same as the previous line
+fn print_line(line: String, last_line: &mut Option<String>) {
+    if last_line.as_ref() != Some(&line) {
+        println!("{}", line);
+        *last_line = Some(line);
+    }
+}
+
+/// One output line; `report` is None while disconnected
+fn format_line(report: Option<&StatusReport>, options: &WatchOptions) -> String {
+    // Nothing to show without a current song
+    let text = report
+        .filter(|report| report.file.is_some())
+        .map(|report| report.render(&options.format))
+        .unwrap_or_default();
+    if !options.json {
+        return text;
+    }
+
+    let tooltip = match report {
+        Some(report) if report.file.is_some() => report.render(&options.tooltip),
+        Some(_) => String::new(),
+        None => "MPD disconnected".to_string(),
+    };
+    let class = report.map_or("disconnected", |report| report.state);
+    let percentage = report
+        .and_then(|report| Some((report.elapsed?, report.duration?)))
+        .filter(|(_, duration)| *duration > 0.0)
+        .map_or(0, |(elapsed, duration)| (elapsed / duration * 100.0) as u8);
+
+    // waybar reads text/tooltip/class/alt/percentage, i3blocks reads full_text
+    serde_json::json!({
+        "text": text,
+        "full_text": text,
+        "tooltip": tooltip,
+        "class": class,
+        "alt": class,
+        "percentage": percentage,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(json: bool) -> WatchOptions {
+        WatchOptions {
+            format: "{artist} - {title}".to_string(),
+            tooltip: "{album}\\n{elapsed}/{duration}".to_string(),
+            json,
+            interval: None,
+        }
+    }
+
+    fn report(state: &'static str) -> StatusReport {
+        StatusReport {
+            state,
+            title: Some("Song".to_string()),
+            artist: Some("Someone".to_string()),
+            album: Some("Album".to_string()),
+            file: Some("Someone/Album/01.flac".to_string()),
+            elapsed: Some(30.0),
+            duration: Some(120.0),
+            single: "off",
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plain_line() {
+        assert_eq!(
+            format_line(Some(&report("playing")), &options(false)),
+            "Someone - Song"
+        );
+        // Stopped with an empty queue, or disconnected
+        let empty = StatusReport {
+            state: "stopped",
+            ..Default::default()
+        };
+        assert_eq!(format_line(Some(&empty), &options(false)), "");
+        assert_eq!(format_line(None, &options(false)), "");
+    }
+
+    #[test]
+    fn test_json_line() {
+        let line = format_line(Some(&report("paused")), &options(true));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["text"], "Someone - Song");
+        assert_eq!(value["full_text"], "Someone - Song");
+        assert_eq!(value["tooltip"], "Album\n0:30/2:00");
+        assert_eq!(value["class"], "paused");
+        assert_eq!(value["percentage"], 25);
+        assert!(!line.contains('\n'));
+
+        let line = format_line(None, &options(true));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["class"], "disconnected");
+        assert_eq!(value["text"], "");
+    }
+}
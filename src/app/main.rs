@@ -1,7 +1,16 @@
+use crate::app::main_loop::ConnectionStatus;
+use crate::app::main_loop::cover_theme::PaletteFade;
+use crate::app::mpd::playback_clock::PlaybackClock;
+use crate::app::mpd::queue_history::QueueHistory;
+use crate::app::mpd_handler::permission_denied_message;
+use crate::app::session::Session;
+use crate::app::song::{BrowseState, LyricsState, PlaylistsState, SearchState, TagViewState};
+use crate::app::ui::{ListFilter, ListKind, Marks};
 use crate::app::{
     Cell, Config, DirtyFlags, KeyBinds, LazyLibrary, ListState, MenuMode, PanelFocus, PlayState,
     SongInfo,
 };
+use mpd_client::client::CommandError;
 
 #[derive(Debug, Clone)]
 pub struct StatusMessage {
@@ -15,6 +24,8 @@ pub enum MessageType {
     InProgress,
     Success,
     Error,
+    /// Shows its text, like `Error`
+    Info,
 }
 
 /// The main application which holds the state and logic of the application.
@@ -49,10 +60,14 @@ pub struct App {
     pub albums_panel_focus: PanelFocus,
     /// Music library (lazy-loaded)
     pub library: Option<LazyLibrary>,
+    /// `[browse]` view shown in the Artists view instead of the library, if any
+    pub tag_view: Option<TagViewState>,
     /// Expanded albums (tracks which albums are currently expanded)
     pub expanded_albums: std::collections::HashSet<(String, String)>, // (artist_name, album_name)
     /// Current MPD status information
     pub mpd_status: Option<mpd_client::responses::Status>,
+    /// Moves the elapsed time on between status updates
+    pub playback_clock: PlaybackClock,
     /// Key bindings handler
     pub key_binds: KeyBinds,
     /// Bit-perfect mode enabled (PipeWire sample rate matching)
@@ -71,12 +86,12 @@ pub struct App {
     pub last_playlist_version: Option<u32>,
     /// Last known song ID from MPD (to skip refetching same song)
     pub last_song_id: Option<mpd_client::commands::SongId>,
+    /// Queue IDs of songs moved on our copy of the queue too, until the next sync
+    pub queue_moved_here: std::collections::HashSet<u64>,
     /// Dirty flags for optimized rendering (tracks which UI regions need redraw)
     pub dirty: DirtyFlags,
     /// Flag to indicate library reload is needed
     pub library_reload_pending: bool,
-    /// Previous artist index to restore after library reload
-    pub pending_artist_index: Option<String>,
     /// Library reload status message
     pub status_message: Option<StatusMessage>,
     /// Track if update is in progress to avoid overlapping updates
@@ -85,6 +100,36 @@ pub struct App {
     pub last_animation_frame: Cell<u64>,
     /// Track if current reload was user-initiated (for status messages)
     pub user_initiated_reload: bool,
+    /// Connection state to MPD (connected or waiting to reconnect)
+    pub connection_status: ConnectionStatus,
+    /// Last rendered reconnect countdown in seconds (to redraw once per second)
+    pub last_reconnect_countdown: Option<u64>,
+    /// Search view state (query, results and list selections)
+    pub search: SearchState,
+    /// Cached panel focus for Search mode (restored when switching back)
+    pub search_panel_focus: PanelFocus,
+    /// `/` filter state for list panels
+    pub list_filter: ListFilter,
+    /// Stored playlists view state (names, contents and open prompt)
+    pub playlists: PlaylistsState,
+    /// Cached panel focus for Playlists mode (restored when switching back)
+    pub playlists_panel_focus: PanelFocus,
+    /// Directory browser state (current directory, entries and selection)
+    pub browse: BrowseState,
+    /// Whether the terminal has focus (from focus change events, assumed unfocused until one arrives)
+    pub terminal_focused: bool,
+    /// Lyrics pane state (lyrics of the current song, shown or hidden)
+    pub lyrics: LyricsState,
+    /// Time and list item of the last click, to detect double-clicks
+    pub last_click: Option<(std::time::Instant, ListKind, usize)>,
+    /// Items marked for bulk operations
+    pub marks: Marks,
+    /// Queue snapshots for undoing and redoing queue changes
+    pub queue_history: QueueHistory,
+    /// Session saved on the last quit, restored once the library and queue are loaded
+    pub saved_session: Option<Session>,
+    /// Running fade to the colors of a new cover, with the dynamic theme
+    pub palette_fade: Option<PaletteFade>,
 }
 
 impl App {
@@ -93,6 +138,18 @@ impl App {
         self.dirty.mark_status_message();
     }
 
+    /// Log a failed MPD command and show permission errors in the status area
+    pub fn report_mpd_error(&mut self, context: &str, error: &CommandError) {
+        log::error!("{}: {}", context, error);
+        if let Some(text) = permission_denied_message(error) {
+            self.set_status_message(StatusMessage {
+                text,
+                created_at: std::time::Instant::now(),
+                message_type: MessageType::Error,
+            });
+        }
+    }
+
     pub fn clear_status_message(&mut self) {
         self.status_message = None;
         self.dirty.mark_status_message();
@@ -1,12 +1,15 @@
 use mpd_client::Client;
+use mpd_client::client::ConnectWithPasswordError;
 use tokio::net::TcpStream;
 
 #[cfg(unix)]
 use tokio::net::UnixStream;
 
-/// Connect to MPD via Unix socket or TCP based on address format
+/// Connect to MPD via Unix socket or TCP based on address format.
+/// The password, if any, is sent right after the handshake.
 pub async fn connect_to_mpd(
     address: &str,
+    password: Option<&str>,
 ) -> color_eyre::Result<(Client, mpd_client::client::ConnectionEvents)> {
     let is_unix_socket = address.contains('/');
 
@@ -14,7 +17,9 @@ pub async fn connect_to_mpd(
         #[cfg(unix)]
         {
             let connection = UnixStream::connect(address).await?;
-            Ok(Client::connect(connection).await?)
+            Client::connect_with_password_opt(connection, password)
+                .await
+                .map_err(password_error)
         }
         #[cfg(not(unix))]
         {
@@ -24,6 +29,17 @@ pub async fn connect_to_mpd(
         }
     } else {
         let connection = TcpStream::connect(address).await?;
-        Ok(Client::connect(connection).await?)
+        Client::connect_with_password_opt(connection, password)
+            .await
+            .map_err(password_error)
+    }
+}
+
+fn password_error(error: ConnectWithPasswordError) -> color_eyre::Report {
+    match error {
+        ConnectWithPasswordError::IncorrectPassword => {
+            color_eyre::eyre::eyre!("MPD rejected the configured password")
+        }
+        ConnectWithPasswordError::ProtocolError(e) => color_eyre::Report::new(e),
     }
 }
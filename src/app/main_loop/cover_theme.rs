@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::App;
+use crate::app::config::theme::Palette;
+use crate::app::ui::cache::cover_cache::SharedCoverCache;
+use crate::app::ui::cover_palette::CoverPalette;
+
+/// How long the colors take to fade to those of the next cover
+const FADE_DURATION: Duration = Duration::from_millis(400);
+
+/// Fade of the UI colors from one palette to another
+#[derive(Debug)]
+pub struct PaletteFade {
+    from: Palette,
+    to: Palette,
+    started: Instant,
+}
+
+impl App {
+    /// Fade the UI colors to those of the current song's cover, or to the theme's
+    /// for songs without one. Extracted colors are kept in the cover cache.
+    pub async fn update_cover_palette(
+        &mut self,
+        cover: Option<&image::DynamicImage>,
+        file_path: &PathBuf,
+        cache: &SharedCoverCache,
+    ) {
+        if !self.config.colors.dynamic {
+            return;
+        }
+
+        let cached = cache.read().await.palette(file_path).cloned();
+        let cover_palette = match cached {
+            Some(palette) => Some(palette),
+            None => {
+                let palette = cover.and_then(CoverPalette::extract);
+                if let Some(ref palette) = palette {
+                    cache.write().await.set_palette(file_path, palette.clone());
+                }
+                palette
+            }
+        };
+
+        let target = match cover_palette {
+            Some(palette) => palette.apply_to(self.config.colors.theme_palette()),
+            None => self.config.colors.theme_palette().clone(),
+        };
+        self.fade_to_palette(target);
+    }
+
+    /// Fade back to the theme's colors, e.g. when nothing is playing
+    pub fn reset_cover_palette(&mut self) {
+        if self.config.colors.dynamic {
+            self.fade_to_palette(self.config.colors.theme_palette().clone());
+        }
+    }
+
+    /// Start fading to `target`, unless the colors already are or are fading there
+    fn fade_to_palette(&mut self, target: Palette) {
+        let current_target = match self.palette_fade {
+            Some(ref fade) => &fade.to,
+            None => self.config.colors.palette(),
+        };
+        if *current_target == target {
+            return;
+        }
+        self.palette_fade = Some(PaletteFade {
+            from: self.config.colors.palette().clone(),
+            to: target,
+            started: Instant::now(),
+        });
+    }
+
+    /// Move a running fade along, called on every loop iteration
+    pub fn step_palette_fade(&mut self) {
+        let Some(ref fade) = self.palette_fade else {
+            return;
+        };
+        let t = fade.started.elapsed().as_secs_f32() / FADE_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            if let Some(fade) = self.palette_fade.take() {
+                self.config.colors.set_palette(fade.to);
+            }
+        } else {
+            let palette = fade.from.blend(&fade.to, t);
+            self.config.colors.set_palette(palette);
+        }
+        // Every widget takes its colors from the palette
+        self.dirty.mark_full_redraw();
+    }
+}
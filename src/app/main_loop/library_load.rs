@@ -0,0 +1,228 @@
+use crate::App;
+use crate::app::navigation::helpers::anchor_offset;
+use crate::app::session::Session;
+use crate::app::song::{LazyLibrary, library_cache};
+use crate::app::ui::compute_album_display_list;
+use mpd_client::Client;
+
+use tokio::sync::mpsc;
+
+/// Message type for background library loading results
+pub enum LibraryMessage {
+    Loaded(LazyLibrary),
+}
+
+/// Spawn a background task that checks the library snapshot against the server's
+/// database. When it is missing or out of date, the whole library is fetched, saved as
+/// the new snapshot and sent back.
+pub fn spawn_library_revalidation(
+    client: &Client,
+    host: String,
+    snapshot_db_update: Option<u64>,
+    tx: mpsc::Sender<LibraryMessage>,
+) {
+    let client = client.clone();
+
+    tokio::spawn(async move {
+        let db_update = match library_cache::db_update(&client).await {
+            Ok(db_update) => db_update,
+            Err(e) => {
+                log::warn!("Failed to check the library snapshot: {}", e);
+                return;
+            }
+        };
+        if snapshot_db_update == Some(db_update) {
+            log::debug!("Library snapshot is up to date");
+            return;
+        }
+
+        log::info!("Library snapshot is missing or out of date, rebuilding it...");
+        let mut library = match LazyLibrary::init(&client).await {
+            Ok(library) => library,
+            Err(e) => {
+                log::warn!("Failed to rebuild the library snapshot: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = library.preload_all_albums(&client).await {
+            log::warn!("Failed to rebuild the library snapshot: {}", e);
+            return;
+        }
+
+        let saved = tokio::task::spawn_blocking(move || {
+            if let Err(e) = library_cache::save(&host, db_update, &library) {
+                log::warn!("Failed to save the library snapshot: {}", e);
+            }
+            library
+        })
+        .await;
+        if let Ok(library) = saved {
+            let _ = tx.send(LibraryMessage::Loaded(library)).await;
+        }
+    });
+}
+
+impl App {
+    /// Replace the library with a freshly loaded one, keeping what was selected
+    pub fn install_library(&mut self, library: LazyLibrary) {
+        // Selections of the library, put aside while a tag view is open
+        self.swap_default_panels();
+        let before = self.library_selection();
+        self.library = Some(library);
+        self.reselect_library(before);
+        self.swap_default_panels();
+    }
+
+    /// Bring the library up to date after a database update. Only changed artists are
+    /// fetched again, and selections, expanded albums and scroll positions are kept.
+    /// An open tag view is listed again.
+    pub async fn refresh_library(&mut self, client: &Client) -> color_eyre::Result<()> {
+        self.swap_default_panels();
+        let result = self.refresh_default_library(client).await;
+        self.swap_default_panels();
+        self.reload_tag_view(client).await;
+        result
+    }
+
+    async fn refresh_default_library(&mut self, client: &Client) -> color_eyre::Result<()> {
+        let before = self.library_selection();
+        let old_db_update = self.library.as_ref().and_then(|library| library.db_update);
+        match self.library.as_mut() {
+            Some(library) => library.refresh(client).await?,
+            None => self.library = Some(LazyLibrary::init(client).await?),
+        }
+        self.reselect_library(before);
+
+        // Keep the snapshot in step, or the next start would load a stale one and rebuild it
+        if let Some(library) = self.library.as_ref()
+            && library.all_albums_complete
+            && let Some(db_update) = library.db_update
+            && old_db_update != Some(db_update)
+        {
+            let host = self.config.mpd.host().to_string();
+            let library = library.clone();
+            tokio::task::spawn_blocking(move || {
+                if let Err(e) = library_cache::save(&host, db_update, &library) {
+                    log::warn!("Failed to save the library snapshot: {}", e);
+                }
+            });
+        }
+
+        // The selected artist may be new, or its albums were dropped
+        if let Some(idx) = self.artist_list_state.selected()
+            && let Some(library) = self.library.as_mut()
+            && let Err(e) = library.load_artist(client, idx).await
+        {
+            log::warn!("Failed to load artist after refresh: {}", e);
+        }
+        self.dirty.mark_library();
+        Ok(())
+    }
+
+    fn library_selection(&self) -> LibrarySelection {
+        LibrarySelection {
+            session: self.session(),
+            artist_row: self.artist_list_state.selected(),
+            album_row: self.all_albums_list_state.selected(),
+        }
+    }
+
+    /// Select again, by name, what was selected before the library changed. Scroll
+    /// positions move with the selection so it stays on the same line.
+    fn reselect_library(&mut self, before: LibrarySelection) {
+        self.clear_library_marks();
+        self.dirty.mark_library();
+        let Some(library) = self.library.as_ref() else {
+            return;
+        };
+        let session = before.session;
+
+        // Artists view
+        let artist_idx = session
+            .artist
+            .as_ref()
+            .and_then(|name| library.artists.iter().position(|a| a.name == *name));
+        match artist_idx {
+            Some(idx) => {
+                self.artist_list_state.select(Some(idx));
+                anchor_offset(
+                    &mut self.artist_list_state,
+                    before.artist_row,
+                    session.artists_offset,
+                );
+                self.restore_artist_row(session.artist_album, session.artist_song);
+            }
+            None => {
+                // The artist is gone, so are its album rows
+                let first = (!library.artists.is_empty()).then_some(0);
+                self.artist_list_state.select(first);
+                self.album_list_state.select(None);
+                self.album_display_list_state.select(None);
+            }
+        }
+
+        let Some(library) = self.library.as_ref() else {
+            return;
+        };
+
+        // Keep the album row in range if the artist's albums changed
+        let rows = self
+            .artist_list_state
+            .selected()
+            .and_then(|idx| library.get_artist(idx))
+            .map_or(0, |artist| {
+                compute_album_display_list(&artist, &self.expanded_albums)
+                    .0
+                    .len()
+            });
+        if self
+            .album_display_list_state
+            .selected()
+            .is_some_and(|row| row >= rows)
+        {
+            self.album_display_list_state.select(None);
+            self.album_list_state.select(None);
+        }
+
+        // Albums view, when its list is loaded
+        let album_idx = session.album.and_then(|(artist, name)| {
+            library
+                .all_albums
+                .iter()
+                .position(|(a, album)| *a == artist && album.name == name)
+        });
+        match album_idx {
+            Some(idx) => {
+                let had_track = session.album_song.is_some();
+                let track = session.album_song.and_then(|uri| {
+                    library.all_albums[idx]
+                        .1
+                        .tracks
+                        .iter()
+                        .position(|song| song.file_path.to_string_lossy() == uri)
+                });
+                self.all_albums_list_state.select(Some(idx));
+                anchor_offset(
+                    &mut self.all_albums_list_state,
+                    before.album_row,
+                    session.albums_offset,
+                );
+                if had_track {
+                    self.album_tracks_list_state.select(track);
+                }
+            }
+            None => {
+                self.all_albums_list_state
+                    .select((!library.all_albums.is_empty()).then_some(0));
+                self.album_tracks_list_state.select(None);
+            }
+        }
+    }
+}
+
+/// Selection in the library views, by name and by row
+struct LibrarySelection {
+    session: Session,
+    artist_row: Option<usize>,
+    album_row: Option<usize>,
+}
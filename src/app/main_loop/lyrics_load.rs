@@ -0,0 +1,28 @@
+use crate::app::song::lyrics::{Lyrics, load_lyrics};
+use mpd_client::Client;
+use std::path::PathBuf;
+
+use tokio::sync::mpsc;
+
+/// Message type for lyrics loading results
+pub enum LyricsMessage {
+    Loaded(Option<Lyrics>, PathBuf),
+}
+
+/// Spawn a background task to load the lyrics of a song
+pub fn spawn_lyrics_loader(
+    client: &Client,
+    file_path: PathBuf,
+    music_directory: Option<PathBuf>,
+    tx: mpsc::Sender<LyricsMessage>,
+) {
+    let client = client.clone();
+
+    tokio::spawn(async move {
+        let lyrics = load_lyrics(&client, music_directory.as_deref(), &file_path).await;
+        if lyrics.is_none() {
+            log::debug!("No lyrics found for {:?}", file_path);
+        }
+        let _ = tx.send(LyricsMessage::Loaded(lyrics, file_path)).await;
+    });
+}
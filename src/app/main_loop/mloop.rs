@@ -1,7 +1,9 @@
 use std::io::Cursor;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyEventKind};
 
 use mpd_client::client::{ConnectionEvent, Subsystem};
 use mpd_client::responses::PlayState;
@@ -17,26 +19,36 @@ use crate::app::main_loop::handle_pipewire_state_change;
 
 use tokio::sync::mpsc;
 
+#[cfg(unix)]
+use crate::app::control::{ControlEvent, ControlServer, protocol::event_line};
+#[cfg(feature = "mpris")]
+use crate::app::mpris::MprisServer;
+#[cfg(feature = "notifications")]
+use crate::app::notifications::Notifier;
+#[cfg(feature = "scrobbling")]
+use crate::app::scrobbler::Scrobbler;
+
 use crate::App;
 use crate::app::LazyLibrary;
-use crate::app::main_loop::connect_to_mpd;
+use crate::app::main_loop::establish_session;
 
-use crate::app::song::SongInfo;
 use crate::app::ui::Protocol;
 use crate::app::ui::WIDTH_CACHE;
 use crate::app::ui::cache::cover_cache::{find_current_index, new_shared_cache};
 use crate::app::ui::rendering::render;
 use crate::app::{
-    MessageType, StatusMessage, event_handlers::EventHandlers, mpd_updates::MPDUpdates,
+    MenuMode, MessageType, StatusMessage, event_handlers::EventHandlers, mpd_handler::MPDAction,
+    mpd_updates::MPDUpdates,
 };
 
-use crate::app::main_loop::check_song_change;
+use crate::app::main_loop::{
+    ConnectionStatus, LibraryMessage, LyricsMessage, check_song_change, spawn_library_revalidation,
+    spawn_lyrics_loader,
+};
+use crate::app::song::library_cache;
 
 use crate::app::main_loop::{CoverArtMessage, spawn_cover_art_loader, spawn_prefetch_loaders};
 
-/// Interval for progress bar updates when playing (in milliseconds)
-const PROGRESS_UPDATE_INTERVAL_MS: u64 = 500;
-
 /// Trait for main application loop
 pub trait AppMainLoop {
     async fn run(self, terminal: DefaultTerminal) -> color_eyre::Result<()>
@@ -52,32 +64,67 @@ impl AppMainLoop for App {
         // Connect to MPD
         log::info!(
             "Attempting to connect to MPD at: {}",
-            self.config.mpd.address
+            self.config.mpd.host()
         );
 
-        let (client, mut state_changes) = connect_to_mpd(&self.config.mpd.address)
-            .await
-            .inspect_err(|e| {
-                crate::logging::log_mpd_connection(
-                    &self.config.mpd.address,
-                    false,
-                    Some(&e.to_string()),
-                );
-            })?;
+        // Set up signal handlers for graceful shutdown (Unix only)
+        #[cfg(unix)]
+        let mut sigint = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())
+            .expect("Failed to set up SIGINT handler");
+        #[cfg(unix)]
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to set up SIGTERM handler");
 
-        crate::logging::log_mpd_connection(&self.config.mpd.address, true, None);
+        // Create protocol with no initial image (will be loaded async)
+        let mut protocol = Protocol { image: None };
 
-        match SongInfo::set_max_art_size(&client, 5 * 1024 * 1024).await {
-            Ok(_) => {
-                log::debug!("Set MPD binary limit to 5MB");
+        // Keep retrying with the reconnect backoff while MPD is not up yet
+        let (mut client, mut state_changes) = loop {
+            match establish_session(&self.config.mpd).await {
+                Ok(session) => break session,
+                Err(e) => {
+                    log::warn!("Failed to connect to MPD: {}", e);
+                    self.schedule_next_reconnect();
+                }
             }
-            Err(e) => {
-                log::warn!("Failed to set MPD binary limit: {}", e);
+            while self.running
+                && matches!(
+                    self.connection_status,
+                    ConnectionStatus::Reconnecting { retry_at, .. } if Instant::now() < retry_at
+                )
+            {
+                self.draw_frame(&mut terminal, &mut protocol)?;
+                self.check_reconnect_countdown();
+                self.check_status_message_expiry();
+                if crossterm::event::poll(Duration::from_millis(10))? {
+                    self.handle_offline_event()?;
+                }
+                #[cfg(unix)]
+                self.poll_signals(&mut sigint, &mut sigterm);
             }
-        }
-
-        // Load library (lazy - only artist names initially)
-        match LazyLibrary::init(&client).await {
+            if !self.running {
+                log::info!("Quit before connecting to MPD");
+                return Ok(());
+            }
+            log::info!(
+                "Attempting to reconnect to MPD at: {}",
+                self.config.mpd.host()
+            );
+        };
+        self.connection_status = ConnectionStatus::Connected;
+        self.last_reconnect_countdown = None;
+        self.dirty.mark_status();
+
+        // Load the library snapshot from disk, or the library itself (lazy - only artist
+        // names initially). The snapshot is checked against the server in the background.
+        let library_host = self.config.mpd.host().to_string();
+        let snapshot = library_cache::load(&library_host);
+        let snapshot_db_update = snapshot.as_ref().map(|(db_update, _)| *db_update);
+        let library = match snapshot {
+            Some((_, library)) => Ok(library),
+            None => LazyLibrary::init(&client).await,
+        };
+        match library {
             Ok(library) => {
                 self.library = Some(library);
 
@@ -114,6 +161,22 @@ impl AppMainLoop for App {
         // Fetch initial song info and status
         self.run_updates(&client).await?;
 
+        // Go back to where the UI was when quitting last time
+        if let Some(session) = self.saved_session.take() {
+            self.restore_session(session, &client).await;
+        }
+
+        // Start on the first `[browse]` view, unless it is the library itself
+        if self
+            .config
+            .browse
+            .views
+            .first()
+            .is_some_and(|view| !view.is_default())
+        {
+            self.open_tag_view(&client, 0).await;
+        }
+
         // Track the current song's file path
         let mut current_song_file: Option<PathBuf> = self
             .current_song
@@ -145,6 +208,13 @@ impl AppMainLoop for App {
         // Channel for cover art loading results
         let (cover_tx, mut cover_rx) = mpsc::channel::<CoverArtMessage>(1);
 
+        // Channel for lyrics loading results
+        let (lyrics_tx, mut lyrics_rx) = mpsc::channel::<LyricsMessage>(1);
+
+        // Check the library snapshot, and rebuild it if needed, in background
+        let (library_tx, mut library_rx) = mpsc::channel::<LibraryMessage>(1);
+        spawn_library_revalidation(&client, library_host, snapshot_db_update, library_tx);
+
         // Create shared cover art cache
         let cover_cache = new_shared_cache();
 
@@ -158,65 +228,77 @@ impl AppMainLoop for App {
         let current_idx = find_current_index(&self.queue, &self.current_song);
         spawn_prefetch_loaders(&client, &self.queue, current_idx, cover_cache.clone());
 
-        // Create protocol with no initial image (will be loaded async)
-        let mut protocol = Protocol { image: None };
-
-        // Progress update interval
-        let progress_interval =
-            tokio::time::interval(Duration::from_millis(PROGRESS_UPDATE_INTERVAL_MS));
-        tokio::pin!(progress_interval);
+        // MPRIS2 service for desktop media keys and widgets
+        #[cfg(feature = "mpris")]
+        let mut mpris = match MprisServer::start().await {
+            Ok(server) => Some(server),
+            Err(e) => {
+                log::warn!("Failed to start MPRIS server: {}", e);
+                None
+            }
+        };
+
+        // Desktop notifications on track change
+        #[cfg(feature = "notifications")]
+        let notifier = if self.config.notifications.enabled {
+            match Notifier::connect(&self.config.notifications).await {
+                Ok(notifier) => Some(notifier),
+                Err(e) => {
+                    log::warn!("Failed to connect to the notification service: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        #[cfg(not(feature = "notifications"))]
+        if self.config.notifications.enabled {
+            log::warn!(
+                "Notifications are enabled but Zarumet was built without the `notifications` feature"
+            );
+        }
+        // The song playing at startup is not announced
+        #[cfg(feature = "notifications")]
+        let mut notified_song_file = current_song_file.clone();
+
+        // Last.fm / ListenBrainz scrobbling
+        #[cfg(feature = "scrobbling")]
+        let mut scrobbler = if self.config.scrobbling.enabled {
+            Scrobbler::spawn(&self.config.scrobbling)
+        } else {
+            None
+        };
+        #[cfg(not(feature = "scrobbling"))]
+        if self.config.scrobbling.enabled {
+            log::warn!(
+                "Scrobbling is enabled but Zarumet was built without the `scrobbling` feature"
+            );
+        }
 
-        // Set up signal handlers for graceful shutdown (Unix only)
+        // Control socket for scripts and window manager keybinds
         #[cfg(unix)]
-        let mut sigint = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())
-            .expect("Failed to set up SIGINT handler");
+        let mut control = if self.config.control.enabled {
+            match ControlServer::start() {
+                Ok(server) => Some(server),
+                Err(e) => {
+                    log::warn!("Failed to start control socket: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
         #[cfg(unix)]
-        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
-            .expect("Failed to set up SIGTERM handler");
+        let mut control_state = self.control_state();
+
+        // Progress bar frames, only ticking while a song plays
+        let mut progress_interval = tokio::time::interval(self.config.progress.frame_interval());
+        progress_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         log::info!("Entering event-driven main loop");
 
         while self.running {
-            // Check terminal size for dirty tracking
-            let term_size = terminal.size()?;
-            self.dirty
-                .check_terminal_size(term_size.width, term_size.height);
-
-            // Only render if something has changed
-            if self.dirty.any_dirty() {
-                terminal.draw(|frame| {
-                    render(
-                        frame,
-                        &mut protocol,
-                        &self.current_song,
-                        &self.queue,
-                        &mut self.queue_list_state,
-                        &self.config,
-                        &self.menu_mode,
-                        &self.library,
-                        &mut self.artist_list_state,
-                        &mut self.album_list_state,
-                        &mut self.album_display_list_state,
-                        &mut self.all_albums_list_state,
-                        &mut self.album_tracks_list_state,
-                        &self.panel_focus,
-                        &self.expanded_albums,
-                        &self.mpd_status,
-                        &self.key_binds,
-                        self.bit_perfect_enabled,
-                        self.show_config_warnings_popup,
-                        &self.config_warnings,
-                        &self.status_message,
-                    )
-                })?;
-
-                if let Some(ref mut img) = protocol.image {
-                    img.last_encoding_result();
-                }
-
-                // Clear dirty flags after render
-                self.dirty.clear_all();
-            }
+            self.draw_frame(&mut terminal, &mut protocol)?;
 
             // Update key bindings for timeouts and mark dirty if state changed
             let was_awaiting = self.key_binds.is_awaiting_input();
@@ -228,6 +310,13 @@ impl AppMainLoop for App {
 
             self.check_status_message_expiry();
             self.check_animation_updates();
+            self.check_reconnect_countdown();
+
+            // Fade the dynamic theme back when nothing is playing, and move fades along
+            if current_song_file.is_none() {
+                self.reset_cover_palette();
+            }
+            self.step_palette_fade();
 
             // Log width cache statistics periodically
             static CACHE_LOG_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -248,17 +337,43 @@ impl AppMainLoop for App {
                 cache_guard.log_stats();
             }
 
+            let connected = self.connection_status.is_connected();
+            let retry_at = match self.connection_status {
+                ConnectionStatus::Reconnecting { retry_at, .. } => {
+                    tokio::time::Instant::from_std(retry_at)
+                }
+                ConnectionStatus::Connected => tokio::time::Instant::now(),
+            };
+
+            // Load lyrics when the song changes or the pane is turned on
+            if connected
+                && let Some(file_path) = self.lyrics.start_load(
+                    self.current_song
+                        .as_ref()
+                        .map(|song| song.file_path.as_path()),
+                )
+            {
+                spawn_lyrics_loader(
+                    &client,
+                    file_path,
+                    self.config.lyrics.music_directory(),
+                    lyrics_tx.clone(),
+                );
+            }
+
             // Event-driven loop using tokio::select!
             tokio::select! {
                 // Keyboard events (with short timeout for responsive UI)
                 _ = tokio::time::sleep(Duration::from_millis(10)) => {
                     // Check for keyboard events non-blocking
                     if crossterm::event::poll(Duration::from_millis(0))? {
-                        self.handle_crossterm_events(&client).await?;
+                        let result = self.handle_crossterm_events(&client).await;
+                        self.recover_from_disconnect(&client, result)?;
 
                         // If user action requires update, do it immediately
-                        if self.force_update {
-                            self.run_updates(&client).await?;
+                        if self.force_update && self.connection_status.is_connected() {
+                            let result = self.run_updates(&client).await;
+                            self.recover_from_disconnect(&client, result)?;
                             self.force_update = false;
 
                             // Check for song change after update
@@ -276,32 +391,35 @@ impl AppMainLoop for App {
                 }
 
                 // MPD state change notifications
-                mpd_event = state_changes.next() => {
+                mpd_event = state_changes.next(), if connected => {
                     match mpd_event {
                         Some(ConnectionEvent::SubsystemChange(subsystem)) => {
                             log::debug!("MPD subsystem change: {:?}", subsystem);
 
                             // Optimize updates based on which subsystem changed
-                            match subsystem {
+                            let result = match subsystem {
                                 // Player state changes (play/pause/stop/seek) - need status + maybe current song
                                 Subsystem::Player => {
-                                    self.run_optimized_updates(&client, false, true).await?;
+                                    self.run_optimized_updates(&client, false, true).await
                                 }
                                 // Mixer changes (volume) - only need status
                                 Subsystem::Mixer => {
-                                    self.update_status_only(&client).await?;
+                                    self.update_status_only(&client).await
                                 }
                                 // Options changes (repeat, random, etc.) - only need status
                                 Subsystem::Options => {
-                                    self.update_status_only(&client).await?;
+                                    self.update_status_only(&client).await
                                 }
                                 // Queue/playlist changes - need full update
                                 Subsystem::Queue => {
-                                    self.run_updates(&client).await?;
+                                    self.run_updates(&client).await
                                 }
                                 // Stored playlist changes - may affect queue if current playlist modified
                                 Subsystem::StoredPlaylist => {
-                                    self.run_updates(&client).await?;
+                                    if self.menu_mode == MenuMode::Playlists {
+                                        self.refresh_playlists(&client).await;
+                                    }
+                                    self.run_updates(&client).await
                                 }
                                 Subsystem::Update => {
                                     if self.library_reload_pending {
@@ -317,121 +435,39 @@ impl AppMainLoop for App {
                                                 message_type: MessageType::Success,
                                             });
                                         }
-                                        log::info!("Database update completed, reloading library...");
-
-                                        // Now reload the music library from MPD
-                                        log::info!("Refreshing library...");
-                                        match LazyLibrary::init(&client).await {
-                                            Ok(new_library) => {
-                                                log::info!("Library refreshed successfully");
-
-                                                self.library = Some(new_library);
-
-                                                // Try to restore artist selection by name
-                                                if let Some(prev_name) = self.pending_artist_index.take() {
-                                                    if let Some(ref mut library) = self.library {
-                                                        if let Some(new_idx) =
-                                                            library.artists.iter().position(|a| a.name == prev_name)
-                                                        {
-                                                            self.artist_list_state.select(Some(new_idx));
-                                                            // Load the restored artist's albums
-                                                            if let Err(e) = library.load_artist(&client, new_idx).await {
-                                                                log::warn!("Failed to load artist after refresh: {}", e);
-                                                            }
-                                                        } else if !library.artists.is_empty() {
-                                                            // Artist no longer exists, select first
-                                                            self.artist_list_state.select(Some(0));
-                                                            if let Err(e) = library.load_artist(&client, 0).await {
-                                                                log::warn!(
-                                                                    "Failed to load first artist after refresh: {}",
-                                                                    e
-                                                                );
-                                                            }
-                                                        }
-                                                    }
-                                                } else if let Some(ref mut library) = self.library {
-                                                    // No previous selection, select first if available
-                                                    if !library.artists.is_empty() {
-                                                        self.artist_list_state.select(Some(0));
-                                                        if let Err(e) = library.load_artist(&client, 0).await {
-                                                            log::warn!("Failed to load first artist after refresh: {}", e);
-                                                        }
-                                                    }
-                                                }
-
-                                                // Clear album selections since they may be stale
-                                                self.album_list_state.select(None);
-                                                self.album_display_list_state.select(None);
-                                                self.expanded_albums.clear();
-
-                                                // Mark library as dirty for re-render
-                                                self.dirty.mark_library();
-
-                                            }
-                                            Err(e) => {
-                                                log::error!("Failed to refresh library: {}", e);
-
-                                                // Show error only if user initiated the update
-                                                if was_user_initiated {  // ← Changed from self.update_in_progress
-                                                    self.set_status_message(StatusMessage {
-                                                        text: e.to_string(),
-                                                        created_at: std::time::Instant::now(),
-                                                        message_type: MessageType::Error,
-                                                    });
-                                                }
+                                        log::info!("Database update completed, refreshing library...");
+
+                                        // Patch the library with what changed, keeping selections
+                                        if let Err(e) = self.refresh_library(&client).await {
+                                            log::error!("Failed to refresh library: {}", e);
+
+                                            // Show error only if user initiated the update
+                                            if was_user_initiated {  // ← Changed from self.update_in_progress
+                                                self.set_status_message(StatusMessage {
+                                                    text: e.to_string(),
+                                                    created_at: std::time::Instant::now(),
+                                                    message_type: MessageType::Error,
+                                                });
                                             }
                                         }
 
-                                        self.run_updates(&client).await?;
-
                                         self.library_reload_pending = false;
-                                        self.pending_artist_index = None;
+
+                                        self.run_updates(&client).await
                                     } else {
-                                        log::debug!("Database update completed (external), reloading silently...");
-
-                                        // Get current artist name (if any) to try to restore after reload
-                                        let current_artist_name = self.artist_list_state.selected()
-                                            .and_then(|idx| self.library.as_ref()?.artists.get(idx).map(|a| a.name.clone()));
-
-                                        match LazyLibrary::init(&client).await {
-                                            Ok(new_library) => {
-                                                self.library = Some(new_library);
-
-                                                // Restore artist selection (find by name to handle removals/renames)
-                                                if let Some(ref name) = current_artist_name {
-                                                    if let Some(ref mut library) = self.library {
-                                                        if let Some(new_idx) = library.artists.iter().position(|a| &a.name == name) {
-                                                            self.artist_list_state.select(Some(new_idx));
-                                                            // Artist still exists, keep album selections
-                                                        } else {
-                                                            // Artist no longer exists, select first and clear album selections
-                                                            if !library.artists.is_empty() {
-                                                                self.artist_list_state.select(Some(0));
-                                                            }
-                                                            self.album_list_state.select(None);
-                                                            self.album_display_list_state.select(None);
-                                                            self.expanded_albums.clear();
-                                                        }
-                                                    }
-                                                } else if let Some(ref mut library) = self.library {
-                                                    // No artist was selected, select first
-                                                    if !library.artists.is_empty() {
-                                                        self.artist_list_state.select(Some(0));
-                                                        if let Err(e) = library.load_artist(&client, 0).await {
-                                                            log::warn!("Failed to load first artist after refresh: {}", e);
-                                                        }
-                                                    }
-                                                }
-
-                                                // Mark library as dirty for re-render
-                                                self.dirty.mark_library();
-                                            }
-                                            Err(e) => {
-                                                log::error!("Failed to reload library after external update: {}", e);
-                                            }
+                                        log::debug!("Database update completed (external), refreshing silently...");
+
+                                        if let Err(e) = self.refresh_library(&client).await {
+                                            log::error!("Failed to refresh library after external update: {}", e);
                                         }
+                                        Ok(())
                                     }
                                 }
+                                // Database changes - re-list the directory being browsed
+                                Subsystem::Database if self.menu_mode == MenuMode::Browse => {
+                                    self.refresh_browse(&client).await;
+                                    Ok(())
+                                }
                                 // Database, output, sticker, etc. - typically don't affect current playback
                                 Subsystem::Database
                                 | Subsystem::Output
@@ -444,13 +480,15 @@ impl AppMainLoop for App {
                                 | Subsystem::Other(_) => {
                                     // These don't typically require UI updates
                                     log::debug!("Ignoring subsystem change: {:?}", subsystem);
+                                    Ok(())
                                 }
                                 // Catch-all for any future subsystem types
                                 _ => {
                                     log::debug!("Unknown subsystem change: {:?}, doing full update", subsystem);
-                                    self.run_updates(&client).await?;
+                                    self.run_updates(&client).await
                                 }
-                            }
+                            };
+                            self.recover_from_disconnect(&client, result)?;
 
                             // Check for song change
                             check_song_change(
@@ -476,57 +514,88 @@ impl AppMainLoop for App {
                         }
                         Some(ConnectionEvent::ConnectionClosed(err)) => {
                             log::error!("MPD connection closed: {:?}", err);
-                            self.running = false;
+                            self.begin_reconnect();
                         }
                         None => {
                             log::info!("MPD connection closed cleanly");
-                            self.running = false;
+                            self.begin_reconnect();
                         }
                     }
                 }
 
-                // Progress bar updates (only when playing)
-                _ = progress_interval.tick() => {
-                    // Only fetch status for progress updates when playing
-                    if let Some(ref status) = self.mpd_status
-                        && status.state == PlayState::Playing
-                    {
-                        // Just update status for progress bar, not full update
-                        if let Ok(new_status) = client.command(mpd_client::commands::Status).await {
-                            let progress = match (new_status.elapsed, new_status.duration) {
-                                (Some(elapsed), Some(duration)) => {
-                                    Some(elapsed.as_secs_f64() / duration.as_secs_f64())
-                                }
-                                _ => None,
-                            };
-
-                            if let Some(ref mut song) = self.current_song {
-                                song.update_playback_info(Some(new_status.state), progress);
-                                song.update_time_info(new_status.elapsed, new_status.duration);
+                // Reconnect attempts while the connection is down
+                _ = tokio::time::sleep_until(retry_at), if !connected => {
+                    log::info!(
+                        "Attempting to reconnect to MPD at: {}",
+                        self.config.mpd.host()
+                    );
+                    match establish_session(&self.config.mpd).await {
+                        Ok((new_client, new_state_changes)) => {
+                            client = new_client;
+                            state_changes = new_state_changes;
+
+                            if let Err(e) = self.resync_after_reconnect(&client).await {
+                                log::warn!("Failed to resync after reconnect: {}", e);
                             }
-                            self.mpd_status = Some(new_status);
 
-                            // Mark progress as dirty to trigger redraw
-                            self.dirty.mark_progress();
+                            // Fetches that failed while disconnected were cached as missing art
+                            cover_cache.write().await.clear();
+                            current_song_file = None;
+                            check_song_change(
+                                &mut current_song_file,
+                                &self.current_song,
+                                &self.queue,
+                                &client,
+                                &cover_tx,
+                                &mut protocol,
+                                cover_cache.clone(),
+                            );
+                        }
+                        Err(e) => {
+                            log::debug!("Reconnect failed: {}", e);
+                            self.schedule_next_reconnect();
                         }
                     }
                 }
 
+                // Progress bar frames, counted locally from the last status
+                _ = progress_interval.tick(), if connected && self.playback_clock.is_running() => {
+                    self.advance_playback();
+                }
+
                 // Cover art loading results
                 Some(msg) = cover_rx.recv() => {
                     match msg {
                         CoverArtMessage::Loaded(data, file_path) => {
                             // Only update if this is still the current song
                             if current_song_file.as_ref() == Some(&file_path) {
-                                protocol.image = data
+                                let cover = data
                                     .as_ref()
                                     .and_then(|raw_data| {
                                         image::ImageReader::new(Cursor::new(raw_data))
                                             .with_guessed_format()
                                             .ok()
                                     })
-                                    .and_then(|reader| reader.decode().ok())
-                                    .map(|dyn_img| picker.new_resize_protocol(dyn_img));
+                                    .and_then(|reader| reader.decode().ok());
+                                self.update_cover_palette(cover.as_ref(), &file_path, &cover_cache)
+                                    .await;
+                                protocol.image =
+                                    cover.map(|dyn_img| picker.new_resize_protocol(dyn_img));
+
+                                #[cfg(feature = "mpris")]
+                                if let Some(ref mut server) = mpris {
+                                    server.set_cover_art(data.as_deref(), &file_path);
+                                }
+
+                                // The cover arrives once per song change, so notify here
+                                #[cfg(feature = "notifications")]
+                                if let Some(ref notifier) = notifier
+                                    && notified_song_file.as_ref() != Some(&file_path)
+                                    && let Some(ref song) = self.current_song
+                                {
+                                    notifier.notify(song, data.as_deref(), self.terminal_focused);
+                                    notified_song_file = Some(file_path.clone());
+                                }
 
                                 // Mark cover art as dirty to trigger redraw
                                 self.dirty.mark_cover_art();
@@ -535,27 +604,74 @@ impl AppMainLoop for App {
                         }
                     }
                 }
+
+                // Library rebuilt from a missing or outdated snapshot
+                Some(msg) = library_rx.recv() => {
+                    let LibraryMessage::Loaded(library) = msg;
+                    log::info!("Library snapshot rebuilt, updating the library");
+                    self.install_library(library);
+                }
+
+                // Lyrics loading results
+                Some(msg) = lyrics_rx.recv() => {
+                    let LyricsMessage::Loaded(lyrics, file_path) = msg;
+                    if self.lyrics.file_path.as_ref() == Some(&file_path) {
+                        self.lyrics.lyrics = lyrics;
+                        // The pane changes the layout around the cover art
+                        self.dirty.mark_full_redraw();
+                    }
+                }
             }
 
             // Check for Unix signals outside of select! to avoid conditional compilation issues
             #[cfg(unix)]
-            {
-                use std::pin::Pin;
-                use std::task::Poll;
+            self.poll_signals(&mut sigint, &mut sigterm);
+
+            // Serve MPRIS requests and publish state changes, also outside of select!
+            #[cfg(feature = "mpris")]
+            if let Some(ref mut server) = mpris {
+                while let Some(command) = server.try_recv_command() {
+                    if self.connection_status.is_connected()
+                        && let Err(e) = self.handle_mpris_command(command, &client).await
+                    {
+                        self.report_mpd_error("Error handling MPRIS request", &e);
+                    }
+                }
 
-                let waker = futures::task::noop_waker();
-                let mut cx = std::task::Context::from_waker(&waker);
+                // Anything that changes the player state marks something dirty
+                if self.dirty.any_dirty() {
+                    let art_url = server.art_url();
+                    let (snapshot, position) = self.mpris_snapshot(art_url.as_deref());
+                    if let Err(e) = server.update(snapshot, position).await {
+                        log::debug!("Failed to publish MPRIS state: {}", e);
+                    }
+                }
+            }
 
-                if let Poll::Ready(Some(())) = Pin::new(&mut sigint).poll_recv(&mut cx) {
-                    log::info!("Received SIGINT, shutting down gracefully");
-                    self.quit();
+            // Serve control socket requests and broadcast what changed
+            #[cfg(unix)]
+            if let Some(ref mut server) = control {
+                while let Some(request) = server.try_recv_request() {
+                    let reply = self.handle_control_command(request.command, &client).await;
+                    let _ = request.reply.send(reply);
                 }
 
-                if let Poll::Ready(Some(())) = Pin::new(&mut sigterm).poll_recv(&mut cx) {
-                    log::info!("Received SIGTERM, shutting down gracefully");
-                    self.quit();
+                if self.dirty.any_dirty() {
+                    let state = self.control_state();
+                    for event in ControlEvent::diff(&control_state, &state) {
+                        server.broadcast(event_line(event, &state));
+                    }
+                    control_state = state;
                 }
             }
+
+            // Progress ticks mark things dirty while playing, so play time keeps counting
+            #[cfg(feature = "scrobbling")]
+            if let Some(ref mut scrobbler) = scrobbler
+                && self.dirty.any_dirty()
+            {
+                scrobbler.update(&self.current_song, &self.mpd_status);
+            }
         }
 
         log::info!("Exiting main loop");
@@ -570,3 +686,105 @@ impl AppMainLoop for App {
         Ok(())
     }
 }
+
+impl App {
+    /// Draw the UI if anything changed since the last frame
+    fn draw_frame(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        protocol: &mut Protocol,
+    ) -> color_eyre::Result<()> {
+        // Check terminal size for dirty tracking
+        let term_size = terminal.size()?;
+        self.dirty
+            .check_terminal_size(term_size.width, term_size.height);
+
+        // Only render if something has changed
+        if self.dirty.any_dirty() {
+            terminal.draw(|frame| {
+                render(
+                    frame,
+                    protocol,
+                    &self.current_song,
+                    &self.queue,
+                    &mut self.queue_list_state,
+                    &self.config,
+                    &self.menu_mode,
+                    &self.library,
+                    &self.tag_view,
+                    &mut self.artist_list_state,
+                    &mut self.album_list_state,
+                    &mut self.album_display_list_state,
+                    &mut self.all_albums_list_state,
+                    &mut self.album_tracks_list_state,
+                    &mut self.search,
+                    &mut self.list_filter,
+                    &self.marks,
+                    &mut self.playlists,
+                    &mut self.browse,
+                    &self.lyrics,
+                    &self.panel_focus,
+                    &self.expanded_albums,
+                    &self.mpd_status,
+                    &self.key_binds,
+                    self.bit_perfect_enabled,
+                    &self.connection_status,
+                    self.show_config_warnings_popup,
+                    &self.config_warnings,
+                    &self.status_message,
+                )
+            })?;
+
+            if let Some(ref mut img) = protocol.image {
+                img.last_encoding_result();
+            }
+
+            // Clear dirty flags after render
+            self.dirty.clear_all();
+        }
+        Ok(())
+    }
+
+    /// Handle a terminal event before the first connection to MPD: only quitting and
+    /// closing the config warnings popup work without one
+    fn handle_offline_event(&mut self) -> color_eyre::Result<()> {
+        if let Event::Key(key) = crossterm::event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            if self.show_config_warnings_popup {
+                self.show_config_warnings_popup = false;
+                self.dirty.mark_full_redraw();
+            } else if let Some(MPDAction::Quit) =
+                self.key_binds
+                    .handle_key(key, &self.menu_mode, &self.panel_focus)
+            {
+                self.quit();
+            }
+        }
+        Ok(())
+    }
+
+    /// Quit on SIGINT or SIGTERM
+    #[cfg(unix)]
+    fn poll_signals(
+        &mut self,
+        sigint: &mut tokio::signal::unix::Signal,
+        sigterm: &mut tokio::signal::unix::Signal,
+    ) {
+        use std::pin::Pin;
+        use std::task::Poll;
+
+        let waker = futures::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+
+        if let Poll::Ready(Some(())) = Pin::new(sigint).poll_recv(&mut cx) {
+            log::info!("Received SIGINT, shutting down gracefully");
+            self.quit();
+        }
+
+        if let Poll::Ready(Some(())) = Pin::new(sigterm).poll_recv(&mut cx) {
+            log::info!("Received SIGTERM, shutting down gracefully");
+            self.quit();
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod connection;
 pub mod cover_load;
+pub mod cover_theme;
+pub mod library_load;
+pub mod lyrics_load;
 pub mod mloop;
+pub mod reconnect;
 
 pub mod state;
 
@@ -11,4 +15,7 @@ pub use state::handle_pipewire_state_change;
 
 pub use connection::connect_to_mpd;
 pub use cover_load::{CoverArtMessage, spawn_cover_art_loader, spawn_prefetch_loaders};
+pub use library_load::{LibraryMessage, spawn_library_revalidation};
+pub use lyrics_load::{LyricsMessage, spawn_lyrics_loader};
 pub use mloop::AppMainLoop;
+pub use reconnect::{ConnectionStatus, establish_session};
@@ -0,0 +1,261 @@
+use std::time::{Duration, Instant};
+
+use mpd_client::Client;
+
+use crate::App;
+use crate::app::LazyLibrary;
+use crate::app::MenuMode;
+use crate::app::config::mpd::MpdConfig;
+use crate::app::main_loop::connect_to_mpd;
+use crate::app::mpd_updates::MPDUpdates;
+use crate::app::song::SongInfo;
+
+/// Delay before the first reconnect attempt
+const RECONNECT_INITIAL_DELAY_MS: u64 = 1000;
+
+/// Upper bound for the reconnect delay
+const RECONNECT_MAX_DELAY_MS: u64 = 30_000;
+
+/// State of the connection to MPD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    /// Connected and receiving events
+    Connected,
+    /// Connection lost, waiting to retry
+    Reconnecting {
+        /// Number of failed attempts so far
+        attempt: u32,
+        /// When the next attempt is scheduled
+        retry_at: Instant,
+    },
+}
+
+impl ConnectionStatus {
+    pub fn is_connected(&self) -> bool {
+        matches!(self, ConnectionStatus::Connected)
+    }
+
+    /// Whole seconds left until the next reconnect attempt (rounded up)
+    pub fn seconds_until_retry(&self) -> Option<u64> {
+        match self {
+            ConnectionStatus::Connected => None,
+            ConnectionStatus::Reconnecting { retry_at, .. } => {
+                let remaining = retry_at.saturating_duration_since(Instant::now());
+                Some(remaining.as_millis().div_ceil(1000) as u64)
+            }
+        }
+    }
+}
+
+/// Exponential backoff delay for the given number of failed attempts
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+    let delay_ms = RECONNECT_INITIAL_DELAY_MS
+        .saturating_mul(factor)
+        .min(RECONNECT_MAX_DELAY_MS);
+    Duration::from_millis(delay_ms)
+}
+
+/// Connect to MPD, authenticate and apply per-session settings.
+/// Used for the initial connection and every reconnect.
+pub async fn establish_session(
+    config: &MpdConfig,
+) -> color_eyre::Result<(Client, mpd_client::client::ConnectionEvents)> {
+    let address = config.host();
+    let password = config.resolve_password()?;
+
+    let (client, state_changes) = connect_to_mpd(address, password.as_deref())
+        .await
+        .inspect_err(|e| {
+            crate::logging::log_mpd_connection(address, false, Some(&e.to_string()));
+        })?;
+
+    crate::logging::log_mpd_connection(address, true, None);
+
+    match SongInfo::set_max_art_size(&client, 5 * 1024 * 1024).await {
+        Ok(_) => {
+            log::debug!("Set MPD binary limit to 5MB");
+        }
+        Err(e) => {
+            log::warn!("Failed to set MPD binary limit: {}", e);
+        }
+    }
+
+    Ok((client, state_changes))
+}
+
+impl App {
+    /// Mark the connection as lost and schedule the first reconnect attempt
+    pub fn begin_reconnect(&mut self) {
+        if !self.connection_status.is_connected() {
+            return;
+        }
+        let delay = reconnect_delay(0);
+        log::warn!("Lost connection to MPD, retrying in {:?}", delay);
+        self.connection_status = ConnectionStatus::Reconnecting {
+            attempt: 0,
+            retry_at: Instant::now() + delay,
+        };
+        self.force_update = false;
+        self.dirty.mark_status();
+    }
+
+    /// Schedule the next reconnect attempt after a failed one
+    pub fn schedule_next_reconnect(&mut self) {
+        let attempt = match self.connection_status {
+            ConnectionStatus::Reconnecting { attempt, .. } => attempt.saturating_add(1),
+            ConnectionStatus::Connected => 0,
+        };
+        let delay = reconnect_delay(attempt);
+        log::debug!(
+            "Reconnect attempt {} failed, retrying in {:?}",
+            attempt,
+            delay
+        );
+        self.connection_status = ConnectionStatus::Reconnecting {
+            attempt,
+            retry_at: Instant::now() + delay,
+        };
+        self.dirty.mark_status();
+    }
+
+    /// Mark the top box dirty whenever the retry countdown ticks over
+    pub fn check_reconnect_countdown(&mut self) {
+        let seconds = self.connection_status.seconds_until_retry();
+        if seconds != self.last_reconnect_countdown {
+            self.last_reconnect_countdown = seconds;
+            self.dirty.mark_status();
+        }
+    }
+
+    /// Swallow errors caused by a dropped connection and start reconnecting instead.
+    /// Any other error is passed through unchanged.
+    pub fn recover_from_disconnect(
+        &mut self,
+        client: &Client,
+        result: color_eyre::Result<()>,
+    ) -> color_eyre::Result<()> {
+        match result {
+            Err(e) if client.is_connection_closed() => {
+                log::debug!("MPD command failed after disconnect: {}", e);
+                self.begin_reconnect();
+                Ok(())
+            }
+            other => other,
+        }
+    }
+
+    /// Refresh everything from MPD after reconnecting while keeping the UI where it was.
+    /// Menu mode, panel focus, expanded albums and selections are restored by name.
+    pub async fn resync_after_reconnect(&mut self, client: &Client) -> color_eyre::Result<()> {
+        self.connection_status = ConnectionStatus::Connected;
+        self.last_reconnect_countdown = None;
+
+        // Force a full refetch of queue and current song
+        self.last_playlist_version = None;
+        self.last_song_id = None;
+
+        // Selections of the library, put aside while a tag view is open
+        self.swap_default_panels();
+        let artist_name = self.artist_list_state.selected().and_then(|idx| {
+            self.library
+                .as_ref()?
+                .artists
+                .get(idx)
+                .map(|a| a.name.clone())
+        });
+        let album_key = self.all_albums_list_state.selected().and_then(|idx| {
+            self.library
+                .as_ref()?
+                .all_albums
+                .get(idx)
+                .map(|(artist, album)| (artist.clone(), album.name.clone()))
+        });
+
+        match LazyLibrary::init(client).await {
+            Ok(mut library) => {
+                let artist_idx = artist_name
+                    .and_then(|name| library.artists.iter().position(|a| a.name == name))
+                    .or(if library.artists.is_empty() {
+                        None
+                    } else {
+                        Some(0)
+                    });
+
+                if let Some(idx) = artist_idx
+                    && let Err(e) = library.load_artist(client, idx).await
+                {
+                    log::warn!("Failed to load artist after reconnect: {}", e);
+                }
+                self.artist_list_state.select(artist_idx);
+                self.library = Some(library);
+                self.clear_library_marks();
+            }
+            Err(e) => {
+                log::error!("Failed to reload library after reconnect: {}", e);
+            }
+        }
+        self.swap_default_panels();
+        self.reload_tag_view(client).await;
+
+        if self.menu_mode == MenuMode::Albums {
+            self.preload_albums_for_view(client).await;
+
+            if let Some((artist, album)) = album_key
+                && let Some(library) = self.library.as_ref()
+                && let Some(idx) = library
+                    .all_albums
+                    .iter()
+                    .position(|(a, al)| *a == artist && al.name == album)
+            {
+                self.all_albums_list_state.select(Some(idx));
+            }
+        }
+
+        match self.menu_mode {
+            MenuMode::Playlists => self.refresh_playlists(client).await,
+            MenuMode::Browse => self.refresh_browse(client).await,
+            _ => {}
+        }
+
+        self.dirty.mark_library();
+        self.run_updates(client).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay_doubles() {
+        assert_eq!(reconnect_delay(0), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(1), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(2), Duration::from_secs(4));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_reconnect_delay_is_capped() {
+        assert_eq!(reconnect_delay(5), Duration::from_secs(30));
+        assert_eq!(reconnect_delay(40), Duration::from_secs(30));
+        assert_eq!(reconnect_delay(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_seconds_until_retry() {
+        assert_eq!(ConnectionStatus::Connected.seconds_until_retry(), None);
+
+        let status = ConnectionStatus::Reconnecting {
+            attempt: 0,
+            retry_at: Instant::now() + Duration::from_millis(2500),
+        };
+        assert_eq!(status.seconds_until_retry(), Some(3));
+
+        let elapsed = ConnectionStatus::Reconnecting {
+            attempt: 0,
+            retry_at: Instant::now() - Duration::from_secs(1),
+        };
+        assert_eq!(elapsed.seconds_until_retry(), Some(0));
+    }
+}
@@ -16,12 +16,22 @@ pub mod binds_handler;
 pub mod cli;
 pub mod config;
 pub mod constructor;
+#[cfg(unix)]
+pub mod control;
 pub mod event_handlers;
+pub mod headless;
 pub mod logging;
 pub mod main;
 pub mod main_loop;
 pub mod mpd;
+#[cfg(feature = "mpris")]
+pub mod mpris;
 pub mod navigation;
+#[cfg(feature = "notifications")]
+pub mod notifications;
+#[cfg(feature = "scrobbling")]
+pub mod scrobbler;
+pub mod session;
 pub mod song;
 pub mod terminal;
 pub mod ui;
@@ -1,2 +1,5 @@
 pub mod mpd_handler;
 pub mod mpd_updates;
+pub mod playback_clock;
+pub mod queue_history;
+pub mod queue_sync;
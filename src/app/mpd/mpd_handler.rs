@@ -7,6 +7,25 @@ use mpd_client::{
 };
 use std::fmt;
 
+/// MPD error code for insufficient permissions (`ACK [4@0]`)
+const ACK_ERROR_PERMISSION: u64 = 4;
+
+/// Build a readable message for permission errors returned by MPD.
+/// Returns None for any other kind of error.
+pub fn permission_denied_message(error: &CommandError) -> Option<String> {
+    match error {
+        CommandError::ErrorResponse { error, .. } if error.code == ACK_ERROR_PERMISSION => {
+            Some(match error.current_command.as_deref() {
+                Some(command) if !command.is_empty() => {
+                    format!("Permission denied for '{}' (check MPD password)", command)
+                }
+                _ => "Permission denied (check MPD password)".to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
 /// Actions that can be performed on MPD
 #[derive(Debug, Clone)]
 pub enum MPDAction {
@@ -51,6 +70,9 @@ pub enum MPDAction {
     SwitchToQueueMenu,
     SwitchToArtists,
     SwitchToAlbums,
+    SwitchToSearch,
+    SwitchToPlaylists,
+    SwitchToBrowse,
 
     // Panel focus
     SwitchPanelLeft,
@@ -75,9 +97,39 @@ pub enum MPDAction {
     // Jump to top/bottom
     GoToTop,
     GoToBottom,
+    GoToCurrent,
 
     // PipeWire bit-perfect mode
     ToggleBitPerfect,
+    ToggleLyrics,
+
+    // List filter
+    Filter,
+    FilterNext,
+    FilterPrevious,
+
+    // Stored playlists
+    LoadPlaylist,
+    SaveQueueAsPlaylist,
+    RenamePlaylist,
+    AddToPlaylist,
+
+    // Marking items for bulk operations
+    ToggleMark,
+    VisualMode,
+    ClearMarks,
+
+    // Play next and queue priorities
+    AddNext,
+    RaisePriority,
+    LowerPriority,
+
+    // Undoing queue changes
+    Undo,
+    Redo,
+
+    // Tag views of the Artists view
+    CycleTagView,
 }
 
 impl fmt::Display for MPDAction {
@@ -109,6 +161,9 @@ impl fmt::Display for MPDAction {
             MPDAction::SwitchToQueueMenu => write!(f, "SwitchToQueueMenu"),
             MPDAction::SwitchToArtists => write!(f, "SwitchToArtists"),
             MPDAction::SwitchToAlbums => write!(f, "SwitchToAlbums"),
+            MPDAction::SwitchToSearch => write!(f, "SwitchToSearch"),
+            MPDAction::SwitchToPlaylists => write!(f, "SwitchToPlaylists"),
+            MPDAction::SwitchToBrowse => write!(f, "SwitchToBrowse"),
             MPDAction::SwitchPanelLeft => write!(f, "SwitchPanelLeft"),
             MPDAction::SwitchPanelRight => write!(f, "SwitchPanelRight"),
             MPDAction::NavigateUp => write!(f, "NavigateUp"),
@@ -121,7 +176,25 @@ impl fmt::Display for MPDAction {
             MPDAction::ScrollDown => write!(f, "ScrollDown"),
             MPDAction::GoToTop => write!(f, "GoToTop"),
             MPDAction::GoToBottom => write!(f, "GoToBottom"),
+            MPDAction::GoToCurrent => write!(f, "GoToCurrent"),
             MPDAction::ToggleBitPerfect => write!(f, "ToggleBitPerfect"),
+            MPDAction::ToggleLyrics => write!(f, "ToggleLyrics"),
+            MPDAction::Filter => write!(f, "Filter"),
+            MPDAction::FilterNext => write!(f, "FilterNext"),
+            MPDAction::FilterPrevious => write!(f, "FilterPrevious"),
+            MPDAction::LoadPlaylist => write!(f, "LoadPlaylist"),
+            MPDAction::SaveQueueAsPlaylist => write!(f, "SaveQueueAsPlaylist"),
+            MPDAction::RenamePlaylist => write!(f, "RenamePlaylist"),
+            MPDAction::AddToPlaylist => write!(f, "AddToPlaylist"),
+            MPDAction::ToggleMark => write!(f, "ToggleMark"),
+            MPDAction::VisualMode => write!(f, "VisualMode"),
+            MPDAction::ClearMarks => write!(f, "ClearMarks"),
+            MPDAction::AddNext => write!(f, "AddNext"),
+            MPDAction::RaisePriority => write!(f, "RaisePriority"),
+            MPDAction::LowerPriority => write!(f, "LowerPriority"),
+            MPDAction::Undo => write!(f, "Undo"),
+            MPDAction::Redo => write!(f, "Redo"),
+            MPDAction::CycleTagView => write!(f, "CycleTagView"),
         }
     }
 }
@@ -320,6 +393,9 @@ impl MPDAction {
             | MPDAction::SwitchToQueueMenu
             | MPDAction::SwitchToArtists
             | MPDAction::SwitchToAlbums
+            | MPDAction::SwitchToSearch
+            | MPDAction::SwitchToPlaylists
+            | MPDAction::SwitchToBrowse
             | MPDAction::SwitchPanelLeft
             | MPDAction::SwitchPanelRight
             | MPDAction::NavigateUp
@@ -332,7 +408,25 @@ impl MPDAction {
             | MPDAction::ScrollDown
             | MPDAction::GoToTop
             | MPDAction::GoToBottom
-            | MPDAction::ToggleBitPerfect => {
+            | MPDAction::GoToCurrent
+            | MPDAction::ToggleBitPerfect
+            | MPDAction::ToggleLyrics
+            | MPDAction::Filter
+            | MPDAction::FilterNext
+            | MPDAction::FilterPrevious
+            | MPDAction::LoadPlaylist
+            | MPDAction::SaveQueueAsPlaylist
+            | MPDAction::RenamePlaylist
+            | MPDAction::AddToPlaylist
+            | MPDAction::ToggleMark
+            | MPDAction::VisualMode
+            | MPDAction::ClearMarks
+            | MPDAction::AddNext
+            | MPDAction::RaisePriority
+            | MPDAction::LowerPriority
+            | MPDAction::Undo
+            | MPDAction::Redo
+            | MPDAction::CycleTagView => {
                 // These are handled by the main application
             }
         }
@@ -1,7 +1,16 @@
-use mpd_client::{Client, commands};
+use mpd_client::{
+    Client,
+    commands::{self, SongId},
+    protocol::command::Command as RawCommand,
+};
+
+use std::time::Duration;
 
 use crate::App;
 use crate::app::SongInfo;
+use crate::app::mpd::queue_sync;
+use crate::app::navigation::helpers::anchor_offset;
+use crate::app::ui::ListKind;
 
 /// Trait for MPD-related updates
 pub trait MPDUpdates {
@@ -54,18 +63,8 @@ impl MPDUpdates for App {
                 self.last_playlist_version.unwrap_or(0),
                 status.playlist_version
             );
-            let queue_songs = client.command(commands::Queue).await?;
-            self.queue = queue_songs
-                .into_iter()
-                .map(|song_in_queue| SongInfo::from_song(&song_in_queue.song))
-                .collect();
-
-            // Update selected index to stay within bounds
-            self.update_queue_selection();
+            self.sync_queue(client, &status).await?;
             self.last_playlist_version = Some(status.playlist_version);
-
-            // Mark queue as dirty for rendering
-            self.dirty.mark_queue();
         }
 
         // Fetch current song only if needed AND it actually changed
@@ -105,6 +104,128 @@ impl MPDUpdates for App {
 }
 
 impl App {
+    /// Bring the queue up to `status`'s version. When the previous version is known,
+    /// only the entries that changed since are fetched; songs that merely moved are
+    /// reused. The selection stays on the same song, and the queue is only redrawn when
+    /// its length or the visible rows changed.
+    async fn sync_queue(
+        &mut self,
+        client: &Client,
+        status: &mpd_client::responses::Status,
+    ) -> color_eyre::Result<()> {
+        let old_length = self.queue.len();
+        let old_row = self.queue_list_state.selected();
+        let old_offset = self.queue_list_state.offset();
+        let selected_id = old_row
+            .and_then(|row| self.queue.get(row))
+            .and_then(|song| song.queue_id);
+
+        let patched = self.patch_queue(client, status).await;
+        self.queue_moved_here.clear();
+        let changed_rows = match patched? {
+            Some(changed_rows) => changed_rows,
+            None => {
+                let queue_songs = client.command(commands::Queue).await?;
+                self.queue = queue_songs.iter().map(SongInfo::from_queue_song).collect();
+                0..self.queue.len().max(old_length)
+            }
+        };
+
+        // Keep the selection on the same song, wherever it moved
+        match selected_id.and_then(|id| self.queue.iter().position(|s| s.queue_id == Some(id))) {
+            Some(row) => {
+                self.queue_list_state.select(Some(row));
+                self.selected_queue_index = Some(row);
+            }
+            None => self.update_queue_selection(),
+        }
+        anchor_offset(&mut self.queue_list_state, old_row, old_offset);
+        self.sync_marks(ListKind::Queue);
+
+        let offset = self.queue_list_state.offset();
+        let visible = offset..offset + self.dirty.terminal_height() as usize;
+        if self.queue.len() != old_length
+            || self.queue_list_state.selected() != old_row
+            || (changed_rows.start < visible.end && visible.start < changed_rows.end)
+        {
+            self.dirty.mark_queue();
+        }
+        Ok(())
+    }
+
+    /// Apply the changes since the last known queue version. Returns the rows that
+    /// changed, or None when the whole queue has to be fetched instead.
+    /// MPD command: plchangesposid, playlistid
+    async fn patch_queue(
+        &mut self,
+        client: &Client,
+        status: &mpd_client::responses::Status,
+    ) -> color_eyre::Result<Option<std::ops::Range<usize>>> {
+        let Some(version) = self.last_playlist_version else {
+            return Ok(None);
+        };
+        if version >= status.playlist_version {
+            // Older than what we have, e.g. MPD restarted
+            return Ok(None);
+        }
+
+        let frame = client
+            .raw_command(RawCommand::new("plchangesposid").argument(version.to_string()))
+            .await?;
+        let changes = queue_sync::parse_changes(&frame);
+        let length = status.playlist_length;
+        let Some(patch) =
+            queue_sync::patch_queue(&self.queue, &changes, length, &self.queue_moved_here)
+        else {
+            return Ok(None);
+        };
+        // Past this many new songs, one full transfer is cheaper
+        if patch.missing.len() > length / 2 + 1 {
+            return Ok(None);
+        }
+        let changed_rows = patch.changed_rows.clone();
+
+        let fetched = if patch.missing.is_empty() {
+            Vec::new()
+        } else {
+            let commands = patch
+                .missing
+                .iter()
+                .map(|change| commands::Queue::song(SongId(change.id)))
+                .collect::<Vec<_>>();
+            match client.command_list(commands).await {
+                Ok(responses) => responses
+                    .iter()
+                    .flatten()
+                    .map(SongInfo::from_queue_song)
+                    .collect(),
+                Err(e) => {
+                    // A song may have been removed in the meantime
+                    log::debug!("Failed to fetch changed queue songs: {}", e);
+                    return Ok(None);
+                }
+            }
+        };
+
+        let fetched_count = fetched.len();
+        Ok(patch.complete(fetched).map(|queue| {
+            log::debug!(
+                "Queue patched: {} changed, {} fetched",
+                changes.len(),
+                fetched_count
+            );
+            self.queue = queue;
+            changed_rows
+        }))
+    }
+
+    /// Remember the songs now at `rows` as moved on our copy of the queue too, so the
+    /// next sync takes their new positions as confirmed instead of fetching them
+    pub fn mark_moved_here(&mut self, rows: &[usize]) {
+        let ids = rows.iter().filter_map(|&row| self.queue.get(row)?.queue_id);
+        self.queue_moved_here.extend(ids);
+    }
+
     /// Update queue selection to stay within bounds
     fn update_queue_selection(&mut self) {
         match self.queue_list_state.selected() {
@@ -129,13 +250,12 @@ impl App {
 
     /// Update app state from MPD status
     fn update_from_status(&mut self, status: mpd_client::responses::Status) {
-        let progress = match (status.elapsed, status.duration) {
-            (Some(elapsed), Some(duration)) => Some(elapsed.as_secs_f64() / duration.as_secs_f64()),
-            _ => None,
-        };
-
+        self.playback_clock.sync(&status);
         if let Some(ref mut song) = self.current_song {
-            song.update_playback_info(Some(status.state), progress);
+            song.update_playback_info(
+                Some(status.state),
+                progress(status.elapsed, status.duration),
+            );
             song.update_time_info(status.elapsed, status.duration);
         }
 
@@ -145,4 +265,32 @@ impl App {
 
         self.mpd_status = Some(status);
     }
+
+    /// Move the elapsed time on from the last status without asking MPD
+    pub fn advance_playback(&mut self) {
+        let Some(elapsed) = self.playback_clock.elapsed() else {
+            return;
+        };
+        let Some(ref mut status) = self.mpd_status else {
+            return;
+        };
+        status.elapsed = Some(elapsed);
+
+        if let Some(ref mut song) = self.current_song {
+            song.update_playback_info(
+                Some(status.state),
+                progress(status.elapsed, status.duration),
+            );
+            song.update_time_info(status.elapsed, status.duration);
+        }
+        self.dirty.mark_progress();
+    }
+}
+
+/// Share of the song that has been played
+fn progress(elapsed: Option<Duration>, duration: Option<Duration>) -> Option<f64> {
+    match (elapsed, duration) {
+        (Some(elapsed), Some(duration)) => Some(elapsed.as_secs_f64() / duration.as_secs_f64()),
+        _ => None,
+    }
 }
@@ -0,0 +1,104 @@
+//! Elapsed time of the playing song, counted locally between MPD status updates.
+//!
+//! MPD only reports the elapsed time when asked, so instead of polling `status` the
+//! last reported value is moved on with a monotonic clock. Every status update (MPD
+//! sends a player event on play, pause, seek and song changes) resets it.
+
+use mpd_client::responses::{PlayState, Status};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlaybackClock {
+    /// Elapsed time MPD reported and when; None unless playing
+    anchor: Option<(Duration, Instant)>,
+    duration: Option<Duration>,
+}
+
+impl PlaybackClock {
+    /// Start again from a status MPD just sent
+    pub fn sync(&mut self, status: &Status) {
+        self.sync_at(
+            status.state,
+            status.elapsed,
+            status.duration,
+            Instant::now(),
+        );
+    }
+
+    fn sync_at(
+        &mut self,
+        state: PlayState,
+        elapsed: Option<Duration>,
+        duration: Option<Duration>,
+        now: Instant,
+    ) {
+        self.anchor = match (state, elapsed) {
+            (PlayState::Playing, Some(elapsed)) => Some((elapsed, now)),
+            _ => None,
+        };
+        self.duration = duration;
+    }
+
+    /// Whether the elapsed time is moving, i.e. a song is playing
+    pub fn is_running(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Elapsed time now, if a song is playing
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed_at(Instant::now())
+    }
+
+    /// Never past the end of the song, where MPD's next status takes over
+    fn elapsed_at(&self, now: Instant) -> Option<Duration> {
+        let (elapsed, since) = self.anchor?;
+        let elapsed = elapsed + now.saturating_duration_since(since);
+        Some(match self.duration {
+            Some(duration) => elapsed.min(duration),
+            None => elapsed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_from_the_last_status() {
+        let start = Instant::now();
+        let mut clock = PlaybackClock::default();
+        clock.sync_at(
+            PlayState::Playing,
+            Some(Duration::from_secs(30)),
+            Some(Duration::from_secs(200)),
+            start,
+        );
+        assert!(clock.is_running());
+        assert_eq!(
+            clock.elapsed_at(start + Duration::from_millis(1500)),
+            Some(Duration::from_millis(31500))
+        );
+        // Stops at the end of the song
+        assert_eq!(
+            clock.elapsed_at(start + Duration::from_secs(500)),
+            Some(Duration::from_secs(200))
+        );
+    }
+
+    #[test]
+    fn test_stands_still_unless_playing() {
+        let start = Instant::now();
+        let mut clock = PlaybackClock::default();
+        assert_eq!(clock.elapsed_at(start), None);
+
+        clock.sync_at(
+            PlayState::Paused,
+            Some(Duration::from_secs(30)),
+            Some(Duration::from_secs(200)),
+            start,
+        );
+        assert!(!clock.is_running());
+        assert_eq!(clock.elapsed_at(start + Duration::from_secs(5)), None);
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use mpd_client::{
+    protocol::command::{Command as RawCommand, CommandList as RawCommandList},
+    responses::PlayState,
+};
+
+/// Number of queue changes that can be undone
+const HISTORY_LIMIT: usize = 50;
+
+/// Queue contents and playback position at one point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueSnapshot {
+    /// The change made right after the snapshot, e.g. "clear queue"
+    pub description: &'static str,
+    pub uris: Vec<String>,
+    /// Priority of each song in `uris`, set with play next
+    pub priorities: Vec<u8>,
+    /// Position of the current song
+    pub current: Option<usize>,
+    pub elapsed: Option<Duration>,
+    pub play_state: PlayState,
+}
+
+impl QueueSnapshot {
+    fn current_uri(&self) -> Option<&str> {
+        self.current
+            .and_then(|position| self.uris.get(position))
+            .map(String::as_str)
+    }
+
+    /// Same songs with the same priorities
+    fn same_queue(&self, other: &QueueSnapshot) -> bool {
+        self.uris == other.uris && self.priorities == other.priorities
+    }
+
+    /// Commands replacing the queue with the snapshot's songs and priorities, and
+    /// continuing playback at the snapshot's current song
+    pub fn restore_commands(&self) -> RawCommandList {
+        let mut commands = RawCommandList::new(RawCommand::new("clear"));
+        for uri in &self.uris {
+            commands.add(RawCommand::new("add").argument(uri.as_str()));
+        }
+        // The new entries' IDs aren't known yet, so priorities go by position
+        for (position, &priority) in self.priorities.iter().enumerate() {
+            if priority > 0 && position < self.uris.len() {
+                let range = format!("{}:{}", position, position + 1);
+                commands.add(RawCommand::new("prio").argument(priority).argument(range));
+            }
+        }
+
+        if let Some(position) = self.current
+            && position < self.uris.len()
+            && self.play_state != PlayState::Stopped
+        {
+            // Seeking starts playback, so pause again afterwards if needed
+            let elapsed = self.elapsed.unwrap_or_default();
+            commands.add(RawCommand::new("seek").argument(position).argument(elapsed));
+            if self.play_state == PlayState::Paused {
+                commands.add(RawCommand::new("pause").argument(true));
+            }
+        }
+        commands
+    }
+
+    /// Keep playing like `live` does when restoring the snapshot, so undoing doesn't
+    /// rewind or pause the song that is playing now
+    pub fn resume_from(&mut self, live: &QueueSnapshot) {
+        if live.current_uri().is_some() && live.current_uri() == self.current_uri() {
+            self.elapsed = live.elapsed;
+        }
+        self.play_state = live.play_state;
+    }
+}
+
+/// Undo and redo stacks of queue snapshots
+#[derive(Debug, Default)]
+pub struct QueueHistory {
+    undo: VecDeque<QueueSnapshot>,
+    redo: Vec<QueueSnapshot>,
+}
+
+impl QueueHistory {
+    /// Remember the queue before a change. A new change can't be redone over.
+    pub fn record(&mut self, snapshot: QueueSnapshot) {
+        // The last change didn't touch the queue (e.g. removing from an empty queue),
+        // so there is nothing to go back to before it
+        if self
+            .undo
+            .back()
+            .is_some_and(|last| last.same_queue(&snapshot))
+        {
+            self.undo.pop_back();
+        }
+        self.undo.push_back(snapshot);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    /// Snapshot to restore to undo the last change, given the queue as it is now
+    pub fn undo(&mut self, mut live: QueueSnapshot) -> Option<QueueSnapshot> {
+        // Skip snapshots of changes that turned out not to change anything
+        let snapshot = std::iter::from_fn(|| self.undo.pop_back())
+            .find(|snapshot| !snapshot.same_queue(&live))?;
+        live.description = snapshot.description;
+        self.redo.push(live);
+        Some(snapshot)
+    }
+
+    /// Snapshot to restore to redo the last undone change
+    pub fn redo(&mut self, mut live: QueueSnapshot) -> Option<QueueSnapshot> {
+        let snapshot = self.redo.pop()?;
+        live.description = snapshot.description;
+        self.undo.push_back(live);
+        Some(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(description: &'static str, uris: &[&str]) -> QueueSnapshot {
+        QueueSnapshot {
+            description,
+            uris: uris.iter().map(|uri| uri.to_string()).collect(),
+            priorities: vec![0; uris.len()],
+            current: None,
+            elapsed: None,
+            play_state: PlayState::Stopped,
+        }
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut history = QueueHistory::default();
+        history.record(snapshot("remove from queue", &["a", "b"]));
+        history.record(snapshot("clear queue", &["a"]));
+
+        let undone = history.undo(snapshot("", &[])).unwrap();
+        assert_eq!(undone.uris, ["a"]);
+        assert_eq!(undone.description, "clear queue");
+
+        let redone = history.redo(snapshot("", &["a"])).unwrap();
+        assert!(redone.uris.is_empty());
+        assert_eq!(redone.description, "clear queue");
+
+        // Undoing again goes back to before the clear, then before the remove
+        assert_eq!(history.undo(snapshot("", &[])).unwrap().uris, ["a"]);
+        assert_eq!(history.undo(snapshot("", &["a"])).unwrap().uris, ["a", "b"]);
+        assert!(history.undo(snapshot("", &["a", "b"])).is_none());
+    }
+
+    #[test]
+    fn test_unchanged_queue_is_skipped() {
+        let mut history = QueueHistory::default();
+        history.record(snapshot("remove from queue", &["a", "b"]));
+        history.record(snapshot("remove from queue", &["a"]));
+        // Nothing was selected, so the queue didn't change
+        history.record(snapshot("remove from queue", &["a"]));
+
+        assert_eq!(history.undo(snapshot("", &["a"])).unwrap().uris, ["a", "b"]);
+        assert!(history.undo(snapshot("", &["a", "b"])).is_none());
+    }
+
+    #[test]
+    fn test_new_change_drops_redo_and_history_is_bounded() {
+        let mut history = QueueHistory::default();
+        history.record(snapshot("clear queue", &["a"]));
+        history.undo(snapshot("", &[]));
+        history.record(snapshot("add to queue", &["a"]));
+        assert!(history.redo(snapshot("", &["a", "b"])).is_none());
+
+        for i in 0..HISTORY_LIMIT + 10 {
+            history.record(snapshot("add to queue", &[&i.to_string()]));
+        }
+        let mut undone = 0;
+        let mut live = snapshot("", &[]);
+        while let Some(snapshot) = history.undo(live) {
+            live = snapshot;
+            undone += 1;
+        }
+        assert_eq!(undone, HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn test_resume_keeps_live_playback() {
+        let mut target = snapshot("remove from queue", &["a", "b", "c"]);
+        target.current = Some(1);
+        target.elapsed = Some(Duration::from_secs(10));
+
+        let mut live = snapshot("", &["a", "b"]);
+        live.current = Some(1);
+        live.elapsed = Some(Duration::from_secs(95));
+        live.play_state = PlayState::Playing;
+
+        target.resume_from(&live);
+        assert_eq!(target.elapsed, Some(Duration::from_secs(95)));
+        assert_eq!(target.play_state, PlayState::Playing);
+
+        // Another song is playing now, so the old position is kept
+        let mut target = snapshot("clear queue", &["x"]);
+        target.current = Some(0);
+        target.elapsed = Some(Duration::from_secs(10));
+        target.resume_from(&live);
+        assert_eq!(target.elapsed, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_priorities_are_restored() {
+        let mut target = snapshot("play next", &["a", "b", "c"]);
+        target.priorities = vec![0, 255, 3];
+        target.current = Some(0);
+        target.elapsed = Some(Duration::from_secs(10));
+        target.play_state = PlayState::Playing;
+
+        let mut expected = RawCommandList::new(RawCommand::new("clear"));
+        expected.add(RawCommand::new("add").argument("a"));
+        expected.add(RawCommand::new("add").argument("b"));
+        expected.add(RawCommand::new("add").argument("c"));
+        expected.add(RawCommand::new("prio").argument(255u8).argument("1:2"));
+        expected.add(RawCommand::new("prio").argument(3u8).argument("2:3"));
+        expected.add(
+            RawCommand::new("seek")
+                .argument(0usize)
+                .argument(Duration::from_secs(10)),
+        );
+        assert_eq!(target.restore_commands(), expected);
+
+        // Play next only changed priorities, which is still a change to undo
+        let mut history = QueueHistory::default();
+        history.record(snapshot("play next", &["a", "b", "c"]));
+        assert_eq!(history.undo(target).unwrap().priorities, [0, 0, 0]);
+    }
+}
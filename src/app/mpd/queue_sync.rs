@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::app::SongInfo;
+
+/// Queue entry that changed since an older queue version, as listed by `plchangesposid`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueueChange {
+    pub position: usize,
+    pub id: u64,
+}
+
+/// Parse a `plchangesposid` response, where each `cpos` is followed by the `Id` now at
+/// that position
+pub fn parse_changes<'a>(fields: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<QueueChange> {
+    let mut changes = Vec::new();
+    let mut position = None;
+    for (key, value) in fields {
+        match key {
+            "cpos" => position = value.parse().ok(),
+            "Id" => {
+                if let (Some(position), Ok(id)) = (position.take(), value.parse()) {
+                    changes.push(QueueChange { position, id });
+                }
+            }
+            _ => {}
+        }
+    }
+    changes
+}
+
+/// The queue with changes applied, where songs that only moved are reused
+#[derive(Debug)]
+pub struct QueuePatch {
+    /// New queue, None where the song has to be fetched
+    pub songs: Vec<Option<SongInfo>>,
+    /// Position and ID of the songs to fetch: new entries, and entries changed in place
+    /// (e.g. a new priority)
+    pub missing: Vec<QueueChange>,
+    /// Rows whose content changed
+    pub changed_rows: Range<usize>,
+}
+
+impl QueuePatch {
+    /// Fill in fetched songs. Returns the queue, or None if a song is still missing.
+    pub fn complete(mut self, fetched: Vec<SongInfo>) -> Option<Vec<SongInfo>> {
+        let mut fetched: HashMap<u64, SongInfo> = fetched
+            .into_iter()
+            .filter_map(|song| Some((song.queue_id?, song)))
+            .collect();
+        for change in &self.missing {
+            self.songs[change.position] = fetched.remove(&change.id);
+        }
+        self.songs.into_iter().collect()
+    }
+}
+
+/// Apply `changes` to `queue`, which now has `length` entries. None if the changes
+/// don't fit the queue, which then has to be fetched again.
+///
+/// Songs listed where they already are changed in place and are fetched again, unless
+/// their ID is in `moved_here`: songs moved on our copy as well, which the change only
+/// confirms.
+pub fn patch_queue(
+    queue: &[SongInfo],
+    changes: &[QueueChange],
+    length: usize,
+    moved_here: &HashSet<u64>,
+) -> Option<QueuePatch> {
+    let old_length = queue.len();
+    let positions: HashMap<u64, usize> = queue
+        .iter()
+        .enumerate()
+        .filter_map(|(position, song)| Some((song.queue_id?, position)))
+        .collect();
+
+    // Songs that moved are copied before their old rows are overwritten
+    let mut moved = Vec::new();
+    let mut missing = Vec::new();
+    let mut changed = Vec::new();
+    for &change in changes {
+        if change.position >= length {
+            return None;
+        }
+        match positions.get(&change.id) {
+            Some(&from) if from == change.position && moved_here.contains(&change.id) => {
+                continue;
+            }
+            Some(&from) if from != change.position => {
+                moved.push((change.position, queue[from].clone()));
+            }
+            _ => missing.push(change),
+        }
+        changed.push(change.position);
+    }
+
+    let mut songs: Vec<Option<SongInfo>> = queue.iter().take(length).cloned().map(Some).collect();
+    songs.resize(length, None);
+    for change in &missing {
+        songs[change.position] = None;
+    }
+    for (position, song) in moved {
+        songs[position] = Some(song);
+    }
+    // Rows added at the end must all be listed as changed
+    if songs.iter().filter(|song| song.is_none()).count() != missing.len() {
+        return None;
+    }
+
+    let first = changed.iter().copied().min();
+    let last = changed.iter().map(|position| position + 1).max();
+    let changed_rows = if old_length != length {
+        // Rows past the shorter queue appeared or disappeared
+        let first = first.unwrap_or(length).min(old_length.min(length));
+        first..old_length.max(length)
+    } else {
+        first.unwrap_or(0)..last.unwrap_or(0)
+    };
+
+    Some(QueuePatch {
+        songs,
+        missing,
+        changed_rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn song(id: u64) -> SongInfo {
+        SongInfo {
+            title: format!("Song {}", id),
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            file_path: PathBuf::from(format!("{}.flac", id)),
+            format: None,
+            play_state: None,
+            progress: None,
+            elapsed: None,
+            duration: None,
+            disc_number: 1,
+            track_number: id,
+            queue_id: Some(id),
+            priority: 0,
+        }
+    }
+
+    fn ids(songs: &[SongInfo]) -> Vec<u64> {
+        songs.iter().filter_map(|song| song.queue_id).collect()
+    }
+
+    fn change(position: usize, id: u64) -> QueueChange {
+        QueueChange { position, id }
+    }
+
+    #[test]
+    fn test_parse_changes() {
+        let fields = [("cpos", "3"), ("Id", "17"), ("cpos", "4"), ("Id", "12")];
+        assert_eq!(parse_changes(fields), [change(3, 17), change(4, 12)]);
+    }
+
+    #[test]
+    fn test_move_reuses_songs() {
+        let queue: Vec<SongInfo> = (1..=5).map(song).collect();
+        // Song 2 moved to the end
+        let changes = [change(1, 3), change(2, 4), change(3, 5), change(4, 2)];
+        let patch = patch_queue(&queue, &changes, 5, &HashSet::new()).unwrap();
+        assert!(patch.missing.is_empty());
+        assert_eq!(patch.changed_rows, 1..5);
+        assert_eq!(ids(&patch.complete(Vec::new()).unwrap()), [1, 3, 4, 5, 2]);
+    }
+
+    #[test]
+    fn test_added_and_modified_songs_are_fetched() {
+        let queue: Vec<SongInfo> = (1..=3).map(song).collect();
+        // Song 2 got a new priority, song 9 was appended
+        let changes = [change(1, 2), change(3, 9)];
+        let patch = patch_queue(&queue, &changes, 4, &HashSet::new()).unwrap();
+        assert_eq!(patch.missing, changes);
+        assert_eq!(patch.changed_rows, 1..4);
+
+        let mut prioritized = song(2);
+        prioritized.priority = 10;
+        let queue = patch.complete(vec![song(9), prioritized]).unwrap();
+        assert_eq!(ids(&queue), [1, 2, 3, 9]);
+        assert_eq!(queue[1].priority, 10);
+    }
+
+    #[test]
+    fn test_moves_made_here_are_only_confirmed() {
+        // Songs 2 and 3 were swapped here already, song 4 got a new priority
+        let queue: Vec<SongInfo> = [1, 3, 2, 4].into_iter().map(song).collect();
+        let changes = [change(1, 3), change(2, 2), change(3, 4)];
+        let patch = patch_queue(&queue, &changes, 4, &HashSet::from([2, 3])).unwrap();
+        assert_eq!(patch.missing, [change(3, 4)]);
+        assert_eq!(patch.changed_rows, 3..4);
+    }
+
+    #[test]
+    fn test_removal_at_the_end_and_bad_deltas() {
+        let queue: Vec<SongInfo> = (1..=4).map(song).collect();
+        let patch = patch_queue(&queue, &[], 2, &HashSet::new()).unwrap();
+        assert_eq!(patch.changed_rows, 2..4);
+        assert_eq!(ids(&patch.complete(Vec::new()).unwrap()), [1, 2]);
+
+        // A position past the new length, or a new row nobody listed
+        assert!(patch_queue(&queue, &[change(5, 1)], 4, &HashSet::new()).is_none());
+        assert!(patch_queue(&queue, &[], 5, &HashSet::new()).is_none());
+
+        // A song that couldn't be fetched
+        let patch = patch_queue(&queue, &[change(0, 7)], 4, &HashSet::new()).unwrap();
+        assert!(patch.complete(Vec::new()).is_none());
+    }
+}
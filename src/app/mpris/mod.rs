@@ -0,0 +1,127 @@
+//! MPRIS2 D-Bus integration
+//!
+//! Exposes Zarumet as `org.mpris.MediaPlayer2.zarumet` so desktop media keys,
+//! panels and widgets can see and control playback.
+
+pub mod server;
+pub mod state;
+
+pub use server::{MprisCommand, MprisServer};
+pub use state::PlayerSnapshot;
+
+use crate::App;
+use crate::app::event_handlers::EventHandlers;
+use crate::app::mpd_handler::MPDAction;
+use mpd_client::{Client, client::CommandError, commands};
+use std::time::Duration;
+
+impl App {
+    /// Current player state as exposed over MPRIS
+    pub fn mpris_snapshot(&self, art_url: Option<&str>) -> (PlayerSnapshot, Duration) {
+        let snapshot = PlayerSnapshot::new(&self.current_song, &self.mpd_status, art_url);
+        let position = self
+            .mpd_status
+            .as_ref()
+            .and_then(|status| status.elapsed)
+            .unwrap_or_default();
+        (snapshot, position)
+    }
+
+    /// Carry out a request from an MPRIS client
+    pub async fn handle_mpris_command(
+        &mut self,
+        command: MprisCommand,
+        client: &Client,
+    ) -> Result<(), CommandError> {
+        log::debug!("MPRIS command: {:?}", command);
+        let status = self.mpd_status.clone();
+
+        match command {
+            MprisCommand::PlayPause => {
+                MPDAction::TogglePlayPause
+                    .execute(client, &self.config, status.as_ref())
+                    .await?;
+            }
+            MprisCommand::Next => {
+                MPDAction::Next
+                    .execute(client, &self.config, status.as_ref())
+                    .await?;
+            }
+            MprisCommand::Previous => {
+                MPDAction::Previous
+                    .execute(client, &self.config, status.as_ref())
+                    .await?;
+            }
+            MprisCommand::Play => client.command(commands::Play::current()).await?,
+            MprisCommand::Pause => client.command(commands::SetPause(true)).await?,
+            MprisCommand::Stop => client.command(commands::Stop).await?,
+            MprisCommand::Seek(offset) => {
+                let Some(status) = status else {
+                    return Ok(());
+                };
+                let elapsed = status.elapsed.unwrap_or_default();
+                let offset_duration = Duration::from_micros(offset.unsigned_abs());
+                if offset >= 0 {
+                    let target = elapsed + offset_duration;
+                    // Seeking past the end moves to the next track, as the spec asks
+                    if status.duration.is_some_and(|duration| target >= duration) {
+                        client.command(commands::Next).await?;
+                    } else {
+                        client
+                            .command(commands::Seek(commands::SeekMode::Absolute(target)))
+                            .await?;
+                    }
+                } else {
+                    let target = elapsed.saturating_sub(offset_duration);
+                    client
+                        .command(commands::Seek(commands::SeekMode::Absolute(target)))
+                        .await?;
+                }
+            }
+            MprisCommand::SetPosition { track_id, position } => {
+                let (snapshot, _) = self.mpris_snapshot(None);
+                let Some(track) = snapshot.track else {
+                    return Ok(());
+                };
+                // Stale requests for a previous track and out of range positions are ignored
+                let target = Duration::from_micros(position.max(0) as u64);
+                if track.track_id == track_id
+                    && position >= 0
+                    && track.length.is_none_or(|length| target <= length)
+                {
+                    client
+                        .command(commands::Seek(commands::SeekMode::Absolute(target)))
+                        .await?;
+                }
+            }
+            MprisCommand::SetVolume(volume) => {
+                client
+                    .command(commands::SetVolume(state::volume_percent(volume)))
+                    .await?;
+            }
+            MprisCommand::SetShuffle(shuffle) => {
+                if status.as_ref().is_some_and(|s| s.random != shuffle) {
+                    MPDAction::Random
+                        .execute(client, &self.config, status.as_ref())
+                        .await?;
+                }
+            }
+            MprisCommand::SetLoopStatus(loop_status) => {
+                let Some(ref current) = status else {
+                    return Ok(());
+                };
+                let (repeat, single) = loop_status.to_mpd();
+                if current.repeat != repeat {
+                    MPDAction::Repeat
+                        .execute(client, &self.config, status.as_ref())
+                        .await?;
+                }
+                if current.single != single {
+                    client.command(commands::SetSingle(single)).await?;
+                }
+            }
+            MprisCommand::Quit => self.quit(),
+        }
+        Ok(())
+    }
+}
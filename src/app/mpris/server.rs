@@ -0,0 +1,591 @@
+use super::state::{LoopStatus, PlayerSnapshot, as_micros, is_seek};
+use mpd_client::responses::PlayState;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+use zbus::{Connection, fdo, interface};
+
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+const BUS_NAME: &str = "org.mpris.MediaPlayer2.zarumet";
+
+/// Requests from MPRIS clients, carried out by the main loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum MprisCommand {
+    PlayPause,
+    Play,
+    Pause,
+    Stop,
+    Next,
+    Previous,
+    /// Relative seek in microseconds
+    Seek(i64),
+    /// Absolute position in microseconds, ignored if the track changed meanwhile
+    SetPosition {
+        track_id: String,
+        position: i64,
+    },
+    SetVolume(f64),
+    SetShuffle(bool),
+    SetLoopStatus(LoopStatus),
+    Quit,
+}
+
+/// `org.mpris.MediaPlayer2` root interface
+struct Root {
+    commands: mpsc::UnboundedSender<MprisCommand>,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {
+        let _ = self.commands.send(MprisCommand::Quit);
+    }
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> &str {
+        "Zarumet"
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// `org.mpris.MediaPlayer2.Player` interface
+struct Player {
+    commands: mpsc::UnboundedSender<MprisCommand>,
+    snapshot: PlayerSnapshot,
+    /// Position reported by MPD and when it was reported
+    position: Duration,
+    position_at: Instant,
+}
+
+impl Player {
+    fn send(&self, command: MprisCommand) -> fdo::Result<()> {
+        self.commands
+            .send(command)
+            .map_err(|_| fdo::Error::Failed("Zarumet is shutting down".to_string()))
+    }
+
+    fn has_track(&self) -> bool {
+        self.snapshot.track.is_some()
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn next(&self) -> fdo::Result<()> {
+        self.send(MprisCommand::Next)
+    }
+
+    fn previous(&self) -> fdo::Result<()> {
+        self.send(MprisCommand::Previous)
+    }
+
+    fn pause(&self) -> fdo::Result<()> {
+        self.send(MprisCommand::Pause)
+    }
+
+    fn play_pause(&self) -> fdo::Result<()> {
+        self.send(MprisCommand::PlayPause)
+    }
+
+    fn stop(&self) -> fdo::Result<()> {
+        self.send(MprisCommand::Stop)
+    }
+
+    fn play(&self) -> fdo::Result<()> {
+        self.send(MprisCommand::Play)
+    }
+
+    fn seek(&self, offset: i64) -> fdo::Result<()> {
+        self.send(MprisCommand::Seek(offset))
+    }
+
+    fn set_position(&self, track_id: ObjectPath<'_>, position: i64) -> fdo::Result<()> {
+        self.send(MprisCommand::SetPosition {
+            track_id: track_id.to_string(),
+            position,
+        })
+    }
+
+    fn open_uri(&self, _uri: &str) -> fdo::Result<()> {
+        Err(fdo::Error::NotSupported(
+            "Opening URIs is not supported".to_string(),
+        ))
+    }
+
+    #[zbus(signal)]
+    async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> &str {
+        self.snapshot.playback_status_str()
+    }
+
+    #[zbus(property)]
+    fn loop_status(&self) -> &str {
+        self.snapshot.loop_status.as_str()
+    }
+
+    #[zbus(property)]
+    fn set_loop_status(&mut self, value: String) -> fdo::Result<()> {
+        let status = LoopStatus::parse(&value)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("Invalid loop status: {}", value)))?;
+        self.send(MprisCommand::SetLoopStatus(status))
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn set_rate(&mut self, _value: f64) {}
+
+    #[zbus(property)]
+    fn shuffle(&self) -> bool {
+        self.snapshot.shuffle
+    }
+
+    #[zbus(property)]
+    fn set_shuffle(&mut self, value: bool) -> fdo::Result<()> {
+        self.send(MprisCommand::SetShuffle(value))
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        metadata_map(&self.snapshot)
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        self.snapshot.volume
+    }
+
+    #[zbus(property)]
+    fn set_volume(&mut self, value: f64) -> fdo::Result<()> {
+        self.send(MprisCommand::SetVolume(value))
+    }
+
+    /// Extrapolated from the last MPD status while playing
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> i64 {
+        let position = if self.snapshot.playback_status == PlayState::Playing {
+            self.position + self.position_at.elapsed()
+        } else {
+            self.position
+        };
+        as_micros(position)
+    }
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        self.has_track()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
+
+/// Build the `Metadata` property; without a track only the "no track" id is set
+fn metadata_map(snapshot: &PlayerSnapshot) -> HashMap<String, OwnedValue> {
+    let mut map = HashMap::new();
+    let mut insert = |key: &str, value: Value<'_>| {
+        if let Ok(value) = value.try_into_owned() {
+            map.insert(key.to_string(), value);
+        }
+    };
+
+    let Some(track) = &snapshot.track else {
+        insert(
+            "mpris:trackid",
+            Value::from(ObjectPath::from_static_str_unchecked(
+                "/org/mpris/MediaPlayer2/TrackList/NoTrack",
+            )),
+        );
+        return map;
+    };
+
+    if let Ok(path) = ObjectPath::try_from(track.track_id.as_str()) {
+        insert("mpris:trackid", Value::from(path));
+    }
+    if let Some(length) = track.length {
+        insert("mpris:length", Value::from(as_micros(length)));
+    }
+    if let Some(art_url) = &track.art_url {
+        insert("mpris:artUrl", Value::from(art_url.as_str()));
+    }
+    insert("xesam:title", Value::from(track.title.as_str()));
+    insert("xesam:artist", Value::from(vec![track.artist.as_str()]));
+    insert("xesam:album", Value::from(track.album.as_str()));
+    if track.track_number > 0 {
+        insert(
+            "xesam:trackNumber",
+            Value::from(i32::try_from(track.track_number).unwrap_or(i32::MAX)),
+        );
+    }
+    insert(
+        "xesam:discNumber",
+        Value::from(i32::try_from(track.disc_number).unwrap_or(i32::MAX)),
+    );
+    map
+}
+
+/// MPRIS2 service on the session bus, kept in sync with MPD by the main loop
+pub struct MprisServer {
+    connection: Connection,
+    commands: mpsc::UnboundedReceiver<MprisCommand>,
+    snapshot: PlayerSnapshot,
+    position: Duration,
+    position_at: Instant,
+    /// Cover art file currently referenced by `mpris:artUrl`
+    art_file: Option<PathBuf>,
+}
+
+impl MprisServer {
+    /// Start the service on the session bus
+    pub async fn start() -> zbus::Result<Self> {
+        Self::start_with(zbus::connection::Builder::session()?).await
+    }
+
+    /// Start the service on the bus given by `builder` (used for tests against a private bus)
+    pub async fn start_with(builder: zbus::connection::Builder<'_>) -> zbus::Result<Self> {
+        let (tx, commands) = mpsc::unbounded_channel();
+        let connection = builder
+            .serve_at(
+                OBJECT_PATH,
+                Root {
+                    commands: tx.clone(),
+                },
+            )?
+            .serve_at(
+                OBJECT_PATH,
+                Player {
+                    commands: tx,
+                    snapshot: PlayerSnapshot::default(),
+                    position: Duration::ZERO,
+                    position_at: Instant::now(),
+                },
+            )?
+            .build()
+            .await?;
+
+        // A second instance gets a unique name, as the spec suggests
+        if let Err(e) = connection.request_name(BUS_NAME).await {
+            log::debug!("{} is taken ({}), using an instance name", BUS_NAME, e);
+            let instance = format!("{}.instance{}", BUS_NAME, std::process::id());
+            connection.request_name(instance).await?;
+        }
+
+        Ok(Self {
+            connection,
+            commands,
+            snapshot: PlayerSnapshot::default(),
+            position: Duration::ZERO,
+            position_at: Instant::now(),
+            art_file: None,
+        })
+    }
+
+    /// Next pending command from MPRIS clients, if any
+    pub fn try_recv_command(&mut self) -> Option<MprisCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    /// `file://` URL of the current cover art file
+    pub fn art_url(&self) -> Option<String> {
+        self.art_file
+            .as_ref()
+            .map(|path| format!("file://{}", path.display()))
+    }
+
+    /// Write the cover art of `song_path` to the cache directory for `mpris:artUrl`.
+    /// Each song gets its own file name so clients do not show a stale cached image.
+    pub fn set_cover_art(&mut self, data: Option<&[u8]>, song_path: &Path) {
+        if let Some(old) = self.art_file.take() {
+            let _ = std::fs::remove_file(old);
+        }
+        let (Some(data), Some(dir)) = (data, cover_art_dir()) else {
+            return;
+        };
+
+        let mut hasher = DefaultHasher::new();
+        song_path.hash(&mut hasher);
+        let path = dir.join(format!("cover-{:016x}", hasher.finish()));
+        match std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, data)) {
+            Ok(()) => self.art_file = Some(path),
+            Err(e) => log::warn!("Failed to write MPRIS cover art to {:?}: {}", path, e),
+        }
+    }
+
+    /// Publish new player state, emitting `PropertiesChanged` for what changed and
+    /// `Seeked` when the position jumped within the same track
+    pub async fn update(
+        &mut self,
+        snapshot: PlayerSnapshot,
+        position: Duration,
+    ) -> zbus::Result<()> {
+        let now = Instant::now();
+        let seeked = snapshot.track.as_ref().map(|t| &t.track_id)
+            == self.snapshot.track.as_ref().map(|t| &t.track_id)
+            && snapshot.track.is_some()
+            && is_seek(
+                self.position,
+                now.duration_since(self.position_at),
+                self.snapshot.playback_status == PlayState::Playing,
+                position,
+            );
+        let previous = std::mem::replace(&mut self.snapshot, snapshot);
+        self.position = position;
+        self.position_at = now;
+
+        let iface_ref = self
+            .connection
+            .object_server()
+            .interface::<_, Player>(OBJECT_PATH)
+            .await?;
+        {
+            let mut iface = iface_ref.get_mut().await;
+            iface.snapshot = self.snapshot.clone();
+            iface.position = position;
+            iface.position_at = now;
+        }
+
+        if previous == self.snapshot && !seeked {
+            return Ok(());
+        }
+
+        let emitter = iface_ref.signal_emitter();
+        let iface = iface_ref.get().await;
+        if previous.playback_status != self.snapshot.playback_status {
+            iface.playback_status_changed(emitter).await?;
+        }
+        if previous.loop_status != self.snapshot.loop_status {
+            iface.loop_status_changed(emitter).await?;
+        }
+        if previous.shuffle != self.snapshot.shuffle {
+            iface.shuffle_changed(emitter).await?;
+        }
+        if previous.volume != self.snapshot.volume {
+            iface.volume_changed(emitter).await?;
+        }
+        if previous.track != self.snapshot.track {
+            iface.metadata_changed(emitter).await?;
+            if previous.track.is_some() != self.snapshot.track.is_some() {
+                iface.can_seek_changed(emitter).await?;
+            }
+        }
+        if seeked {
+            Player::seeked(emitter, as_micros(position)).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MprisServer {
+    fn drop(&mut self) {
+        if let Some(path) = self.art_file.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn cover_art_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("zarumet").join("mpris"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::mpris::state::{TrackMetadata, track_id};
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+
+    /// Private `dbus-daemon --session` killed on drop
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        fn spawn() -> Self {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("failed to start dbus-daemon");
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+            Self {
+                daemon,
+                address: address.trim().to_string(),
+            }
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    fn snapshot() -> PlayerSnapshot {
+        PlayerSnapshot {
+            playback_status: PlayState::Playing,
+            loop_status: LoopStatus::Playlist,
+            shuffle: true,
+            volume: 0.4,
+            track: Some(TrackMetadata {
+                track_id: track_id(7),
+                title: "Song".to_string(),
+                artist: "Artist".to_string(),
+                album: "Album".to_string(),
+                track_number: 3,
+                disc_number: 1,
+                length: Some(Duration::from_secs(200)),
+                art_url: Some("file:///tmp/cover".to_string()),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_metadata_map() {
+        let map = metadata_map(&snapshot());
+        assert_eq!(
+            map["xesam:title"],
+            OwnedValue::try_from(Value::from("Song")).unwrap()
+        );
+        assert_eq!(map["mpris:length"], OwnedValue::from(200_000_000i64));
+        assert!(map.contains_key("mpris:artUrl"));
+
+        let empty = metadata_map(&PlayerSnapshot::default());
+        assert_eq!(empty.len(), 1);
+        assert!(empty.contains_key("mpris:trackid"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires dbus-daemon; run with --ignored
+    async fn test_server_on_private_bus() {
+        let bus = PrivateBus::spawn();
+        let builder = zbus::connection::Builder::address(bus.address.as_str()).unwrap();
+        let mut server = MprisServer::start_with(builder).await.unwrap();
+        server
+            .update(snapshot(), Duration::from_secs(12))
+            .await
+            .unwrap();
+
+        let client = zbus::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let player = zbus::Proxy::new(
+            &client,
+            BUS_NAME,
+            OBJECT_PATH,
+            "org.mpris.MediaPlayer2.Player",
+        )
+        .await
+        .unwrap();
+
+        let status: String = player.get_property("PlaybackStatus").await.unwrap();
+        assert_eq!(status, "Playing");
+        let loop_status: String = player.get_property("LoopStatus").await.unwrap();
+        assert_eq!(loop_status, "Playlist");
+        let position: i64 = player.get_property("Position").await.unwrap();
+        assert!(position >= 12_000_000);
+        let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").await.unwrap();
+        assert_eq!(
+            metadata["xesam:album"],
+            OwnedValue::try_from(Value::from("Album")).unwrap()
+        );
+
+        let _: () = player.call("PlayPause", &()).await.unwrap();
+        let _: () = player.call("Seek", &(5_000_000i64)).await.unwrap();
+        player.set_property("Shuffle", false).await.unwrap();
+        player.set_property("Volume", 0.75).await.unwrap();
+        player.set_property("LoopStatus", "Track").await.unwrap();
+
+        assert_eq!(server.try_recv_command(), Some(MprisCommand::PlayPause));
+        assert_eq!(
+            server.try_recv_command(),
+            Some(MprisCommand::Seek(5_000_000))
+        );
+        assert_eq!(
+            server.try_recv_command(),
+            Some(MprisCommand::SetShuffle(false))
+        );
+        assert_eq!(
+            server.try_recv_command(),
+            Some(MprisCommand::SetVolume(0.75))
+        );
+        assert_eq!(
+            server.try_recv_command(),
+            Some(MprisCommand::SetLoopStatus(LoopStatus::Track))
+        );
+        assert_eq!(server.try_recv_command(), None);
+    }
+}
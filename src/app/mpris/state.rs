@@ -0,0 +1,214 @@
+use crate::app::SongInfo;
+use mpd_client::commands::SingleMode;
+use mpd_client::responses::{PlayState, Status};
+use std::time::Duration;
+
+/// Object path prefix for `mpris:trackid` (the `/org/mpris` namespace is reserved)
+pub const TRACK_ID_PREFIX: &str = "/org/zarumet/track/";
+
+/// Drift between the expected and reported position that counts as a seek.
+/// Larger than the progress polling interval so normal playback never triggers it.
+const SEEK_TOLERANCE: Duration = Duration::from_millis(1500);
+
+/// `LoopStatus` values of the MPRIS Player interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopStatus {
+    None,
+    Track,
+    Playlist,
+}
+
+impl LoopStatus {
+    /// Map MPD repeat/single to a loop status (single without repeat stops after the song)
+    pub fn from_mpd(repeat: bool, single: SingleMode) -> Self {
+        match (repeat, single) {
+            (true, SingleMode::Enabled) => LoopStatus::Track,
+            (true, _) => LoopStatus::Playlist,
+            (false, _) => LoopStatus::None,
+        }
+    }
+
+    /// MPD repeat flag and single mode that give this loop status
+    pub fn to_mpd(self) -> (bool, SingleMode) {
+        match self {
+            LoopStatus::None => (false, SingleMode::Disabled),
+            LoopStatus::Track => (true, SingleMode::Enabled),
+            LoopStatus::Playlist => (true, SingleMode::Disabled),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LoopStatus::None => "None",
+            LoopStatus::Track => "Track",
+            LoopStatus::Playlist => "Playlist",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "None" => Some(LoopStatus::None),
+            "Track" => Some(LoopStatus::Track),
+            "Playlist" => Some(LoopStatus::Playlist),
+            _ => None,
+        }
+    }
+}
+
+/// Metadata of the current track, as exposed in the `Metadata` property
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackMetadata {
+    /// `mpris:trackid`, derived from the MPD song id
+    pub track_id: String,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub track_number: u64,
+    pub disc_number: u64,
+    pub length: Option<Duration>,
+    /// `file://` URL of the cover art written to the cache directory
+    pub art_url: Option<String>,
+}
+
+/// Everything the Player interface exposes apart from the position
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerSnapshot {
+    pub playback_status: PlayState,
+    pub loop_status: LoopStatus,
+    pub shuffle: bool,
+    /// 0.0 to 1.0
+    pub volume: f64,
+    pub track: Option<TrackMetadata>,
+}
+
+impl Default for PlayerSnapshot {
+    fn default() -> Self {
+        Self {
+            playback_status: PlayState::Stopped,
+            loop_status: LoopStatus::None,
+            shuffle: false,
+            volume: 0.0,
+            track: None,
+        }
+    }
+}
+
+impl PlayerSnapshot {
+    pub fn new(
+        current_song: &Option<SongInfo>,
+        status: &Option<Status>,
+        art_url: Option<&str>,
+    ) -> Self {
+        let Some(status) = status else {
+            return Self::default();
+        };
+        let track = match (current_song, status.current_song) {
+            (Some(song), Some((_, id))) => Some(TrackMetadata {
+                track_id: track_id(id.0),
+                title: song.title.clone(),
+                artist: song.artist.clone(),
+                album: song.album.clone(),
+                track_number: song.track_number,
+                disc_number: song.disc_number,
+                length: song.duration,
+                art_url: art_url.map(str::to_string),
+            }),
+            _ => None,
+        };
+        Self {
+            playback_status: status.state,
+            loop_status: LoopStatus::from_mpd(status.repeat, status.single),
+            shuffle: status.random,
+            volume: f64::from(status.volume) / 100.0,
+            track,
+        }
+    }
+
+    /// `PlaybackStatus` property value
+    pub fn playback_status_str(&self) -> &'static str {
+        match self.playback_status {
+            PlayState::Playing => "Playing",
+            PlayState::Paused => "Paused",
+            PlayState::Stopped => "Stopped",
+        }
+    }
+}
+
+pub fn track_id(song_id: u64) -> String {
+    format!("{}{}", TRACK_ID_PREFIX, song_id)
+}
+
+/// MPD volume (0-100) for an MPRIS volume, clamping out of range values
+pub fn volume_percent(volume: f64) -> u8 {
+    (volume.clamp(0.0, 1.0) * 100.0).round() as u8
+}
+
+/// Whether the position jumped compared to where playback should be by now
+pub fn is_seek(
+    previous: Duration,
+    elapsed_since: Duration,
+    was_playing: bool,
+    current: Duration,
+) -> bool {
+    let expected = if was_playing {
+        previous + elapsed_since
+    } else {
+        previous
+    };
+    expected.abs_diff(current) > SEEK_TOLERANCE
+}
+
+/// Microseconds, the time unit of MPRIS
+pub fn as_micros(duration: Duration) -> i64 {
+    i64::try_from(duration.as_micros()).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loop_status_round_trip() {
+        for status in [LoopStatus::None, LoopStatus::Track, LoopStatus::Playlist] {
+            let (repeat, single) = status.to_mpd();
+            assert_eq!(LoopStatus::from_mpd(repeat, single), status);
+            assert_eq!(LoopStatus::parse(status.as_str()), Some(status));
+        }
+        // Single without repeat stops after the song, which MPRIS has no value for
+        assert_eq!(
+            LoopStatus::from_mpd(false, SingleMode::Enabled),
+            LoopStatus::None
+        );
+        assert_eq!(
+            LoopStatus::from_mpd(true, SingleMode::Oneshot),
+            LoopStatus::Playlist
+        );
+        assert_eq!(LoopStatus::parse("track"), None);
+    }
+
+    #[test]
+    fn test_volume_percent_clamps() {
+        assert_eq!(volume_percent(0.5), 50);
+        assert_eq!(volume_percent(1.7), 100);
+        assert_eq!(volume_percent(-0.2), 0);
+    }
+
+    #[test]
+    fn test_is_seek() {
+        let secs = Duration::from_secs;
+        // Normal playback and polling jitter
+        assert!(!is_seek(secs(10), secs(1), true, secs(11)));
+        assert!(!is_seek(
+            secs(10),
+            Duration::from_millis(500),
+            true,
+            secs(11)
+        ));
+        // Paused position does not move
+        assert!(!is_seek(secs(10), secs(30), false, secs(10)));
+        assert!(is_seek(secs(10), secs(1), false, secs(40)));
+        // Jumps in either direction
+        assert!(is_seek(secs(10), secs(1), true, secs(60)));
+        assert!(is_seek(secs(60), secs(1), true, secs(5)));
+    }
+}
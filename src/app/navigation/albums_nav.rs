@@ -1,5 +1,4 @@
 use crate::App;
-use log::error;
 use mpd_client::{Client, commands};
 
 impl App {
@@ -23,11 +22,11 @@ impl App {
                 ))
                 .await
             {
-                error!("Error adding song to queue: {}", e);
+                self.report_mpd_error("Error adding song to queue", &e);
             } else if queue_was_empty {
                 // Start playback if queue was empty
                 if let Err(e) = client.command(commands::Play::current()).await {
-                    error!("Error starting playback: {}", e);
+                    self.report_mpd_error("Error starting playback", &e);
                 }
             }
         }
@@ -45,17 +44,21 @@ impl App {
         {
             // Add all songs from the album to queue
             let queue_was_empty = self.queue.is_empty();
+            let mut add_error = None;
             for song in &album.tracks {
                 if let Err(e) = client
                     .command(commands::Add::uri(song.file_path.to_str().unwrap()))
                     .await
                 {
-                    error!("Error adding song to queue: {}", e);
+                    add_error = Some(e);
                 }
             }
+            if let Some(e) = add_error {
+                self.report_mpd_error("Error adding song to queue", &e);
+            }
             // Start playback if queue was empty
             if queue_was_empty && let Err(e) = client.command(commands::Play::current()).await {
-                error!("Error starting playback: {}", e);
+                self.report_mpd_error("Error starting playback", &e);
             }
         }
         Ok(())
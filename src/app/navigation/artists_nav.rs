@@ -1,13 +1,12 @@
 use crate::App;
-use crate::app::ui::{DisplayItem, compute_album_display_list};
-use log::error;
+use crate::app::ui::{DisplayItem, ListKind, compute_album_display_list};
 use mpd_client::{Client, commands};
 
 impl App {
     /// Handle album expansion toggle
     pub async fn handle_album_toggle(&mut self, client: &Client) -> color_eyre::Result<()> {
         if let (Some(library), Some(selected_artist_index)) =
-            (&self.library, self.artist_list_state.selected())
+            (self.artists_library(), self.artist_list_state.selected())
             && let Some(selected_artist) = library.get_artist(selected_artist_index)
             && let Some(display_index) = self.album_display_list_state.selected()
         {
@@ -33,17 +32,19 @@ impl App {
                             .command(commands::Add::uri(file_path.to_str().unwrap()))
                             .await
                         {
-                            error!("Error adding song to queue: {}", e);
+                            self.report_mpd_error("Error adding song to queue", &e);
                         } else if queue_was_empty {
                             // Start playback if queue was empty
                             if let Err(e) = client.command(commands::Play::current()).await {
-                                error!("Error starting playback: {}", e);
+                                self.report_mpd_error("Error starting playback", &e);
                             }
                         }
                     }
                 }
             }
         }
+        // Rows below the toggled album moved
+        self.sync_marks(ListKind::ArtistAlbums);
         // Mark library dirty for album expansion changes
         self.dirty.mark_library();
         Ok(())
@@ -56,7 +57,7 @@ impl App {
         client: &Client,
     ) -> color_eyre::Result<()> {
         if let (Some(library), Some(selected_artist_index)) =
-            (&self.library, self.artist_list_state.selected())
+            (self.artists_library(), self.artist_list_state.selected())
             && let Some(selected_artist) = library.get_artist(selected_artist_index)
             && let Some(display_index) = self.album_display_list_state.selected()
         {
@@ -78,13 +79,13 @@ impl App {
                                     .command(commands::Add::uri(song.file_path.to_str().unwrap()))
                                     .await
                                 {
-                                    error!("Error adding song to queue: {}", e);
+                                    self.report_mpd_error("Error adding song to queue", &e);
                                 }
                             }
                             if queue_was_empty
                                 && let Err(e) = client.command(commands::Play::current()).await
                             {
-                                error!("Error starting playback: {}", e);
+                                self.report_mpd_error("Error starting playback", &e);
                             }
                         }
                     }
@@ -95,11 +96,11 @@ impl App {
                             .command(commands::Add::uri(file_path.to_str().unwrap()))
                             .await
                         {
-                            error!("Error adding song to queue: {}", e);
+                            self.report_mpd_error("Error adding song to queue", &e);
                         } else if queue_was_empty
                             && let Err(e) = client.command(commands::Play::current()).await
                         {
-                            error!("Error starting playback: {}", e);
+                            self.report_mpd_error("Error starting playback", &e);
                         }
                     }
                 }
@@ -0,0 +1,114 @@
+use crate::App;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::navigation::helpers::step_selection;
+use crate::app::song::browse::{BrowseEntry, list_directory};
+use mpd_client::{Client, commands, protocol::command::Command as RawCommand};
+
+impl App {
+    /// List `path` and show it in the Browse view
+    pub async fn browse_directory(
+        &mut self,
+        path: String,
+        selected: Option<usize>,
+        client: &Client,
+    ) {
+        match list_directory(client, &path).await {
+            Ok(entries) => {
+                self.browse.set_entries(path, entries, selected);
+                self.dirty.mark_library();
+            }
+            Err(e) => self.report_mpd_error("Error listing directory", &e),
+        }
+    }
+
+    /// Re-list the current directory, keeping the selection
+    pub async fn refresh_browse(&mut self, client: &Client) {
+        let path = self.browse.path.clone();
+        let selected = self.browse.list_state.selected();
+        self.browse_directory(path, selected, client).await;
+    }
+
+    /// Move the selection in the Browse list
+    pub fn handle_browse_navigation(&mut self, action: MPDAction) {
+        let state = &mut self.browse.list_state;
+        if let Some(new_index) =
+            step_selection(&action, state.selected(), self.browse.entries.len())
+        {
+            state.select(Some(new_index));
+        }
+    }
+
+    /// Open the selected entry: enter a directory, or play a song
+    pub async fn handle_browse_open(&mut self, client: &Client) {
+        match self.browse.selected_entry().cloned() {
+            Some(BrowseEntry::Directory(path)) => {
+                let selected = self.browse.list_state.selected();
+                self.browse.parent_selections.push(selected);
+                self.browse_directory(path, None, client).await;
+            }
+            Some(BrowseEntry::Song(song)) => {
+                let uri = song.file_path.to_string_lossy().into_owned();
+                match client.command(commands::Add::uri(&uri)).await {
+                    Ok(song_id) => {
+                        if let Err(e) = client.command(commands::Play::song(song_id)).await {
+                            self.report_mpd_error("Error playing song", &e);
+                        }
+                    }
+                    Err(e) => self.report_mpd_error("Error adding song to queue", &e),
+                }
+            }
+            // Playlist files are appended like `add_to_queue` does
+            Some(BrowseEntry::Playlist(_)) => self.handle_browse_add(client).await,
+            None => {}
+        }
+    }
+
+    /// Go up to the parent directory, selecting the directory we came from
+    pub async fn handle_browse_parent(&mut self, client: &Client) {
+        let Some(parent) = self.browse.parent_path() else {
+            return;
+        };
+        // Without a saved selection (e.g. after a reconnect) find the directory by name
+        let selected = self.browse.parent_selections.pop().flatten();
+        let current = self.browse.path.clone();
+        self.browse_directory(parent, selected, client).await;
+        if selected.is_none()
+            && let Some(idx) = self
+                .browse
+                .entries
+                .iter()
+                .position(|entry| matches!(entry, BrowseEntry::Directory(p) if *p == current))
+        {
+            self.browse.list_state.select(Some(idx));
+        }
+    }
+
+    /// Add the selected entry to the queue; directories are added recursively
+    pub async fn handle_browse_add(&mut self, client: &Client) {
+        let Some(entry) = self.browse.selected_entry().cloned() else {
+            return;
+        };
+
+        let queue_was_empty = self.queue.is_empty();
+        let path = entry.path();
+        let result = match entry {
+            // `add` takes directories too, unlike `addid`
+            BrowseEntry::Directory(_) | BrowseEntry::Song(_) => client
+                .raw_command(RawCommand::new("add").argument(path.as_str()))
+                .await
+                .map(|_| ()),
+            BrowseEntry::Playlist(_) => client.command(commands::LoadPlaylist::name(&path)).await,
+        };
+
+        match result {
+            Err(e) => self.report_mpd_error("Error adding to queue", &e),
+            // Start playback if queue was empty
+            Ok(()) if queue_was_empty => {
+                if let Err(e) = client.command(commands::Play::current()).await {
+                    self.report_mpd_error("Error starting playback", &e);
+                }
+            }
+            Ok(()) => {}
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::App;
+use crate::app::ui::filter::{FilterTarget, find_matches, next_match};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mpd_client::Client;
+
+impl App {
+    /// Text each item of a filter target is matched against
+    fn filter_candidates(&self, target: FilterTarget) -> Vec<String> {
+        match target {
+            FilterTarget::Queue => self
+                .queue
+                .iter()
+                .map(|song| format!("{} {} {}", song.title, song.artist, song.album))
+                .collect(),
+            FilterTarget::Artists => self
+                .artists_library()
+                .map(|library| library.artists.iter().map(|a| a.name.clone()).collect())
+                .unwrap_or_default(),
+            FilterTarget::AlbumList => self
+                .library
+                .as_ref()
+                .map(|library| {
+                    library
+                        .all_albums
+                        .iter()
+                        .map(|(artist, album)| format!("{} {}", album.name, artist))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            FilterTarget::AlbumTracks => self
+                .library
+                .as_ref()
+                .zip(self.all_albums_list_state.selected())
+                .and_then(|(library, idx)| library.all_albums.get(idx))
+                .map(|(_, album)| album.tracks.iter().map(|t| t.title.clone()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Current selection in the list of a filter target
+    fn filter_selection(&self, target: FilterTarget) -> Option<usize> {
+        match target {
+            FilterTarget::Queue => self.queue_list_state.selected(),
+            FilterTarget::Artists => self.artist_list_state.selected(),
+            FilterTarget::AlbumList => self.all_albums_list_state.selected(),
+            FilterTarget::AlbumTracks => self.album_tracks_list_state.selected(),
+        }
+    }
+
+    /// Select an item in the list of a filter target, the same way navigating to it would
+    pub async fn select_filter_item(
+        &mut self,
+        target: FilterTarget,
+        index: Option<usize>,
+        client: &Client,
+    ) {
+        match target {
+            FilterTarget::Queue => {
+                self.queue_list_state.select(index);
+                self.selected_queue_index = index;
+                self.dirty.mark_queue_selection();
+            }
+            FilterTarget::Artists => {
+                if self.artist_list_state.selected() == index {
+                    return;
+                }
+                self.artist_list_state.select(index);
+                // Clear album selection when changing artists
+                self.album_list_state.select(None);
+                self.album_display_list_state.select(None);
+
+                // Lazy load the newly selected artist's albums
+                if let Some(idx) = index
+                    && let Err(e) = self.load_artist(client, idx).await
+                {
+                    log::warn!("Failed to load artist: {}", e);
+                }
+                self.dirty.mark_library();
+            }
+            FilterTarget::AlbumList => {
+                if self.all_albums_list_state.selected() != index {
+                    self.all_albums_list_state.select(index);
+                    // Reset track selection when changing albums
+                    self.album_tracks_list_state.select(Some(0));
+                }
+                self.dirty.mark_library();
+            }
+            FilterTarget::AlbumTracks => {
+                self.album_tracks_list_state.select(index);
+                self.dirty.mark_library();
+            }
+        }
+    }
+
+    /// Open the `/` filter on the focused panel
+    pub fn open_filter(&mut self) {
+        let Some(target) = FilterTarget::for_panel(&self.menu_mode, &self.panel_focus) else {
+            return;
+        };
+        let len = self.filter_candidates(target).len();
+        let selection = self.filter_selection(target);
+        self.list_filter.open(target, selection, len);
+        self.mark_filter_dirty(target);
+    }
+
+    /// Handle a key press while the filter input is open
+    pub async fn handle_filter_input(
+        &mut self,
+        key: KeyEvent,
+        client: &Client,
+    ) -> color_eyre::Result<()> {
+        let Some(target) = self.list_filter.target else {
+            return Ok(());
+        };
+
+        match key.code {
+            KeyCode::Enter => {
+                // Keep the selected match and show the full list again
+                self.list_filter.close();
+            }
+            KeyCode::Esc => {
+                self.cancel_filter(target, client).await;
+            }
+            KeyCode::Backspace if self.list_filter.query.is_empty() => {
+                self.cancel_filter(target, client).await;
+            }
+            KeyCode::Backspace => {
+                self.list_filter.query.pop();
+                self.refresh_filter_matches(target, client).await;
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.list_filter.query.clear();
+                self.refresh_filter_matches(target, client).await;
+            }
+            KeyCode::Up | KeyCode::Down => {
+                let current = self.filter_selection(target);
+                let forward = key.code == KeyCode::Down;
+                if let Some(idx) = next_match(&self.list_filter.matches, current, forward) {
+                    self.select_filter_item(target, Some(idx), client).await;
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.list_filter.query.push(c);
+                self.refresh_filter_matches(target, client).await;
+            }
+            _ => return Ok(()),
+        }
+        self.mark_filter_dirty(target);
+        Ok(())
+    }
+
+    /// Redraw the list a filter target belongs to
+    fn mark_filter_dirty(&self, target: FilterTarget) {
+        match target {
+            FilterTarget::Queue => self.dirty.mark_queue(),
+            _ => self.dirty.mark_library(),
+        }
+    }
+
+    /// Close the filter and restore the unfiltered list and the selection from before it opened
+    async fn cancel_filter(&mut self, target: FilterTarget, client: &Client) {
+        let saved = self.list_filter.saved_selection;
+        self.list_filter.query.clear();
+        self.list_filter.close();
+        self.select_filter_item(target, saved, client).await;
+    }
+
+    /// Re-run the query and move the selection onto a match if it is no longer on one
+    async fn refresh_filter_matches(&mut self, target: FilterTarget, client: &Client) {
+        let candidates = self.filter_candidates(target);
+        self.list_filter.update_matches(&candidates);
+
+        let current = self.filter_selection(target);
+        if !current.is_some_and(|idx| self.list_filter.matches.contains(&idx))
+            && let Some(&first) = self.list_filter.matches.first()
+        {
+            self.select_filter_item(target, Some(first), client).await;
+        }
+    }
+
+    /// Jump to the next (`n`) or previous (`N`) match of the last filter query
+    pub async fn jump_to_filter_match(&mut self, forward: bool, client: &Client) {
+        if self.list_filter.query.is_empty() {
+            return;
+        }
+        let Some(target) = FilterTarget::for_panel(&self.menu_mode, &self.panel_focus) else {
+            return;
+        };
+
+        let matches = find_matches(&self.list_filter.query, &self.filter_candidates(target));
+        let current = self.filter_selection(target);
+        if let Some(idx) = next_match(&matches, current, forward) {
+            self.select_filter_item(target, Some(idx), client).await;
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use crate::App;
+use crate::app::MenuMode;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::ui::{ListKind, compute_album_display_list};
 use mpd_client::Client;
+use ratatui::widgets::ListState;
 
 impl App {
     /// Preload all albums for Albums view and initialize selection
@@ -24,4 +28,68 @@ impl App {
             self.all_albums_list_state.select(Some(0));
         }
     }
+
+    /// Cache the panel focus of the current mode so it can be restored on return
+    pub fn save_panel_focus(&mut self) {
+        match self.menu_mode {
+            MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
+            MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
+            MenuMode::Search => self.search_panel_focus = self.panel_focus.clone(),
+            MenuMode::Playlists => self.playlists_panel_focus = self.panel_focus.clone(),
+            MenuMode::Queue | MenuMode::Browse => {}
+        }
+    }
+
+    /// Number of items in a clickable or markable list
+    pub fn list_len(&self, list: ListKind) -> usize {
+        let library = self.library.as_ref();
+        match list {
+            ListKind::Queue => self.queue.len(),
+            ListKind::Artists => self
+                .artists_library()
+                .map_or(0, |library| library.artists.len()),
+            ListKind::AllAlbums => library.map_or(0, |library| library.all_albums.len()),
+            ListKind::AlbumTracks => library
+                .zip(self.all_albums_list_state.selected())
+                .and_then(|(library, idx)| library.all_albums.get(idx))
+                .map_or(0, |(_, album)| album.tracks.len()),
+            ListKind::ArtistAlbums => self
+                .artists_library()
+                .zip(self.artist_list_state.selected())
+                .and_then(|(library, idx)| library.get_artist(idx))
+                .map_or(0, |artist| {
+                    compute_album_display_list(&artist, &self.expanded_albums)
+                        .0
+                        .len()
+                }),
+        }
+    }
+}
+
+/// New selection in a list of `len` items after a navigation action.
+/// Up/down wrap around at the edges; scrolling only wraps when already at the edge.
+pub fn step_selection(action: &MPDAction, current: Option<usize>, len: usize) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    let current = current.unwrap_or(0).min(last);
+    let new_index = match action {
+        MPDAction::NavigateUp => current.checked_sub(1).unwrap_or(last),
+        MPDAction::NavigateDown if current < last => current + 1,
+        MPDAction::NavigateDown => 0,
+        MPDAction::ScrollUp if current == 0 => last,
+        MPDAction::ScrollUp => current.saturating_sub(15),
+        MPDAction::ScrollDown if current == last => 0,
+        MPDAction::ScrollDown => (current + 15).min(last),
+        MPDAction::GoToTop => 0,
+        MPDAction::GoToBottom => last,
+        _ => return None,
+    };
+    Some(new_index)
+}
+
+/// Scroll so the selected item stays on the screen line it was on at `old_row`, e.g.
+/// after items were added or removed above it
+pub fn anchor_offset(state: &mut ListState, old_row: Option<usize>, old_offset: usize) {
+    if let (Some(old_row), Some(new_row)) = (old_row, state.selected()) {
+        *state.offset_mut() = (old_offset + new_row).saturating_sub(old_row).min(new_row);
+    }
 }
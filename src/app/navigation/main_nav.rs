@@ -1,4 +1,3 @@
-use log::error;
 use mpd_client::{Client, commands};
 
 use crate::App;
@@ -42,7 +41,10 @@ impl Navigation for App {
                     MenuMode::Artists => {
                         // Navigation is now handled by NavigateUp/Down actions based on panel focus
                     }
-                    MenuMode::Albums => {
+                    MenuMode::Albums
+                    | MenuMode::Search
+                    | MenuMode::Playlists
+                    | MenuMode::Browse => {
                         // Navigation is handled by NavigateUp/Down actions based on panel focus
                     }
                 }
@@ -65,7 +67,10 @@ impl Navigation for App {
                     MenuMode::Artists => {
                         // Navigation is now handled by NavigateUp/Down actions based on panel focus
                     }
-                    MenuMode::Albums => {
+                    MenuMode::Albums
+                    | MenuMode::Search
+                    | MenuMode::Playlists
+                    | MenuMode::Browse => {
                         // Navigation is handled by NavigateUp/Down actions based on panel focus
                     }
                 }
@@ -82,7 +87,7 @@ impl Navigation for App {
                                 .command(mpd_client::commands::Play::song(song_position))
                                 .await
                             {
-                                error!("Error playing selected song: {}", e);
+                                self.report_mpd_error("Error playing selected song", &e);
                             }
                         }
                     }
@@ -91,11 +96,38 @@ impl Navigation for App {
                         // Note: In AlbumList panel, binds.rs maps this to SwitchPanelRight
                         self.handle_add_song_in_album_view(client).await?;
                     }
+                    MenuMode::Search => {
+                        // Search mode: add selected result and play it (AlbumTracks panel)
+                        self.handle_play_search_result(client).await?;
+                    }
+                    MenuMode::Playlists => {
+                        // Playlists mode: replace the queue with the playlist and play the selected entry
+                        self.handle_load_playlist(client).await;
+                    }
+                    MenuMode::Browse => {
+                        // Browse mode: enter the selected directory or play the selected song
+                        self.handle_browse_open(client).await;
+                    }
                     MenuMode::Artists => {
                         // Artists mode: handled via ToggleAlbumExpansion in binds.rs
                     }
                 }
             }
+            // Marked songs are removed or moved as a block
+            MPDAction::RemoveFromQueue if self.has_marked_items() => {
+                self.handle_remove_marked(client).await;
+            }
+            MPDAction::MoveUpInQueue | MPDAction::MoveDownInQueue if self.has_marked_items() => {
+                self.handle_move_marked(matches!(action, MPDAction::MoveUpInQueue), client)
+                    .await;
+            }
+            MPDAction::MoveUpInQueue | MPDAction::MoveDownInQueue
+                if self.menu_mode == MenuMode::Playlists =>
+            {
+                // Reorder the selected playlist entry instead of the queue
+                self.handle_move_in_playlist(matches!(action, MPDAction::MoveUpInQueue), client)
+                    .await;
+            }
             MPDAction::MoveUpInQueue => {
                 if let Some(selected) = self.queue_list_state.selected()
                     && selected > 0
@@ -108,9 +140,12 @@ impl Navigation for App {
                         .command(mpd_client::commands::Move::position(from_pos).to_position(to_pos))
                         .await
                     {
-                        error!("Error moving song up in queue: {}", e);
+                        self.report_mpd_error("Error moving song up in queue", &e);
                     } else {
-                        // Update selected index to follow the moved song
+                        // Move it here too, so the selection stays on it and the
+                        // queue sync only confirms the move
+                        self.queue.swap(selected, selected - 1);
+                        self.mark_moved_here(&[selected, selected - 1]);
                         self.queue_list_state.select(Some(selected - 1));
                         self.selected_queue_index = self.queue_list_state.selected();
                         self.dirty.mark_queue();
@@ -128,9 +163,12 @@ impl Navigation for App {
                         .command(mpd_client::commands::Move::position(from_pos).to_position(to_pos))
                         .await
                     {
-                        error!("Error moving song down in queue: {}", e);
+                        self.report_mpd_error("Error moving song down in queue", &e);
                     } else {
-                        // Update selected index to follow the moved song
+                        // Move it here too, so the selection stays on it and the
+                        // queue sync only confirms the move
+                        self.queue.swap(selected, selected + 1);
+                        self.mark_moved_here(&[selected, selected + 1]);
                         self.queue_list_state.select(Some(selected + 1));
                         self.selected_queue_index = self.queue_list_state.selected();
                         self.dirty.mark_queue();
@@ -138,6 +176,9 @@ impl Navigation for App {
                 }
             }
 
+            MPDAction::RemoveFromQueue if self.menu_mode == MenuMode::Playlists => {
+                self.handle_remove_in_playlists(client).await;
+            }
             MPDAction::RemoveFromQueue => {
                 if let Some(selected) = self.queue_list_state.selected()
                     && selected < self.queue.len()
@@ -148,7 +189,7 @@ impl Navigation for App {
                         .command(mpd_client::commands::Delete::position(song_position))
                         .await
                     {
-                        error!("Error removing song from queue: {}", e);
+                        self.report_mpd_error("Error removing song from queue", &e);
                     } else {
                         // Update selected index to stay within bounds
                         if self.queue.is_empty() {
@@ -178,12 +219,6 @@ impl Navigation for App {
 
                         self.library_reload_pending = true;
 
-                        self.pending_artist_index = self.library.as_ref().and_then(|lib| {
-                            self.artist_list_state
-                                .selected()
-                                .and_then(|idx| lib.artists.get(idx).map(|a| a.name.clone()))
-                        });
-
                         self.set_status_message(StatusMessage {
                             text: String::new(),
                             created_at: std::time::Instant::now(),
@@ -191,35 +226,20 @@ impl Navigation for App {
                         })
                     }
                     Err(e) => {
-                        error!("Failed to start MPD database update: {}", e);
-                        // Continue with library reload anyway
-                        //
-                        self.set_status_message(StatusMessage {
-                            text: String::new(),
-                            created_at: std::time::Instant::now(),
-                            message_type: MessageType::Error,
-                        })
+                        self.report_mpd_error("Failed to start MPD database update", &e);
                     }
                 }
             }
             MPDAction::SwitchToQueueMenu => {
                 // Save current panel focus before leaving
-                match self.menu_mode {
-                    MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
-                }
+                self.save_panel_focus();
                 self.menu_mode = MenuMode::Queue;
                 self.dirty.mark_menu_mode();
                 // Queue mode doesn't use panel focus
             }
             MPDAction::SwitchToArtists => {
                 // Save current panel focus before leaving
-                match self.menu_mode {
-                    MenuMode::Artists => {} // Already in Artists mode
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
-                }
+                self.save_panel_focus();
                 self.menu_mode = MenuMode::Artists;
                 // Restore cached panel focus for Artists mode
                 self.panel_focus = self.artists_panel_focus.clone();
@@ -227,11 +247,7 @@ impl Navigation for App {
             }
             MPDAction::SwitchToAlbums => {
                 // Save current panel focus before leaving
-                match self.menu_mode {
-                    MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => {} // Already in Albums mode
-                    MenuMode::Queue => {}
-                }
+                self.save_panel_focus();
                 self.menu_mode = MenuMode::Albums;
                 // Restore cached panel focus for Albums mode
                 self.panel_focus = self.albums_panel_focus.clone();
@@ -239,12 +255,41 @@ impl Navigation for App {
 
                 self.preload_albums_for_view(client).await;
             }
+            MPDAction::SwitchToSearch => {
+                // Pressing the bind again while in Search mode goes back to the query input
+                if self.menu_mode == MenuMode::Search || self.search.results.is_empty() {
+                    self.search.input_active = true;
+                }
+                self.save_panel_focus();
+                self.menu_mode = MenuMode::Search;
+                // Restore cached panel focus for Search mode
+                self.panel_focus = self.search_panel_focus.clone();
+                self.dirty.mark_menu_mode();
+            }
+            MPDAction::SwitchToPlaylists => {
+                self.save_panel_focus();
+                self.menu_mode = MenuMode::Playlists;
+                // Restore cached panel focus for Playlists mode
+                self.panel_focus = self.playlists_panel_focus.clone();
+                self.dirty.mark_menu_mode();
+
+                self.refresh_playlists(client).await;
+            }
+            MPDAction::SwitchToBrowse => {
+                self.save_panel_focus();
+                self.menu_mode = MenuMode::Browse;
+                self.dirty.mark_menu_mode();
+
+                // List the current directory (the music root the first time)
+                self.refresh_browse(client).await;
+            }
             MPDAction::SwitchPanelLeft => {
                 match self.menu_mode {
                     MenuMode::Artists => {
                         match self.panel_focus {
                             PanelFocus::Artists => {
-                                // Already at leftmost panel
+                                // Leftmost panel: back up a level of the tag view, if any
+                                self.close_tag_level();
                             }
                             PanelFocus::Albums => {
                                 self.panel_focus = PanelFocus::Artists;
@@ -259,7 +304,7 @@ impl Navigation for App {
                             }
                         }
                     }
-                    MenuMode::Albums => {
+                    MenuMode::Albums | MenuMode::Search | MenuMode::Playlists => {
                         match self.panel_focus {
                             PanelFocus::AlbumList => {
                                 // Already at leftmost panel
@@ -275,6 +320,10 @@ impl Navigation for App {
                             }
                         }
                     }
+                    MenuMode::Browse => {
+                        // Browse mode: go up to the parent directory
+                        self.handle_browse_parent(client).await;
+                    }
                     MenuMode::Queue => {
                         // Queue mode doesn't have panels
                     }
@@ -285,11 +334,15 @@ impl Navigation for App {
                     MenuMode::Artists => {
                         match self.panel_focus {
                             PanelFocus::Artists => {
+                                // Entries of outer tag view levels open the next level
+                                if self.open_tag_level(client).await {
+                                    return Ok(());
+                                }
                                 self.panel_focus = PanelFocus::Albums;
                                 self.dirty.mark_panel_focus();
                                 // Initialize album selection when switching to albums panel
                                 // only if not already set (preserve position on return)
-                                if let Some(ref library) = self.library
+                                if let Some(library) = self.artists_library()
                                     && let Some(selected_artist_index) =
                                         self.artist_list_state.selected()
                                     && let Some(selected_artist) =
@@ -336,6 +389,34 @@ impl Navigation for App {
                             }
                         }
                     }
+                    MenuMode::Search => {
+                        if self.panel_focus == PanelFocus::AlbumList {
+                            self.panel_focus = PanelFocus::AlbumTracks;
+                            if self.search.tracks_list_state.selected().is_none() {
+                                self.search.tracks_list_state.select(Some(0));
+                            }
+                        } else {
+                            self.panel_focus = PanelFocus::AlbumList;
+                        }
+                        self.dirty.mark_panel_focus();
+                    }
+                    MenuMode::Playlists => {
+                        if self.panel_focus == PanelFocus::AlbumList {
+                            self.panel_focus = PanelFocus::AlbumTracks;
+                            if self.playlists.tracks_list_state.selected().is_none()
+                                && !self.playlists.contents.is_empty()
+                            {
+                                self.playlists.tracks_list_state.select(Some(0));
+                            }
+                        } else {
+                            self.panel_focus = PanelFocus::AlbumList;
+                        }
+                        self.dirty.mark_panel_focus();
+                    }
+                    MenuMode::Browse => {
+                        // Browse mode: enter the selected directory
+                        self.handle_browse_open(client).await;
+                    }
                     MenuMode::Queue => {
                         // Queue mode doesn't have panels
                     }
@@ -347,9 +428,29 @@ impl Navigation for App {
             MPDAction::GoToTop | MPDAction::GoToBottom => {
                 self.handle_go_to_edge(action, client).await;
             }
+            MPDAction::GoToCurrent => {
+                // Show the queue with the playing song selected
+                if self.menu_mode != MenuMode::Queue {
+                    self.save_panel_focus();
+                    self.menu_mode = MenuMode::Queue;
+                    self.dirty.mark_menu_mode();
+                }
+                if let Some((position, _)) = self
+                    .mpd_status
+                    .as_ref()
+                    .and_then(|status| status.current_song)
+                {
+                    self.queue_list_state.select(Some(position.0));
+                    self.selected_queue_index = Some(position.0);
+                    self.dirty.mark_queue_selection();
+                }
+            }
             MPDAction::ToggleAlbumExpansion => {
                 self.handle_album_toggle(client).await?;
             }
+            MPDAction::AddSongToQueue if self.has_marked_items() => {
+                self.handle_add_marked(client).await;
+            }
             MPDAction::AddSongToQueue => {
                 match self.menu_mode {
                     MenuMode::Albums => {
@@ -366,6 +467,18 @@ impl Navigation for App {
                             _ => {}
                         }
                     }
+                    MenuMode::Search => {
+                        // Search mode: song in tracks panel, whole album in album list
+                        self.handle_add_search_result(client).await?;
+                    }
+                    MenuMode::Playlists => {
+                        // Playlists mode: entry in tracks panel, whole playlist in playlist list
+                        self.handle_add_playlist_to_queue(client).await;
+                    }
+                    MenuMode::Browse => {
+                        // Browse mode: song, playlist file, or whole directory recursively
+                        self.handle_browse_add(client).await;
+                    }
                     MenuMode::Artists => {
                         // Artists mode: context-aware based on what's selected
                         // If on a song, add the song; if on an album, add the album
@@ -377,15 +490,26 @@ impl Navigation for App {
                 }
             }
             MPDAction::CycleModeLeft => {
-                // Cycle modes left: Queue -> Albums -> Artists -> Queue
+                // Cycle modes left: Queue -> Browse -> Playlists -> Search -> Albums -> Artists -> Queue
                 // Save current panel focus before leaving
-                match self.menu_mode {
-                    MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
-                }
+                self.save_panel_focus();
                 match self.menu_mode {
                     MenuMode::Queue => {
+                        self.menu_mode = MenuMode::Browse;
+
+                        self.refresh_browse(client).await;
+                    }
+                    MenuMode::Browse => {
+                        self.menu_mode = MenuMode::Playlists;
+                        self.panel_focus = self.playlists_panel_focus.clone();
+
+                        self.refresh_playlists(client).await;
+                    }
+                    MenuMode::Playlists => {
+                        self.menu_mode = MenuMode::Search;
+                        self.panel_focus = self.search_panel_focus.clone();
+                    }
+                    MenuMode::Search => {
                         self.menu_mode = MenuMode::Albums;
                         self.panel_focus = self.albums_panel_focus.clone();
 
@@ -402,13 +526,9 @@ impl Navigation for App {
                 self.dirty.mark_menu_mode();
             }
             MPDAction::CycleModeRight => {
-                // Cycle modes right: Queue -> Artists -> Albums -> Queue
+                // Cycle modes right: Queue -> Artists -> Albums -> Search -> Playlists -> Browse -> Queue
                 // Save current panel focus before leaving
-                match self.menu_mode {
-                    MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
-                }
+                self.save_panel_focus();
                 match self.menu_mode {
                     MenuMode::Queue => {
                         self.menu_mode = MenuMode::Artists;
@@ -421,6 +541,21 @@ impl Navigation for App {
                         self.preload_albums_for_view(client).await;
                     }
                     MenuMode::Albums => {
+                        self.menu_mode = MenuMode::Search;
+                        self.panel_focus = self.search_panel_focus.clone();
+                    }
+                    MenuMode::Search => {
+                        self.menu_mode = MenuMode::Playlists;
+                        self.panel_focus = self.playlists_panel_focus.clone();
+
+                        self.refresh_playlists(client).await;
+                    }
+                    MenuMode::Playlists => {
+                        self.menu_mode = MenuMode::Browse;
+
+                        self.refresh_browse(client).await;
+                    }
+                    MenuMode::Browse => {
                         self.menu_mode = MenuMode::Queue;
                     }
                 };
@@ -429,13 +564,57 @@ impl Navigation for App {
             MPDAction::ScrollUp | MPDAction::ScrollDown => {
                 self.handle_scroll(action, client).await;
             }
+            MPDAction::Filter => {
+                self.open_filter();
+            }
+            MPDAction::FilterNext | MPDAction::FilterPrevious => {
+                self.jump_to_filter_match(matches!(action, MPDAction::FilterNext), client)
+                    .await;
+            }
+            MPDAction::LoadPlaylist => {
+                self.handle_load_playlist(client).await;
+            }
+            MPDAction::SaveQueueAsPlaylist => {
+                self.open_save_playlist_prompt();
+            }
+            MPDAction::RenamePlaylist => {
+                self.open_rename_playlist_prompt();
+            }
+            MPDAction::AddToPlaylist => {
+                self.open_add_to_playlist_prompt(client).await;
+            }
+            MPDAction::ToggleMark => {
+                self.handle_toggle_mark(client).await;
+            }
+            MPDAction::VisualMode => {
+                self.handle_visual_mode();
+            }
+            MPDAction::ClearMarks => {
+                self.handle_clear_marks();
+            }
+            MPDAction::AddNext => {
+                self.handle_add_next(client).await;
+            }
+            MPDAction::Undo | MPDAction::Redo => {
+                self.handle_undo(matches!(action, MPDAction::Redo), client)
+                    .await;
+            }
+            MPDAction::CycleTagView => {
+                if self.menu_mode == MenuMode::Artists {
+                    self.handle_next_tag_view(client).await;
+                }
+            }
+            MPDAction::RaisePriority | MPDAction::LowerPriority => {
+                self.handle_change_priority(matches!(action, MPDAction::RaisePriority), client)
+                    .await;
+            }
             _ => {
                 // Execute MPD command for other actions, passing cached status
                 if let Err(e) = action
                     .execute(client, &self.config, self.mpd_status.as_ref())
                     .await
                 {
-                    error!("Error executing MPD command: {}", e);
+                    self.report_mpd_error("Error executing MPD command", &e);
                 }
             }
         }
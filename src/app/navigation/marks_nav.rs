@@ -0,0 +1,328 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::App;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::ui::{DisplayItem, ListKind, compute_album_display_list};
+use mpd_client::{Client, commands};
+
+impl App {
+    /// Focused list that items can be marked in, with its parent selection and cursor
+    pub fn mark_context(&self) -> Option<(ListKind, Option<usize>, Option<usize>)> {
+        let list = ListKind::markable(&self.menu_mode, &self.panel_focus)?;
+        let cursor = match list {
+            ListKind::Queue => self.queue_list_state.selected(),
+            ListKind::ArtistAlbums => self.album_display_list_state.selected(),
+            ListKind::AllAlbums => self.all_albums_list_state.selected(),
+            ListKind::AlbumTracks => self.album_tracks_list_state.selected(),
+            ListKind::Artists => return None,
+        };
+        Some((list, self.mark_parent(list), cursor))
+    }
+
+    /// Selection in the list whose selected item `list` shows the contents of
+    fn mark_parent(&self, list: ListKind) -> Option<usize> {
+        match list {
+            ListKind::ArtistAlbums => self.artist_list_state.selected(),
+            ListKind::AlbumTracks => self.all_albums_list_state.selected(),
+            ListKind::Queue | ListKind::Artists | ListKind::AllAlbums => None,
+        }
+    }
+
+    /// Identity and song URIs of list items, skipping indices past the end of the list
+    pub fn list_items(
+        &self,
+        list: ListKind,
+        indices: impl IntoIterator<Item = usize>,
+    ) -> Vec<(usize, String, Vec<String>)> {
+        let song_item = |index: usize, song: &crate::app::SongInfo| {
+            let uri = song.file_path.to_string_lossy().into_owned();
+            (index, uri.clone(), vec![uri])
+        };
+
+        match list {
+            ListKind::Queue => indices
+                .into_iter()
+                .filter_map(|i| self.queue.get(i).map(|song| song_item(i, song)))
+                .collect(),
+            ListKind::AllAlbums => {
+                let Some(library) = self.library.as_ref() else {
+                    return Vec::new();
+                };
+                indices
+                    .into_iter()
+                    .filter_map(|i| {
+                        let (artist, album) = library.all_albums.get(i)?;
+                        let uris = album
+                            .tracks
+                            .iter()
+                            .map(|song| song.file_path.to_string_lossy().into_owned())
+                            .collect();
+                        Some((i, format!("{}\n{}", artist, album.name), uris))
+                    })
+                    .collect()
+            }
+            ListKind::AlbumTracks => {
+                let Some((_, album)) = self
+                    .library
+                    .as_ref()
+                    .zip(self.all_albums_list_state.selected())
+                    .and_then(|(library, idx)| library.all_albums.get(idx))
+                else {
+                    return Vec::new();
+                };
+                indices
+                    .into_iter()
+                    .filter_map(|i| album.tracks.get(i).map(|song| song_item(i, song)))
+                    .collect()
+            }
+            ListKind::ArtistAlbums => {
+                let Some(artist) = self
+                    .artists_library()
+                    .zip(self.artist_list_state.selected())
+                    .and_then(|(library, idx)| library.get_artist(idx))
+                else {
+                    return Vec::new();
+                };
+                let (display_items, _album_indices) =
+                    compute_album_display_list(&artist, &self.expanded_albums);
+                indices
+                    .into_iter()
+                    .filter_map(|i| match display_items.get(i)? {
+                        DisplayItem::Album(album_name) => {
+                            let album = artist.albums.iter().find(|a| &a.name == album_name)?;
+                            let uris = album
+                                .tracks
+                                .iter()
+                                .map(|song| song.file_path.to_string_lossy().into_owned())
+                                .collect();
+                            Some((i, album_name.clone(), uris))
+                        }
+                        DisplayItem::Song(_title, _duration, file_path) => {
+                            let uri = file_path.to_string_lossy().into_owned();
+                            Some((i, uri.clone(), vec![uri]))
+                        }
+                    })
+                    .collect()
+            }
+            ListKind::Artists => Vec::new(),
+        }
+    }
+
+    fn mark_list_dirty(&self, list: ListKind) {
+        match list {
+            ListKind::Queue => self.dirty.mark_queue(),
+            _ => self.dirty.mark_library(),
+        }
+    }
+
+    /// Mark or unmark the selected item and move on to the next one
+    pub async fn handle_toggle_mark(&mut self, client: &Client) {
+        let Some((list, parent, Some(cursor))) = self.mark_context() else {
+            return;
+        };
+        let Some((_, key, _)) = self.list_items(list, [cursor]).pop() else {
+            return;
+        };
+        self.marks.toggle(list, parent, cursor, key);
+        self.mark_list_dirty(list);
+
+        // Stop at the last item instead of wrapping around
+        if cursor + 1 < self.list_len(list) {
+            if list == ListKind::Queue {
+                self.queue_list_state.select(Some(cursor + 1));
+                self.selected_queue_index = self.queue_list_state.selected();
+            } else {
+                self.handle_panel_navigation(MPDAction::NavigateDown, client)
+                    .await;
+            }
+        }
+    }
+
+    /// Start a visual range at the selected item, or mark the range and leave visual mode
+    pub fn handle_visual_mode(&mut self) {
+        let Some((list, parent, Some(cursor))) = self.mark_context() else {
+            return;
+        };
+        if self.marks.in_visual(list, parent) {
+            self.commit_visual_range(list, parent, cursor);
+        } else {
+            self.marks.start_visual(list, parent, cursor);
+        }
+        self.mark_list_dirty(list);
+    }
+
+    pub fn handle_clear_marks(&mut self) {
+        if !self.marks.is_empty() {
+            self.marks.clear();
+            self.dirty.mark_queue();
+            self.dirty.mark_library();
+        }
+    }
+
+    /// Turn the visual range into marks
+    fn commit_visual_range(&mut self, list: ListKind, parent: Option<usize>, cursor: usize) {
+        if let Some(range) = self.marks.end_visual(cursor) {
+            for (index, key, _) in self.list_items(list, range) {
+                self.marks.mark(list, parent, index, key);
+            }
+        }
+    }
+
+    /// Whether the focused list has marked items, which actions then work on
+    /// instead of the selected item
+    pub fn has_marked_items(&self) -> bool {
+        self.mark_context().is_some_and(|(list, parent, cursor)| {
+            !self.marks.selected(list, parent, cursor).is_empty()
+        })
+    }
+
+    /// All marked items of the focused list, with the visual range turned into marks
+    pub fn marked_items(&mut self) -> Option<(ListKind, Option<usize>, BTreeSet<usize>)> {
+        let (list, parent, cursor) = self.mark_context()?;
+        if let Some(cursor) = cursor
+            && self.marks.in_visual(list, parent)
+        {
+            self.commit_visual_range(list, parent, cursor);
+        }
+        let marked = self.marks.selected(list, parent, cursor);
+        (!marked.is_empty()).then_some((list, parent, marked))
+    }
+
+    /// Remove the marked songs from the queue in one command list
+    pub async fn handle_remove_marked(&mut self, client: &Client) {
+        let Some((ListKind::Queue, _, marked)) = self.marked_items() else {
+            return;
+        };
+        // Delete from the end so the remaining positions stay valid
+        let deletes: Vec<_> = marked
+            .iter()
+            .rev()
+            .filter(|&&position| position < self.queue.len())
+            .map(|&position| commands::Delete::position(position.into()))
+            .collect();
+
+        if let Err(e) = client.command_list(deletes).await {
+            self.report_mpd_error("Error removing songs from queue", &e);
+            return;
+        }
+        self.marks.clear();
+        if let Some(&first) = marked.first() {
+            // The queue is reloaded right after, which keeps the selection within bounds
+            self.queue_list_state.select(Some(first));
+            self.selected_queue_index = Some(first);
+        }
+        self.dirty.mark_queue();
+    }
+
+    /// Move the marked songs up or down the queue by one position, as a block
+    pub async fn handle_move_marked(&mut self, up: bool, client: &Client) {
+        let Some((ListKind::Queue, _, marked)) = self.marked_items() else {
+            return;
+        };
+        let (Some(&first), Some(&last)) = (marked.first(), marked.last()) else {
+            return;
+        };
+        if (up && first == 0) || (!up && last + 1 >= self.queue.len()) {
+            return;
+        }
+
+        // Move the songs nearest to the destination first so the block stays in order
+        let positions: Vec<usize> = if up {
+            marked.iter().copied().collect()
+        } else {
+            marked.iter().rev().copied().collect()
+        };
+        let target = |position: usize| if up { position - 1 } else { position + 1 };
+        let moves: Vec<_> = positions
+            .iter()
+            .map(|&position| {
+                commands::Move::position(position.into()).to_position(target(position).into())
+            })
+            .collect();
+
+        if let Err(e) = client.command_list(moves).await {
+            self.report_mpd_error("Error moving songs in queue", &e);
+            return;
+        }
+        // Same moves on our copy, which the queue sync then only confirms
+        for &position in &positions {
+            self.queue.swap(position, target(position));
+        }
+        let rows: Vec<usize> = positions
+            .iter()
+            .flat_map(|&position| [position, target(position)])
+            .collect();
+        self.mark_moved_here(&rows);
+
+        let moved: BTreeMap<usize, String> = self
+            .marks
+            .items()
+            .iter()
+            .map(|(&position, key)| (target(position), key.clone()))
+            .collect();
+        self.marks.set(ListKind::Queue, None, moved);
+
+        // Keep the selection on the same song if it was one of the moved ones
+        if let Some(selected) = self.queue_list_state.selected()
+            && marked.contains(&selected)
+        {
+            self.queue_list_state.select(Some(target(selected)));
+            self.selected_queue_index = self.queue_list_state.selected();
+        }
+        self.dirty.mark_queue();
+    }
+
+    /// Add the songs of the marked library items to the queue in one command list
+    pub async fn handle_add_marked(&mut self, client: &Client) {
+        let Some((list, _, marked)) = self.marked_items() else {
+            return;
+        };
+        if list == ListKind::Queue {
+            return;
+        }
+        let uris: Vec<String> = self
+            .list_items(list, marked)
+            .into_iter()
+            .flat_map(|(_, _, uris)| uris)
+            .collect();
+        let adds: Vec<_> = uris.iter().map(|uri| commands::Add::uri(uri)).collect();
+
+        let queue_was_empty = self.queue.is_empty();
+        if let Err(e) = client.command_list(adds).await {
+            self.report_mpd_error("Error adding songs to queue", &e);
+            return;
+        }
+        self.marks.clear();
+        self.mark_list_dirty(list);
+
+        // Start playback if queue was empty
+        if queue_was_empty && let Err(e) = client.command(commands::Play::current()).await {
+            self.report_mpd_error("Error starting playback", &e);
+        }
+    }
+
+    /// Drop marks made in the library lists, e.g. when the library was reloaded
+    pub fn clear_library_marks(&mut self) {
+        if self
+            .marks
+            .list()
+            .is_some_and(|list| list != ListKind::Queue)
+        {
+            self.marks.clear();
+        }
+    }
+
+    /// Keep the marks on the same items after `list` changed
+    pub fn sync_marks(&mut self, list: ListKind) {
+        if self.marks.is_empty() {
+            return;
+        }
+        let len = self.list_len(list);
+        let keys: Vec<String> = self
+            .list_items(list, 0..len)
+            .into_iter()
+            .map(|(_, key, _)| key)
+            .collect();
+        self.marks.sync(list, self.mark_parent(list), &keys);
+    }
+}
@@ -1,8 +1,17 @@
 pub mod albums_nav;
 pub mod artists_nav;
+pub mod browse_nav;
+pub mod filter_nav;
 pub mod helpers;
 pub mod main_nav;
+pub mod marks_nav;
+pub mod mouse_nav;
 pub mod panel_nav;
+pub mod play_next_nav;
+pub mod playlists_nav;
 pub mod scrolling;
+pub mod search_nav;
+pub mod tag_view_nav;
+pub mod undo_nav;
 
 pub use main_nav::Navigation;
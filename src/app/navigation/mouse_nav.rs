@@ -0,0 +1,196 @@
+use crate::App;
+use crate::app::event_handlers::EventHandlers;
+use crate::app::logging::log_user_interaction;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::ui::{
+    DisplayItem, FilterTarget, HIT_MAP, HitTarget, ListKind, MenuMode, PanelFocus,
+    compute_album_display_list, hit_map::horizontal_fraction,
+};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use mpd_client::{Client, commands, responses::PlayState};
+use std::time::{Duration, Instant};
+
+/// Two clicks on the same item within this interval make a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+impl App {
+    /// Handle a mouse event using the regions recorded while rendering the last frame
+    pub async fn handle_mouse_event(
+        &mut self,
+        mouse: MouseEvent,
+        client: &Client,
+    ) -> color_eyre::Result<()> {
+        // Handle config warnings popup - any click closes it
+        if self.show_config_warnings_popup {
+            if matches!(mouse.kind, MouseEventKind::Down(_)) {
+                self.show_config_warnings_popup = false;
+            }
+            return Ok(());
+        }
+
+        // Prompts and text inputs keep all input until they are closed
+        if self.playlists.prompt.is_some()
+            || (self.menu_mode == MenuMode::Search && self.search.input_active)
+            || self.list_filter.is_active()
+        {
+            return Ok(());
+        }
+
+        let Some((area, target)) = HIT_MAP.with(|map| map.borrow().hit(mouse.column, mouse.row))
+        else {
+            return Ok(());
+        };
+
+        let action = match (mouse.kind, target) {
+            (MouseEventKind::Down(MouseButton::Left), HitTarget::List { .. }) => {
+                let Some((list, index)) = target.list_index(area, mouse.row) else {
+                    return Ok(());
+                };
+                if !self.select_list_item(list, index, client).await {
+                    return Ok(());
+                }
+                self.is_double_click(list, index)
+                    .then_some(double_click_action(list))
+            }
+            (MouseEventKind::Down(MouseButton::Left), HitTarget::ProgressBar) => {
+                self.seek_to_fraction(horizontal_fraction(area, mouse.column), client)
+                    .await;
+                None
+            }
+            (MouseEventKind::ScrollUp, HitTarget::List { list, .. }) => {
+                self.focus_list(list);
+                Some(MPDAction::ScrollUp)
+            }
+            (MouseEventKind::ScrollDown, HitTarget::List { list, .. }) => {
+                self.focus_list(list);
+                Some(MPDAction::ScrollDown)
+            }
+            (MouseEventKind::ScrollUp, HitTarget::ProgressBar) => Some(MPDAction::SeekForward),
+            (MouseEventKind::ScrollDown, HitTarget::ProgressBar) => Some(MPDAction::SeekBackward),
+            (MouseEventKind::ScrollUp, HitTarget::Volume) => Some(MPDAction::VolumeUp),
+            (MouseEventKind::ScrollDown, HitTarget::Volume) => Some(MPDAction::VolumeDown),
+            (
+                MouseEventKind::Down(MouseButton::Left) | MouseEventKind::ScrollDown,
+                HitTarget::MenuMode,
+            ) => Some(MPDAction::CycleModeRight),
+            (
+                MouseEventKind::Down(MouseButton::Right) | MouseEventKind::ScrollUp,
+                HitTarget::MenuMode,
+            ) => Some(MPDAction::CycleModeLeft),
+            _ => None,
+        };
+
+        if let Some(action) = action {
+            let context = format!("mouse, menu:{:?}", self.menu_mode);
+            log_user_interaction(&action.to_string(), Some(&context));
+            self.perform_action(action, client).await?;
+        }
+        Ok(())
+    }
+
+    /// Move the panel focus to a clicked list
+    fn focus_list(&mut self, list: ListKind) {
+        let focus = match list {
+            ListKind::Queue => return,
+            ListKind::Artists => PanelFocus::Artists,
+            ListKind::ArtistAlbums => PanelFocus::Albums,
+            ListKind::AllAlbums => PanelFocus::AlbumList,
+            ListKind::AlbumTracks => PanelFocus::AlbumTracks,
+        };
+        if self.panel_focus != focus {
+            self.panel_focus = focus;
+            self.dirty.mark_panel_focus();
+        }
+    }
+
+    /// Focus a list and select one of its items, returning false if there is no item at `index`
+    async fn select_list_item(&mut self, list: ListKind, index: usize, client: &Client) -> bool {
+        let target = match list {
+            ListKind::Queue => FilterTarget::Queue,
+            ListKind::Artists => FilterTarget::Artists,
+            ListKind::AllAlbums => FilterTarget::AlbumList,
+            ListKind::AlbumTracks => FilterTarget::AlbumTracks,
+            ListKind::ArtistAlbums => return self.select_artist_album_row(index),
+        };
+        if index >= self.list_len(list) {
+            return false;
+        }
+        self.focus_list(list);
+        self.select_filter_item(target, Some(index), client).await;
+        true
+    }
+
+    /// Select a row of the selected artist's albums and expanded tracks
+    fn select_artist_album_row(&mut self, index: usize) -> bool {
+        let Some(artist) = self
+            .artists_library()
+            .zip(self.artist_list_state.selected())
+            .and_then(|(library, idx)| library.get_artist(idx))
+        else {
+            return false;
+        };
+        let (display_items, _album_indices) =
+            compute_album_display_list(&artist, &self.expanded_albums);
+        if index >= display_items.len() {
+            return false;
+        }
+
+        self.focus_list(ListKind::ArtistAlbums);
+        self.album_display_list_state.select(Some(index));
+        // Update the legacy album_list_state if on an album
+        if let Some(DisplayItem::Album(_)) = display_items.get(index) {
+            let album_count = display_items[..index]
+                .iter()
+                .filter(|item| matches!(item, DisplayItem::Album(_)))
+                .count();
+            self.album_list_state.select(Some(album_count));
+        }
+        self.dirty.mark_library();
+        true
+    }
+
+    /// Whether a click on a list item completes a double-click
+    fn is_double_click(&mut self, list: ListKind, index: usize) -> bool {
+        let now = Instant::now();
+        let double_click = self.last_click.is_some_and(|(at, last_list, last_index)| {
+            last_list == list
+                && last_index == index
+                && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        });
+        // A third click starts a new double-click
+        self.last_click = (!double_click).then_some((now, list, index));
+        double_click
+    }
+
+    /// Seek the current song to a fraction of its duration
+    async fn seek_to_fraction(&mut self, fraction: f64, client: &Client) {
+        let Some(song) = self.current_song.as_ref() else {
+            return;
+        };
+        let Some(duration) = song.duration else {
+            return;
+        };
+        if matches!(song.play_state, None | Some(PlayState::Stopped)) {
+            return;
+        }
+
+        let position = duration.mul_f64(fraction.clamp(0.0, 1.0));
+        if let Err(e) = client
+            .command(commands::Seek(commands::SeekMode::Absolute(position)))
+            .await
+        {
+            self.report_mpd_error("Error seeking", &e);
+        }
+        self.force_update = true;
+    }
+}
+
+/// What double-clicking an item does, the same as pressing Enter on it
+fn double_click_action(list: ListKind) -> MPDAction {
+    match list {
+        ListKind::Queue => MPDAction::PlaySelected,
+        ListKind::Artists | ListKind::AllAlbums => MPDAction::SwitchPanelRight,
+        ListKind::ArtistAlbums => MPDAction::ToggleAlbumExpansion,
+        ListKind::AlbumTracks => MPDAction::AddSongToQueue,
+    }
+}
@@ -19,7 +19,7 @@ impl App {
                         match self.panel_focus {
                             PanelFocus::Artists => {
                                 // Navigate artists list
-                                if let Some(ref library) = self.library
+                                if let Some(library) = self.artists_library()
                                     && !library.artists.is_empty()
                                 {
                                     let current = self.artist_list_state.selected().unwrap_or(0);
@@ -35,9 +35,7 @@ impl App {
                                     self.album_display_list_state.select(None);
 
                                     // Lazy load the newly selected artist's albums
-                                    if let Some(ref mut library) = self.library
-                                        && let Err(e) = library.load_artist(client, new_index).await
-                                    {
+                                    if let Err(e) = self.load_artist(client, new_index).await {
                                         log::warn!("Failed to load artist: {}", e);
                                     }
                                 }
@@ -45,7 +43,7 @@ impl App {
                             PanelFocus::Albums => {
                                 // Navigate albums list using display list state
                                 if let (Some(library), Some(selected_artist_index)) =
-                                    (&self.library, self.artist_list_state.selected())
+                                    (self.artists_library(), self.artist_list_state.selected())
                                     && let Some(selected_artist) =
                                         library.get_artist(selected_artist_index)
                                 {
@@ -146,6 +144,9 @@ impl App {
                             }
                         }
                     }
+                    MenuMode::Search => self.handle_search_navigation(action),
+                    MenuMode::Playlists => self.handle_playlists_navigation(action, client).await,
+                    MenuMode::Browse => self.handle_browse_navigation(action),
                 }
             }
             MPDAction::NavigateDown => {
@@ -157,7 +158,7 @@ impl App {
                         match self.panel_focus {
                             PanelFocus::Artists => {
                                 // Navigate artists list
-                                if let Some(ref library) = self.library
+                                if let Some(library) = self.artists_library()
                                     && !library.artists.is_empty()
                                 {
                                     let current = self.artist_list_state.selected().unwrap_or(0);
@@ -174,9 +175,7 @@ impl App {
                                     self.album_display_list_state.select(None);
 
                                     // Lazy load the newly selected artist's albums
-                                    if let Some(ref mut library) = self.library
-                                        && let Err(e) = library.load_artist(client, new_index).await
-                                    {
+                                    if let Err(e) = self.load_artist(client, new_index).await {
                                         log::warn!("Failed to load artist: {}", e);
                                     }
                                 }
@@ -184,7 +183,7 @@ impl App {
                             PanelFocus::Albums => {
                                 // Navigate albums list using display list state
                                 if let (Some(library), Some(selected_artist_index)) =
-                                    (&self.library, self.artist_list_state.selected())
+                                    (self.artists_library(), self.artist_list_state.selected())
                                     && let Some(selected_artist) =
                                         library.get_artist(selected_artist_index)
                                 {
@@ -275,6 +274,9 @@ impl App {
                             }
                         }
                     }
+                    MenuMode::Search => self.handle_search_navigation(action),
+                    MenuMode::Playlists => self.handle_playlists_navigation(action, client).await,
+                    MenuMode::Browse => self.handle_browse_navigation(action),
                 }
             }
             _ => {}
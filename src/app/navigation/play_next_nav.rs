@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+
+use crate::App;
+use crate::app::song::browse::BrowseEntry;
+use crate::app::ui::{ListKind, MenuMode};
+use mpd_client::{
+    Client,
+    commands::{self, SongId},
+    protocol::command::{Command as RawCommand, CommandList as RawCommandList},
+};
+
+impl App {
+    /// Position of the current song in the queue, if there is one
+    fn current_queue_position(&self) -> Option<usize> {
+        self.mpd_status
+            .as_ref()
+            .and_then(|status| status.current_song)
+            .map(|(position, _)| position.0)
+    }
+
+    /// Marked queue positions, or the selected one, and whether marks were used
+    fn queue_targets(&mut self) -> (BTreeSet<usize>, bool) {
+        match self.marked_items() {
+            Some((ListKind::Queue, _, marked)) => (marked, true),
+            _ => (
+                self.queue_list_state
+                    .selected()
+                    .filter(|&selected| selected < self.queue.len())
+                    .into_iter()
+                    .collect(),
+                false,
+            ),
+        }
+    }
+
+    /// Song URIs of the marked library items, or of the selected one, and whether
+    /// marks were used
+    fn library_targets(&mut self) -> (Vec<String>, bool) {
+        if let Some((list, _, marked)) = self.marked_items() {
+            let uris = self
+                .list_items(list, marked)
+                .into_iter()
+                .flat_map(|(_, _, uris)| uris)
+                .collect();
+            return (uris, true);
+        }
+
+        let uris = match self.menu_mode {
+            MenuMode::Search => self.selected_search_uris(),
+            // Only songs, as `addid` can't add directories or playlists
+            MenuMode::Browse => match self.browse.selected_entry() {
+                Some(entry @ BrowseEntry::Song(_)) => vec![entry.path()],
+                _ => Vec::new(),
+            },
+            _ => match self.mark_context() {
+                Some((list, _, Some(cursor))) => self
+                    .list_items(list, [cursor])
+                    .into_iter()
+                    .flat_map(|(_, _, uris)| uris)
+                    .collect(),
+                _ => Vec::new(),
+            },
+        };
+        (uris, false)
+    }
+
+    /// Insert the selected or marked songs right after the current song. In the queue
+    /// they are moved there instead.
+    pub async fn handle_add_next(&mut self, client: &Client) {
+        if self.menu_mode == MenuMode::Queue {
+            self.handle_queue_play_next(client).await;
+            return;
+        }
+
+        let (uris, used_marks) = self.library_targets();
+        if uris.is_empty() {
+            return;
+        }
+
+        let result = if self.current_queue_position().is_some() {
+            // Offsets from the current song keep the songs in order
+            let adds: Vec<_> = uris
+                .iter()
+                .enumerate()
+                .map(|(i, uri)| commands::Add::uri(uri).after_current(i))
+                .collect();
+            client.command_list(adds).await.map(|_| ())
+        } else {
+            // Nothing is playing, so there is nothing to insert after
+            let queue_was_empty = self.queue.is_empty();
+            let adds: Vec<_> = uris.iter().map(|uri| commands::Add::uri(uri)).collect();
+            match client.command_list(adds).await {
+                Ok(_) if queue_was_empty => client.command(commands::Play::current()).await,
+                result => result.map(|_| ()),
+            }
+        };
+
+        if let Err(e) = result {
+            self.report_mpd_error("Error adding songs after the current song", &e);
+            return;
+        }
+        if used_marks {
+            self.marks.clear();
+            self.dirty.mark_library();
+        }
+    }
+
+    /// Move the selected or marked queue songs right after the current song
+    async fn handle_queue_play_next(&mut self, client: &Client) {
+        let Some(current) = self.current_queue_position() else {
+            return;
+        };
+        let (positions, used_marks) = self.queue_targets();
+        let moves: Vec<_> = positions
+            .iter()
+            .filter(|&&position| position != current)
+            .filter_map(|&position| self.queue.get(position)?.queue_id)
+            .enumerate()
+            .map(|(i, id)| commands::Move::id(SongId(id)).after_current(i))
+            .collect();
+        if moves.is_empty() {
+            return;
+        }
+
+        if let Err(e) = client.command_list(moves).await {
+            self.report_mpd_error("Error moving songs after the current song", &e);
+            return;
+        }
+        if used_marks {
+            self.marks.clear();
+        }
+        self.dirty.mark_queue();
+    }
+
+    /// Raise or lower the priority of the selected or marked queue songs by one.
+    /// MPD plays songs with a higher priority first in random mode.
+    pub async fn handle_change_priority(&mut self, raise: bool, client: &Client) {
+        if self.menu_mode != MenuMode::Queue {
+            return;
+        }
+        let (positions, _) = self.queue_targets();
+        let mut changes = positions
+            .iter()
+            .filter_map(|&position| self.queue.get(position))
+            .filter_map(|song| {
+                let id = song.queue_id?;
+                let priority = if raise {
+                    song.priority.saturating_add(1)
+                } else {
+                    song.priority.saturating_sub(1)
+                };
+                (priority != song.priority)
+                    .then(|| RawCommand::new("prioid").argument(priority).argument(id))
+            });
+
+        let Some(first) = changes.next() else {
+            return;
+        };
+        let mut list = RawCommandList::new(first);
+        list.extend(changes);
+
+        if let Err(e) = client.raw_command_list(list).await {
+            self.report_mpd_error("Error changing song priority", &e);
+        }
+    }
+}
@@ -0,0 +1,487 @@
+use crate::App;
+use crate::app::navigation::helpers::step_selection;
+use crate::app::song::browse::BrowseEntry;
+use crate::app::song::playlists::{PlaylistPrompt, fetch_playlist_contents, fetch_playlist_names};
+use crate::app::ui::{DisplayItem, compute_album_display_list};
+use crate::app::{MenuMode, PanelFocus, mpd_handler::MPDAction};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mpd_client::{Client, commands};
+
+impl App {
+    /// Reload the stored playlist names and the contents of the selected playlist
+    pub async fn refresh_playlists(&mut self, client: &Client) {
+        match fetch_playlist_names(client).await {
+            Ok(names) => self.playlists.set_names(names),
+            Err(e) => self.report_mpd_error("Error listing playlists", &e),
+        }
+        self.load_selected_playlist(client).await;
+    }
+
+    /// Load the contents of the selected playlist into the tracks panel
+    async fn load_selected_playlist(&mut self, client: &Client) {
+        let contents = match self.playlists.selected_name().map(str::to_string) {
+            Some(name) => match fetch_playlist_contents(client, &name).await {
+                Ok(contents) => contents,
+                Err(e) => {
+                    self.report_mpd_error("Error loading playlist", &e);
+                    Vec::new()
+                }
+            },
+            None => Vec::new(),
+        };
+        self.playlists.set_contents(contents);
+        self.dirty.mark_library();
+    }
+
+    /// Move the selection in the focused Playlists panel
+    pub async fn handle_playlists_navigation(&mut self, action: MPDAction, client: &Client) {
+        match self.panel_focus {
+            PanelFocus::AlbumTracks => {
+                let len = self.playlists.contents.len();
+                let state = &mut self.playlists.tracks_list_state;
+                if let Some(new_index) = step_selection(&action, state.selected(), len) {
+                    state.select(Some(new_index));
+                }
+            }
+            _ => {
+                let current = self.playlists.list_state.selected();
+                let Some(new_index) = step_selection(&action, current, self.playlists.names.len())
+                else {
+                    return;
+                };
+                if current != Some(new_index) {
+                    self.playlists.list_state.select(Some(new_index));
+                    // Start at the top of the newly selected playlist
+                    self.playlists.tracks_list_state.select(Some(0));
+                    self.load_selected_playlist(client).await;
+                }
+            }
+        }
+    }
+
+    /// Replace the queue with the selected playlist and play it,
+    /// starting at the selected entry when the tracks panel is focused
+    pub async fn handle_load_playlist(&mut self, client: &Client) {
+        if self.menu_mode != MenuMode::Playlists {
+            return;
+        }
+        let Some(name) = self.playlists.selected_name().map(str::to_string) else {
+            return;
+        };
+        let position = match self.panel_focus {
+            PanelFocus::AlbumTracks => self.playlists.tracks_list_state.selected().unwrap_or(0),
+            _ => 0,
+        };
+
+        // Append the playlist, then delete the old songs in the same command list. MPD
+        // stops at the first failing command, so a playlist that fails to load leaves
+        // the queue as it was.
+        let length = match client.command(commands::Status).await {
+            Ok(status) => status.playlist_length,
+            Err(e) => {
+                self.report_mpd_error("Error loading playlist", &e);
+                return;
+            }
+        };
+        let load = commands::LoadPlaylist::name(&name);
+        let result = if length == 0 {
+            client.command(load).await
+        } else {
+            let old_songs = commands::SongPosition(0)..commands::SongPosition(length);
+            client
+                .command_list((load, commands::Delete::range(old_songs)))
+                .await
+                .map(|_| ())
+        };
+        if let Err(e) = result {
+            self.report_mpd_error("Error loading playlist", &e);
+            return;
+        }
+        let song_position: commands::SongPosition = position.into();
+        if let Err(e) = client.command(commands::Play::song(song_position)).await {
+            self.report_mpd_error("Error starting playback", &e);
+        }
+    }
+
+    /// Append the selected playlist to the queue, or only the selected entry in the tracks panel
+    pub async fn handle_add_playlist_to_queue(&mut self, client: &Client) {
+        let Some(name) = self.playlists.selected_name().map(str::to_string) else {
+            return;
+        };
+
+        let queue_was_empty = self.queue.is_empty();
+        let result = match self.panel_focus {
+            PanelFocus::AlbumTracks => {
+                let Some(song) = self
+                    .playlists
+                    .tracks_list_state
+                    .selected()
+                    .and_then(|idx| self.playlists.contents.get(idx))
+                else {
+                    return;
+                };
+                let uri = song.file_path.to_string_lossy().into_owned();
+                client.command(commands::Add::uri(&uri)).await.map(|_| ())
+            }
+            _ => client.command(commands::LoadPlaylist::name(&name)).await,
+        };
+
+        match result {
+            Err(e) => self.report_mpd_error("Error adding playlist to queue", &e),
+            // Start playback if queue was empty
+            Ok(()) if queue_was_empty => {
+                if let Err(e) = client.command(commands::Play::current()).await {
+                    self.report_mpd_error("Error starting playback", &e);
+                }
+            }
+            Ok(()) => {}
+        }
+    }
+
+    /// Remove the selected entry from its playlist, or ask to delete the selected playlist
+    pub async fn handle_remove_in_playlists(&mut self, client: &Client) {
+        let Some(name) = self.playlists.selected_name().map(str::to_string) else {
+            return;
+        };
+
+        match self.panel_focus {
+            PanelFocus::AlbumTracks => {
+                let Some(selected) = self
+                    .playlists
+                    .tracks_list_state
+                    .selected()
+                    .filter(|&idx| idx < self.playlists.contents.len())
+                else {
+                    return;
+                };
+                match client
+                    .command(commands::RemoveFromPlaylist::position(&name, selected))
+                    .await
+                {
+                    Ok(()) => {
+                        // Update locally; the stored playlist idle event refreshes it again
+                        self.playlists.contents.remove(selected);
+                        let last = self.playlists.contents.len().checked_sub(1);
+                        self.playlists
+                            .tracks_list_state
+                            .select(last.map(|last| selected.min(last)));
+                        self.dirty.mark_library();
+                    }
+                    Err(e) => self.report_mpd_error("Error removing song from playlist", &e),
+                }
+            }
+            _ => {
+                self.playlists.prompt = Some(PlaylistPrompt::ConfirmDelete { name });
+                self.dirty.mark_library();
+            }
+        }
+    }
+
+    /// Move the selected playlist entry one position up or down
+    pub async fn handle_move_in_playlist(&mut self, up: bool, client: &Client) {
+        if self.panel_focus != PanelFocus::AlbumTracks {
+            return;
+        }
+        let Some(name) = self.playlists.selected_name().map(str::to_string) else {
+            return;
+        };
+        let len = self.playlists.contents.len();
+        let Some(from) = self
+            .playlists
+            .tracks_list_state
+            .selected()
+            .filter(|&idx| idx < len)
+        else {
+            return;
+        };
+        let to = if up {
+            from.checked_sub(1)
+        } else {
+            Some(from + 1).filter(|&to| to < len)
+        };
+        let Some(to) = to else {
+            return;
+        };
+
+        match client
+            .command(commands::MoveInPlaylist::new(&name, from, to))
+            .await
+        {
+            Ok(()) => {
+                // Follow the moved song
+                self.playlists.contents.swap(from, to);
+                self.playlists.tracks_list_state.select(Some(to));
+                self.dirty.mark_library();
+            }
+            Err(e) => self.report_mpd_error("Error moving song in playlist", &e),
+        }
+    }
+
+    /// URIs of the songs selected in the current view, used by "add to playlist"
+    fn selected_song_uris(&self) -> Vec<String> {
+        let uri = |path: &std::path::Path| path.to_string_lossy().into_owned();
+        match self.menu_mode {
+            MenuMode::Queue => self
+                .queue_list_state
+                .selected()
+                .and_then(|idx| self.queue.get(idx))
+                .map(|song| vec![uri(&song.file_path)])
+                .unwrap_or_default(),
+            MenuMode::Artists => {
+                let Some(artist) = self
+                    .artists_library()
+                    .zip(self.artist_list_state.selected())
+                    .and_then(|(library, idx)| library.get_artist(idx))
+                else {
+                    return Vec::new();
+                };
+                if self.panel_focus == PanelFocus::Artists {
+                    // Every loaded album of the artist
+                    return artist
+                        .albums
+                        .iter()
+                        .flat_map(|album| album.tracks.iter())
+                        .map(|song| uri(&song.file_path))
+                        .collect();
+                }
+                let (display_items, _) = compute_album_display_list(&artist, &self.expanded_albums);
+                match self
+                    .album_display_list_state
+                    .selected()
+                    .and_then(|idx| display_items.get(idx))
+                {
+                    Some(DisplayItem::Album(album_name)) => artist
+                        .albums
+                        .iter()
+                        .find(|album| &album.name == album_name)
+                        .map(|album| {
+                            album
+                                .tracks
+                                .iter()
+                                .map(|song| uri(&song.file_path))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    Some(DisplayItem::Song(_, _, file_path)) => vec![uri(file_path)],
+                    None => Vec::new(),
+                }
+            }
+            MenuMode::Albums => {
+                let Some((_, album)) = self
+                    .library
+                    .as_ref()
+                    .zip(self.all_albums_list_state.selected())
+                    .and_then(|(library, idx)| library.all_albums.get(idx))
+                else {
+                    return Vec::new();
+                };
+                match self.panel_focus {
+                    PanelFocus::AlbumTracks => self
+                        .album_tracks_list_state
+                        .selected()
+                        .and_then(|idx| album.tracks.get(idx))
+                        .map(|song| vec![uri(&song.file_path)])
+                        .unwrap_or_default(),
+                    _ => album
+                        .tracks
+                        .iter()
+                        .map(|song| uri(&song.file_path))
+                        .collect(),
+                }
+            }
+            MenuMode::Search => self.selected_search_uris(),
+            // Directories are added recursively by MPD; playlist files can't be added
+            MenuMode::Browse => match self.browse.selected_entry() {
+                Some(entry @ (BrowseEntry::Directory(_) | BrowseEntry::Song(_))) => {
+                    vec![entry.path()]
+                }
+                _ => Vec::new(),
+            },
+            MenuMode::Playlists => match self.panel_focus {
+                PanelFocus::AlbumTracks => self
+                    .playlists
+                    .tracks_list_state
+                    .selected()
+                    .and_then(|idx| self.playlists.contents.get(idx))
+                    .map(|song| vec![uri(&song.file_path)])
+                    .unwrap_or_default(),
+                _ => self
+                    .playlists
+                    .contents
+                    .iter()
+                    .map(|song| uri(&song.file_path))
+                    .collect(),
+            },
+        }
+    }
+
+    /// Ask for a name to save the current queue as a new playlist
+    pub fn open_save_playlist_prompt(&mut self) {
+        self.playlists.prompt = Some(PlaylistPrompt::SaveQueue {
+            input: String::new(),
+        });
+        self.dirty.mark_library();
+    }
+
+    /// Ask for a new name for the selected playlist
+    pub fn open_rename_playlist_prompt(&mut self) {
+        if self.menu_mode != MenuMode::Playlists || self.panel_focus == PanelFocus::AlbumTracks {
+            return;
+        }
+        if let Some(name) = self.playlists.selected_name().map(str::to_string) {
+            self.playlists.prompt = Some(PlaylistPrompt::Rename {
+                input: name.clone(),
+                from: name,
+            });
+            self.dirty.mark_library();
+        }
+    }
+
+    /// Open the playlist picker for the songs selected in the current view
+    pub async fn open_add_to_playlist_prompt(&mut self, client: &Client) {
+        let uris = self.selected_song_uris();
+        if uris.is_empty() {
+            return;
+        }
+        // The picker lists every playlist, so make sure the names are current
+        match fetch_playlist_names(client).await {
+            Ok(names) => self.playlists.set_names(names),
+            Err(e) => {
+                self.report_mpd_error("Error listing playlists", &e);
+                return;
+            }
+        }
+        self.playlists.prompt = Some(PlaylistPrompt::AddTo { uris, selected: 0 });
+        self.dirty.mark_library();
+    }
+
+    /// Handle a key press while a playlist prompt is open
+    pub async fn handle_playlist_prompt_input(
+        &mut self,
+        key: KeyEvent,
+        client: &Client,
+    ) -> color_eyre::Result<()> {
+        let Some(mut prompt) = self.playlists.prompt.take() else {
+            return Ok(());
+        };
+        self.dirty.mark_library();
+
+        if key.code == KeyCode::Esc {
+            return Ok(());
+        }
+
+        match prompt {
+            PlaylistPrompt::ConfirmDelete { name } => {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.delete_playlist(&name, client).await;
+                }
+            }
+            PlaylistPrompt::AddTo { uris, selected } => {
+                // Entry 0 is "New playlist", the stored playlists follow
+                let len = self.playlists.names.len() + 1;
+                let selected = match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => selected.checked_sub(1).unwrap_or(len - 1),
+                    KeyCode::Down | KeyCode::Char('j') => (selected + 1) % len,
+                    KeyCode::Enter if selected == 0 => {
+                        self.playlists.prompt = Some(PlaylistPrompt::AddToNew {
+                            uris,
+                            input: String::new(),
+                        });
+                        return Ok(());
+                    }
+                    KeyCode::Enter => {
+                        if let Some(name) = self.playlists.names.get(selected - 1).cloned() {
+                            self.add_to_playlist(&name, &uris, client).await;
+                        }
+                        return Ok(());
+                    }
+                    _ => selected,
+                };
+                self.playlists.prompt = Some(PlaylistPrompt::AddTo { uris, selected });
+            }
+            _ if key.code == KeyCode::Enter => {
+                self.submit_playlist_prompt(prompt, client).await;
+            }
+            _ => {
+                if let Some(input) = prompt.input_mut() {
+                    match key.code {
+                        KeyCode::Backspace => {
+                            input.pop();
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            input.clear();
+                        }
+                        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            input.push(c);
+                        }
+                        _ => {}
+                    }
+                }
+                self.playlists.prompt = Some(prompt);
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the MPD command for a submitted text prompt
+    async fn submit_playlist_prompt(&mut self, prompt: PlaylistPrompt, client: &Client) {
+        match prompt {
+            PlaylistPrompt::SaveQueue { input } => {
+                let name = input.trim();
+                if name.is_empty() {
+                    return;
+                }
+                match client.command(commands::SaveQueueAsPlaylist(name)).await {
+                    Ok(()) => log::info!("Saved queue as playlist {:?}", name),
+                    Err(e) => self.report_mpd_error("Error saving queue as playlist", &e),
+                }
+            }
+            PlaylistPrompt::Rename { from, input } => {
+                let to = input.trim();
+                if to.is_empty() || to == from {
+                    return;
+                }
+                if let Err(e) = client
+                    .command(commands::RenamePlaylist::new(&from, to))
+                    .await
+                {
+                    self.report_mpd_error("Error renaming playlist", &e);
+                    return;
+                }
+                // Keep the renamed playlist selected
+                if let Some(idx) = self.playlists.names.iter().position(|n| *n == from) {
+                    self.playlists.names[idx] = to.to_string();
+                }
+            }
+            PlaylistPrompt::AddToNew { uris, input } => {
+                let name = input.trim();
+                if !name.is_empty() {
+                    self.add_to_playlist(name, &uris, client).await;
+                }
+            }
+            PlaylistPrompt::ConfirmDelete { .. } | PlaylistPrompt::AddTo { .. } => {}
+        }
+    }
+
+    /// Add songs to a stored playlist (MPD creates it if it doesn't exist)
+    async fn add_to_playlist(&mut self, name: &str, uris: &[String], client: &Client) {
+        for uri in uris {
+            if let Err(e) = client
+                .command(commands::AddToPlaylist::new(name, uri))
+                .await
+            {
+                self.report_mpd_error("Error adding song to playlist", &e);
+                return;
+            }
+        }
+        log::info!("Added {} songs to playlist {:?}", uris.len(), name);
+    }
+
+    /// Delete a stored playlist
+    async fn delete_playlist(&mut self, name: &str, client: &Client) {
+        match client.command(commands::DeletePlaylist(name)).await {
+            Ok(()) => log::info!("Deleted playlist {:?}", name),
+            Err(e) => self.report_mpd_error("Error deleting playlist", &e),
+        }
+    }
+}
@@ -45,7 +45,7 @@ impl App {
                 // Handle scrolling based on current panel focus
                 match self.panel_focus {
                     PanelFocus::Artists => {
-                        if let Some(ref library) = self.library
+                        if let Some(library) = self.artists_library()
                             && !library.artists.is_empty()
                         {
                             let current = self.artist_list_state.selected().unwrap_or(0);
@@ -81,16 +81,14 @@ impl App {
                             self.album_display_list_state.select(None);
 
                             // Lazy load the newly selected artist's albums
-                            if let Some(ref mut library) = self.library
-                                && let Err(e) = library.load_artist(client, new_index).await
-                            {
+                            if let Err(e) = self.load_artist(client, new_index).await {
                                 log::warn!("Failed to load artist: {}", e);
                             }
                         }
                     }
                     PanelFocus::Albums => {
                         if let (Some(library), Some(selected_artist_index)) =
-                            (&self.library, self.artist_list_state.selected())
+                            (self.artists_library(), self.artist_list_state.selected())
                             && let Some(selected_artist) = library.get_artist(selected_artist_index)
                         {
                             // Compute display list to get total count
@@ -224,11 +222,18 @@ impl App {
                     }
                 }
             }
+            MenuMode::Search => self.handle_search_navigation(action),
+            MenuMode::Playlists => self.handle_playlists_navigation(action, client).await,
+            MenuMode::Browse => self.handle_browse_navigation(action),
         }
         // Mark appropriate dirty flags for scrolling
         match self.menu_mode {
             MenuMode::Queue => self.dirty.mark_queue_selection(),
-            MenuMode::Artists | MenuMode::Albums => self.dirty.mark_library(),
+            MenuMode::Artists
+            | MenuMode::Albums
+            | MenuMode::Search
+            | MenuMode::Playlists
+            | MenuMode::Browse => self.dirty.mark_library(),
         }
     }
 
@@ -249,7 +254,7 @@ impl App {
             MenuMode::Artists => {
                 match self.panel_focus {
                     PanelFocus::Artists => {
-                        if let Some(ref library) = self.library
+                        if let Some(library) = self.artists_library()
                             && !library.artists.is_empty()
                         {
                             let new_index = match action {
@@ -263,16 +268,14 @@ impl App {
                             self.album_display_list_state.select(None);
 
                             // Lazy load the newly selected artist's albums
-                            if let Some(ref mut library) = self.library
-                                && let Err(e) = library.load_artist(client, new_index).await
-                            {
+                            if let Err(e) = self.load_artist(client, new_index).await {
                                 log::warn!("Failed to load artist: {}", e);
                             }
                         }
                     }
                     PanelFocus::Albums => {
                         if let (Some(library), Some(selected_artist_index)) =
-                            (&self.library, self.artist_list_state.selected())
+                            (self.artists_library(), self.artist_list_state.selected())
                             && let Some(selected_artist) = library.get_artist(selected_artist_index)
                         {
                             let (display_items, _album_indices) =
@@ -341,11 +344,18 @@ impl App {
                     }
                 }
             }
+            MenuMode::Search => self.handle_search_navigation(action),
+            MenuMode::Playlists => self.handle_playlists_navigation(action, client).await,
+            MenuMode::Browse => self.handle_browse_navigation(action),
         }
         // Mark appropriate dirty flags for go to edge
         match self.menu_mode {
             MenuMode::Queue => self.dirty.mark_queue_selection(),
-            MenuMode::Artists | MenuMode::Albums => self.dirty.mark_library(),
+            MenuMode::Artists
+            | MenuMode::Albums
+            | MenuMode::Search
+            | MenuMode::Playlists
+            | MenuMode::Browse => self.dirty.mark_library(),
         }
     }
 }
@@ -0,0 +1,166 @@
+use crate::App;
+use crate::app::navigation::helpers::step_selection;
+use crate::app::song::search::{SearchQuery, search_library};
+use crate::app::{MessageType, PanelFocus, StatusMessage, mpd_handler::MPDAction};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mpd_client::{Client, commands};
+
+impl App {
+    /// Handle a key press while the search input is focused
+    pub async fn handle_search_input(
+        &mut self,
+        key: KeyEvent,
+        client: &Client,
+    ) -> color_eyre::Result<()> {
+        match key.code {
+            KeyCode::Enter => {
+                self.run_search(client).await;
+            }
+            KeyCode::Esc => {
+                // Leave the input and go back to browsing results
+                self.search.input_active = false;
+            }
+            KeyCode::Backspace => {
+                self.search.query.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.query.clear();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.query.push(c);
+            }
+            _ => return Ok(()),
+        }
+        self.dirty.mark_library();
+        Ok(())
+    }
+
+    /// Run the typed query against the MPD database and show the results
+    async fn run_search(&mut self, client: &Client) {
+        let query = SearchQuery::parse(&self.search.query);
+        if query.is_empty() {
+            return;
+        }
+
+        match search_library(client, &query).await {
+            Ok(results) => {
+                self.search.song_count = results.iter().map(|(_, a)| a.tracks.len()).sum();
+                self.search.results = results;
+                let first = (!self.search.results.is_empty()).then_some(0);
+                self.search.album_list_state.select(first);
+                self.search.tracks_list_state.select(first);
+                self.search.input_active = false;
+                self.panel_focus = PanelFocus::AlbumList;
+            }
+            Err(e) => {
+                log::error!("{}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Search failed".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+    }
+
+    /// Move the selection in the focused Search panel
+    pub fn handle_search_navigation(&mut self, action: MPDAction) {
+        let search = &mut self.search;
+        let (len, state) = match self.panel_focus {
+            PanelFocus::AlbumTracks => {
+                let len = search
+                    .album_list_state
+                    .selected()
+                    .and_then(|idx| search.results.get(idx))
+                    .map_or(0, |(_, album)| album.tracks.len());
+                (len, &mut search.tracks_list_state)
+            }
+            _ => (search.results.len(), &mut search.album_list_state),
+        };
+        let Some(new_index) = step_selection(&action, state.selected(), len) else {
+            return;
+        };
+        state.select(Some(new_index));
+
+        // Reset track selection when moving between albums
+        if self.panel_focus != PanelFocus::AlbumTracks {
+            search.tracks_list_state.select(Some(0));
+        }
+    }
+
+    /// URIs of the selected search result: the selected song in the tracks panel,
+    /// or the whole album in the album list
+    pub fn selected_search_uris(&self) -> Vec<String> {
+        let Some((_, album)) = self
+            .search
+            .album_list_state
+            .selected()
+            .and_then(|idx| self.search.results.get(idx))
+        else {
+            return Vec::new();
+        };
+
+        match self.panel_focus {
+            PanelFocus::AlbumTracks => self
+                .search
+                .tracks_list_state
+                .selected()
+                .and_then(|idx| album.tracks.get(idx))
+                .into_iter()
+                .map(|song| song.file_path.to_string_lossy().into_owned())
+                .collect(),
+            _ => album
+                .tracks
+                .iter()
+                .map(|song| song.file_path.to_string_lossy().into_owned())
+                .collect(),
+        }
+    }
+
+    /// Handle adding the selected search result to the queue.
+    /// Adds the selected song in the tracks panel, or the whole album in the album list.
+    pub async fn handle_add_search_result(&mut self, client: &Client) -> color_eyre::Result<()> {
+        let uris = self.selected_search_uris();
+        if uris.is_empty() {
+            return Ok(());
+        }
+
+        let queue_was_empty = self.queue.is_empty();
+        let mut add_error = None;
+        for uri in &uris {
+            if let Err(e) = client.command(commands::Add::uri(uri)).await {
+                add_error = Some(e);
+            }
+        }
+        if let Some(e) = add_error {
+            self.report_mpd_error("Error adding search result to queue", &e);
+        }
+        // Start playback if queue was empty
+        if queue_was_empty && let Err(e) = client.command(commands::Play::current()).await {
+            self.report_mpd_error("Error starting playback", &e);
+        }
+        Ok(())
+    }
+
+    /// Handle playing the selected search result (adds it to the queue and plays it)
+    pub async fn handle_play_search_result(&mut self, client: &Client) -> color_eyre::Result<()> {
+        if let Some(album_idx) = self.search.album_list_state.selected()
+            && let Some((_, album)) = self.search.results.get(album_idx)
+            && let Some(track_idx) = self.search.tracks_list_state.selected()
+            && let Some(song) = album.tracks.get(track_idx)
+        {
+            let uri = song.file_path.to_string_lossy().into_owned();
+            match client.command(commands::Add::uri(&uri)).await {
+                Ok(song_id) => {
+                    if let Err(e) = client.command(commands::Play::song(song_id)).await {
+                        self.report_mpd_error("Error playing search result", &e);
+                    }
+                }
+                Err(e) => {
+                    self.report_mpd_error("Error adding search result to queue", &e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use crate::App;
+use crate::app::song::{
+    LazyLibrary, TagViewState,
+    tag_view::{ArtistsPanels, TagLevel},
+};
+use crate::app::ui::ALBUM_DISPLAY_CACHE;
+use crate::app::{ListState, MenuMode, MessageType, PanelFocus, StatusMessage};
+use mpd_client::Client;
+
+impl App {
+    /// Library shown in the Artists view: the open tag view's, or the default one
+    pub fn artists_library(&self) -> Option<&LazyLibrary> {
+        match &self.tag_view {
+            Some(open) => Some(&open.library),
+            None => self.library.as_ref(),
+        }
+    }
+
+    pub fn artists_library_mut(&mut self) -> Option<&mut LazyLibrary> {
+        match &mut self.tag_view {
+            Some(open) => Some(&mut open.library),
+            None => self.library.as_mut(),
+        }
+    }
+
+    /// Lazy load the songs of an entry of the Artists view
+    pub async fn load_artist(&mut self, client: &Client, idx: usize) -> color_eyre::Result<()> {
+        match self.artists_library_mut() {
+            Some(library) => library.load_artist(client, idx).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Show the next `[browse]` view in the Artists view
+    pub async fn handle_next_tag_view(&mut self, client: &Client) {
+        let views = &self.config.browse.views;
+        if views.is_empty() {
+            return;
+        }
+        let current = match &self.tag_view {
+            Some(open) => Some(open.view),
+            None => views.iter().position(|view| view.is_default()),
+        };
+        let next = current.map_or(0, |idx| (idx + 1) % views.len());
+        self.open_tag_view(client, next).await;
+    }
+
+    /// Show `[browse] views[index]` in the Artists view, starting at its first level.
+    /// AlbumArtist → Album views show the default library with its selections.
+    pub async fn open_tag_view(&mut self, client: &Client, index: usize) {
+        let Some(view) = self.config.browse.views.get(index) else {
+            return;
+        };
+        if view.is_default() {
+            self.close_tag_view();
+            return;
+        }
+        let Some(level) = TagLevel::of_view(&view.mpd_tags(), Vec::new()) else {
+            return;
+        };
+        let library = match LazyLibrary::init_level(client, level).await {
+            Ok(library) => library,
+            Err(e) => {
+                log::error!("Failed to open the '{}' view: {}", view.name, e);
+                self.set_status_message(StatusMessage {
+                    text: e.to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+                return;
+            }
+        };
+
+        let default_panels = match self.tag_view.take() {
+            Some(open) => open.default_panels,
+            None => self.take_artists_panels(),
+        };
+        self.tag_view = Some(TagViewState {
+            view: index,
+            library,
+            parents: Vec::new(),
+            default_panels,
+        });
+        self.artist_list_state = ListState::default();
+        self.album_list_state = ListState::default();
+        self.album_display_list_state = ListState::default();
+        self.expanded_albums.clear();
+        self.select_first_entry(client).await;
+        self.artists_source_changed();
+        self.focus_artists_panel();
+    }
+
+    /// Go back to the default library and the selections it had
+    pub fn close_tag_view(&mut self) {
+        if let Some(open) = self.tag_view.take() {
+            self.put_artists_panels(open.default_panels);
+            self.artists_source_changed();
+            self.focus_artists_panel();
+        }
+    }
+
+    /// Open the selected entry of a tag view level whose entries hold the next level.
+    /// Returns false when the entries group songs instead, so the Albums panel is next.
+    pub async fn open_tag_level(&mut self, client: &Client) -> bool {
+        let Some(open) = self.tag_view.as_ref() else {
+            return false;
+        };
+        if open.library.level.group.is_some() {
+            return false;
+        }
+        let Some(row) = self.artist_list_state.selected() else {
+            return true;
+        };
+        let Some(level) = open.library.artists.get(row).and_then(|entry| {
+            let tags = self.config.browse.views.get(open.view)?.mpd_tags();
+            open.library.level.open(&tags, &entry.name)
+        }) else {
+            return true;
+        };
+
+        match LazyLibrary::init_level(client, level).await {
+            Ok(library) => {
+                let offset = self.artist_list_state.offset();
+                if let Some(open) = self.tag_view.as_mut() {
+                    let parent = std::mem::replace(&mut open.library, library);
+                    open.parents.push((parent, row, offset));
+                }
+                self.artist_list_state = ListState::default();
+                self.album_list_state.select(None);
+                self.album_display_list_state.select(None);
+                self.select_first_entry(client).await;
+                self.artists_source_changed();
+            }
+            Err(e) => {
+                self.set_status_message(StatusMessage {
+                    text: e.to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+        true
+    }
+
+    /// Go back to the level above in a tag view, with the entry that was opened
+    /// selected. Returns false at the first level.
+    pub fn close_tag_level(&mut self) -> bool {
+        let Some(open) = self.tag_view.as_mut() else {
+            return false;
+        };
+        let Some((library, row, offset)) = open.parents.pop() else {
+            return false;
+        };
+        open.library = library;
+        self.artist_list_state = ListState::default()
+            .with_selected(Some(row))
+            .with_offset(offset);
+        self.album_list_state.select(None);
+        self.album_display_list_state.select(None);
+        self.artists_source_changed();
+        true
+    }
+
+    /// Swap the default library's panel selections back in while a tag view is open,
+    /// or out again. Lets library refreshes and the session work on the default library.
+    pub fn swap_default_panels(&mut self) {
+        if let Some(open) = self.tag_view.as_mut() {
+            let panels = &mut open.default_panels;
+            std::mem::swap(&mut self.artist_list_state, &mut panels.artist_list_state);
+            std::mem::swap(&mut self.album_list_state, &mut panels.album_list_state);
+            std::mem::swap(
+                &mut self.album_display_list_state,
+                &mut panels.album_display_list_state,
+            );
+            std::mem::swap(&mut self.expanded_albums, &mut panels.expanded_albums);
+        }
+    }
+
+    /// List the open tag view's levels again after the database changed, keeping the
+    /// opened and selected entries by name
+    pub async fn reload_tag_view(&mut self, client: &Client) {
+        let Some(open) = self.tag_view.as_mut() else {
+            return;
+        };
+        let mut result = Ok(());
+        for (library, row, _offset) in &mut open.parents {
+            match reload_level(client, library, Some(*row)).await {
+                Ok(new_row) => *row = new_row.unwrap_or(0),
+                Err(e) => result = Err(e),
+            }
+        }
+        let selected = self.artist_list_state.selected();
+        match reload_level(client, &mut open.library, selected).await {
+            Ok(row) => {
+                if row != selected {
+                    self.album_list_state.select(None);
+                    self.album_display_list_state.select(None);
+                }
+                self.artist_list_state.select(row);
+            }
+            Err(e) => result = Err(e),
+        }
+        if let Err(e) = result {
+            log::warn!("Failed to reload the tag view: {}", e);
+        }
+        if let Some(idx) = self.artist_list_state.selected()
+            && let Err(e) = self.load_artist(client, idx).await
+        {
+            log::warn!("Failed to load entry after reload: {}", e);
+        }
+        self.artists_source_changed();
+    }
+
+    /// Select and load the first entry of the Artists view
+    async fn select_first_entry(&mut self, client: &Client) {
+        let has_entries = self
+            .artists_library()
+            .is_some_and(|library| !library.artists.is_empty());
+        self.artist_list_state.select(has_entries.then_some(0));
+        if has_entries && let Err(e) = self.load_artist(client, 0).await {
+            log::warn!("Failed to load first entry: {}", e);
+        }
+    }
+
+    /// The Artists view lists other entries: marked rows and the album display cache
+    /// no longer match
+    fn artists_source_changed(&mut self) {
+        ALBUM_DISPLAY_CACHE.with(|cache| cache.borrow_mut().invalidate());
+        self.clear_library_marks();
+        self.dirty.mark_library();
+    }
+
+    /// Focus the Artists panel, or have it focused when the Artists view is shown next
+    fn focus_artists_panel(&mut self) {
+        if self.menu_mode == MenuMode::Artists {
+            self.panel_focus = PanelFocus::Artists;
+            self.dirty.mark_panel_focus();
+        } else {
+            self.artists_panel_focus = PanelFocus::Artists;
+        }
+    }
+
+    fn take_artists_panels(&mut self) -> ArtistsPanels {
+        ArtistsPanels {
+            artist_list_state: std::mem::take(&mut self.artist_list_state),
+            album_list_state: std::mem::take(&mut self.album_list_state),
+            album_display_list_state: std::mem::take(&mut self.album_display_list_state),
+            expanded_albums: std::mem::take(&mut self.expanded_albums),
+        }
+    }
+
+    fn put_artists_panels(&mut self, panels: ArtistsPanels) {
+        self.artist_list_state = panels.artist_list_state;
+        self.album_list_state = panels.album_list_state;
+        self.album_display_list_state = panels.album_display_list_state;
+        self.expanded_albums = panels.expanded_albums;
+    }
+}
+
+/// List a level again, returning the row of the entry that was at `row`, or the first
+/// one if it is gone
+async fn reload_level(
+    client: &Client,
+    library: &mut LazyLibrary,
+    row: Option<usize>,
+) -> color_eyre::Result<Option<usize>> {
+    let name = row
+        .and_then(|row| library.artists.get(row))
+        .map(|entry| entry.name.clone());
+    *library = LazyLibrary::init_level(client, library.level.clone()).await?;
+    Ok(name
+        .and_then(|name| library.artists.iter().position(|entry| entry.name == name))
+        .or((!library.artists.is_empty()).then_some(0)))
+}
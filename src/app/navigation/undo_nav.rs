@@ -0,0 +1,100 @@
+use crate::App;
+use crate::app::mpd::queue_history::QueueSnapshot;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::ui::MenuMode;
+use crate::app::{MessageType, StatusMessage};
+use mpd_client::{Client, client::CommandError, responses::PlayState};
+
+impl App {
+    /// What `action` does to the queue, or None if it leaves the queue alone
+    fn queue_change(&self, action: &MPDAction) -> Option<&'static str> {
+        let in_queue = self.menu_mode == MenuMode::Queue;
+        match action {
+            MPDAction::ClearQueue => Some("clear queue"),
+            // In the Playlists view these edit the stored playlists instead
+            MPDAction::RemoveFromQueue if in_queue => Some("remove from queue"),
+            MPDAction::MoveUpInQueue | MPDAction::MoveDownInQueue if in_queue => {
+                Some("move in queue")
+            }
+            MPDAction::AddSongToQueue if !in_queue => Some("add to queue"),
+            MPDAction::AddNext => Some("play next"),
+            MPDAction::LoadPlaylist => Some("load playlist"),
+            MPDAction::PlaySelected => match self.menu_mode {
+                MenuMode::Playlists => Some("load playlist"),
+                MenuMode::Albums | MenuMode::Search | MenuMode::Browse => Some("add to queue"),
+                MenuMode::Queue | MenuMode::Artists => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn queue_snapshot(&self, description: &'static str) -> QueueSnapshot {
+        let status = self.mpd_status.as_ref();
+        QueueSnapshot {
+            description,
+            uris: self
+                .queue
+                .iter()
+                .map(|song| song.file_path.to_string_lossy().into_owned())
+                .collect(),
+            priorities: self.queue.iter().map(|song| song.priority).collect(),
+            current: status
+                .and_then(|status| status.current_song)
+                .map(|(position, _)| position.0),
+            elapsed: status.and_then(|status| status.elapsed),
+            play_state: status.map_or(PlayState::Stopped, |status| status.state),
+        }
+    }
+
+    /// Snapshot the queue before an action that changes it, so it can be undone
+    pub fn record_queue_change(&mut self, action: &MPDAction) {
+        if let Some(description) = self.queue_change(action) {
+            let snapshot = self.queue_snapshot(description);
+            self.queue_history.record(snapshot);
+        }
+    }
+
+    /// Restore the queue from before the last change, or redo the last undone change
+    pub async fn handle_undo(&mut self, redo: bool, client: &Client) {
+        let live = self.queue_snapshot("");
+        let snapshot = if redo {
+            self.queue_history.redo(live.clone())
+        } else {
+            self.queue_history.undo(live.clone())
+        };
+        let Some(mut snapshot) = snapshot else {
+            let text = if redo {
+                "Nothing to redo"
+            } else {
+                "Nothing to undo"
+            };
+            self.show_info(text.to_string());
+            return;
+        };
+
+        snapshot.resume_from(&live);
+        if let Err(e) = restore_queue(&snapshot, client).await {
+            self.report_mpd_error("Error restoring the queue", &e);
+            return;
+        }
+        let verb = if redo { "Redid" } else { "Undid" };
+        self.show_info(format!("{}: {}", verb, snapshot.description));
+        self.dirty.mark_queue();
+    }
+
+    fn show_info(&mut self, text: String) {
+        self.set_status_message(StatusMessage {
+            text,
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Info,
+        });
+    }
+}
+
+/// Replace the queue with the snapshot's songs in one command list
+async fn restore_queue(snapshot: &QueueSnapshot, client: &Client) -> Result<(), CommandError> {
+    client
+        .raw_command_list(snapshot.restore_commands())
+        .await
+        .map(|_| ())
+}
@@ -0,0 +1,135 @@
+//! Desktop notifications on track change
+//!
+//! Posts the new song to `org.freedesktop.Notifications`, replacing the
+//! previous notification instead of stacking a new one for every track.
+
+use crate::app::SongInfo;
+use crate::app::config::notifications::NotificationsConfig;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use zbus::zvariant::Value;
+use zbus::{Connection, Proxy};
+
+/// Longest side of the cover sent in the image hint
+const COVER_SIZE: u32 = 128;
+
+/// Track change notifier on the session bus
+pub struct Notifier {
+    proxy: Proxy<'static>,
+    config: NotificationsConfig,
+    /// Id of the last notification, replaced by the next one (0 means none yet)
+    last_id: Arc<AtomicU32>,
+}
+
+impl Notifier {
+    pub async fn connect(config: &NotificationsConfig) -> zbus::Result<Self> {
+        let connection = Connection::session().await?;
+        let proxy = Proxy::new(
+            &connection,
+            "org.freedesktop.Notifications",
+            "/org/freedesktop/Notifications",
+            "org.freedesktop.Notifications",
+        )
+        .await?;
+        Ok(Self {
+            proxy,
+            config: config.clone(),
+            last_id: Arc::new(AtomicU32::new(0)),
+        })
+    }
+
+    /// Post a notification for `song` without blocking the main loop
+    pub fn notify(&self, song: &SongInfo, cover: Option<&[u8]>, terminal_focused: bool) {
+        if self.config.suppress_when_focused && terminal_focused {
+            log::debug!(
+                "Terminal focused, skipping notification for {:?}",
+                song.file_path
+            );
+            return;
+        }
+
+        let proxy = self.proxy.clone();
+        let last_id = self.last_id.clone();
+        let timeout = self.config.timeout_ms;
+        let summary = song.title.clone();
+        let body = format!("{}\n{}", song.artist, song.album);
+        let cover = cover.filter(|_| self.config.show_cover).map(<[u8]>::to_vec);
+
+        tokio::spawn(async move {
+            let mut hints: HashMap<&str, Value<'_>> = HashMap::new();
+            hints.insert("desktop-entry", Value::from("zarumet"));
+            if let Some(image) = cover.as_deref().and_then(image_hint) {
+                hints.insert("image-data", image);
+            }
+
+            let replaces_id = last_id.load(Ordering::Relaxed);
+            let result: zbus::Result<u32> = proxy
+                .call(
+                    "Notify",
+                    &(
+                        "Zarumet",
+                        replaces_id,
+                        "",
+                        summary.as_str(),
+                        body.as_str(),
+                        Vec::<&str>::new(),
+                        hints,
+                        timeout,
+                    ),
+                )
+                .await;
+            match result {
+                Ok(id) => last_id.store(id, Ordering::Relaxed),
+                Err(e) => log::warn!("Failed to send notification: {}", e),
+            }
+        });
+    }
+}
+
+/// Decode the cover into the `image-data` hint: (width, height, rowstride,
+/// has_alpha, bits_per_sample, channels, data) with RGBA pixels
+fn image_hint(data: &[u8]) -> Option<Value<'static>> {
+    let image = image::load_from_memory(data)
+        .inspect_err(|e| log::debug!("Failed to decode cover for notification: {}", e))
+        .ok()?
+        .thumbnail(COVER_SIZE, COVER_SIZE)
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    let width = i32::try_from(width).ok()?;
+    let height = i32::try_from(height).ok()?;
+    Some(Value::from((
+        width,
+        height,
+        width * 4,
+        true,
+        8i32,
+        4i32,
+        image.into_raw(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_image_hint_scales_cover() {
+        let mut png = Vec::new();
+        image::RgbImage::new(512, 256)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let Some(Value::Structure(hint)) = image_hint(&png) else {
+            panic!("expected an image-data structure");
+        };
+        let fields = hint.fields();
+        assert_eq!(fields[0], Value::from(128i32));
+        assert_eq!(fields[1], Value::from(64i32));
+        assert_eq!(fields[2], Value::from(512i32));
+        assert_eq!(fields.len(), 7);
+
+        assert!(image_hint(b"not an image").is_none());
+    }
+}
@@ -0,0 +1,182 @@
+use super::SubmitError;
+use super::tracker::{Listen, Track};
+use crate::app::config::scrobbling::LastFmConfig;
+use serde_json::Value;
+
+/// Most scrobbles Last.fm accepts in one request
+pub const BATCH_SIZE: usize = 50;
+
+/// Last.fm error codes that retrying cannot fix (invalid parameters / resource)
+const PERMANENT_ERRORS: &[u64] = &[6, 7];
+
+/// Last.fm API client (audioscrobbler 2.0)
+pub struct LastFm {
+    client: reqwest::Client,
+    config: LastFmConfig,
+    session_key: Option<String>,
+}
+
+impl LastFm {
+    pub fn new(client: reqwest::Client, config: LastFmConfig) -> Self {
+        let session_key = config.session_key.clone();
+        Self {
+            client,
+            config,
+            session_key,
+        }
+    }
+
+    /// Announce the track with `track.updateNowPlaying`
+    pub async fn now_playing(&mut self, track: &Track) -> Result<(), SubmitError> {
+        let mut params = vec![("method".to_string(), "track.updateNowPlaying".to_string())];
+        params.extend(track_params(track, None));
+        params.push(("sk".to_string(), self.session_key().await?));
+        self.signed_call(params).await.map(|_| ())
+    }
+
+    /// Submit up to [`BATCH_SIZE`] listens with `track.scrobble`
+    pub async fn scrobble(&mut self, listens: &[Listen]) -> Result<(), SubmitError> {
+        let mut params = vec![("method".to_string(), "track.scrobble".to_string())];
+        for (i, listen) in listens.iter().enumerate() {
+            params.extend(track_params(&listen.track, Some(i)));
+            params.push((format!("timestamp[{}]", i), listen.listened_at.to_string()));
+        }
+        params.push(("sk".to_string(), self.session_key().await?));
+        self.signed_call(params).await.map(|_| ())
+    }
+
+    /// Session key from the config, or requested once with `auth.getMobileSession`
+    async fn session_key(&mut self) -> Result<String, SubmitError> {
+        if let Some(key) = &self.session_key {
+            return Ok(key.clone());
+        }
+        let (Some(username), Some(password)) = (&self.config.username, &self.config.password)
+        else {
+            return Err(SubmitError::Retry(
+                "Last.fm needs a session_key, or a username and password".to_string(),
+            ));
+        };
+        let params = vec![
+            ("method".to_string(), "auth.getMobileSession".to_string()),
+            ("username".to_string(), username.clone()),
+            ("password".to_string(), password.clone()),
+        ];
+        let response = self.signed_call(params).await?;
+        let key = response["session"]["key"]
+            .as_str()
+            .ok_or_else(|| SubmitError::Retry("Last.fm returned no session key".to_string()))?
+            .to_string();
+        log::info!("Obtained Last.fm session key");
+        self.session_key = Some(key.clone());
+        Ok(key)
+    }
+
+    async fn signed_call(&self, mut params: Vec<(String, String)>) -> Result<Value, SubmitError> {
+        params.push(("api_key".to_string(), self.config.api_key.clone()));
+        let signature = api_signature(&params, &self.config.api_secret);
+        params.push(("api_sig".to_string(), signature));
+        params.push(("format".to_string(), "json".to_string()));
+
+        let response = self
+            .client
+            .post(&self.config.endpoint)
+            .form(&params)
+            .send()
+            .await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+
+        // Errors come back as {"error": code, "message": "..."}, usually with a 4xx status
+        if let Some(code) = body["error"].as_u64() {
+            let message = format!(
+                "Last.fm error {}: {}",
+                code,
+                body["message"].as_str().unwrap_or("unknown error")
+            );
+            return Err(if PERMANENT_ERRORS.contains(&code) {
+                SubmitError::Rejected(message)
+            } else {
+                SubmitError::Retry(message)
+            });
+        }
+        if !status.is_success() {
+            return Err(SubmitError::from_status(status, "Last.fm"));
+        }
+        Ok(body)
+    }
+}
+
+/// Request parameters for a track, with `[i]` suffixes for batched scrobbles
+fn track_params(track: &Track, index: Option<usize>) -> Vec<(String, String)> {
+    let key = |name: &str| match index {
+        Some(i) => format!("{}[{}]", name, i),
+        None => name.to_string(),
+    };
+    let mut params = vec![
+        (key("artist"), track.artist.clone()),
+        (key("track"), track.title.clone()),
+    ];
+    if let Some(album) = &track.album {
+        params.push((key("album"), album.clone()));
+    }
+    if let Some(duration) = track.duration_secs {
+        params.push((key("duration"), duration.to_string()));
+    }
+    if let Some(number) = track.track_number {
+        params.push((key("trackNumber"), number.to_string()));
+    }
+    params
+}
+
+/// `api_sig`: md5 of the parameters sorted by name and concatenated, followed by the secret
+pub fn api_signature(params: &[(String, String)], secret: &str) -> String {
+    let mut sorted: Vec<&(String, String)> = params.iter().collect();
+    sorted.sort_by_key(|&(name, _)| name);
+    let mut input = String::new();
+    for (name, value) in sorted {
+        input.push_str(name);
+        input.push_str(value);
+    }
+    input.push_str(secret);
+    format!("{:x}", md5::compute(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_signature() {
+        let params = vec![
+            ("method".to_string(), "auth.getMobileSession".to_string()),
+            ("username".to_string(), "user".to_string()),
+            ("api_key".to_string(), "key".to_string()),
+            ("password".to_string(), "pass".to_string()),
+        ];
+        // md5("api_keykeymethodauth.getMobileSessionpasswordpassusernameusersecret")
+        assert_eq!(
+            api_signature(&params, "secret"),
+            "9c54f6cf8fc68a3826368902af94331e"
+        );
+    }
+
+    #[test]
+    fn test_batched_track_params() {
+        let track = Track {
+            artist: "Artist".to_string(),
+            title: "Title".to_string(),
+            album: None,
+            duration_secs: Some(200),
+            track_number: None,
+        };
+        let params = track_params(&track, Some(3));
+        assert_eq!(
+            params,
+            vec![
+                ("artist[3]".to_string(), "Artist".to_string()),
+                ("track[3]".to_string(), "Title".to_string()),
+                ("duration[3]".to_string(), "200".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use super::SubmitError;
+use super::tracker::{Listen, Track};
+use crate::app::config::scrobbling::ListenBrainzConfig;
+use serde_json::{Value, json};
+
+/// Listens per `import` request (the API allows up to 1000)
+pub const BATCH_SIZE: usize = 100;
+
+/// ListenBrainz API client
+pub struct ListenBrainz {
+    client: reqwest::Client,
+    config: ListenBrainzConfig,
+}
+
+impl ListenBrainz {
+    pub fn new(client: reqwest::Client, config: ListenBrainzConfig) -> Self {
+        Self { client, config }
+    }
+
+    pub async fn now_playing(&self, track: &Track) -> Result<(), SubmitError> {
+        self.submit("playing_now", vec![listen_payload(track, None)])
+            .await
+    }
+
+    /// Submit up to [`BATCH_SIZE`] listens
+    pub async fn scrobble(&self, listens: &[Listen]) -> Result<(), SubmitError> {
+        let listen_type = if listens.len() == 1 {
+            "single"
+        } else {
+            "import"
+        };
+        let payload = listens
+            .iter()
+            .map(|listen| listen_payload(&listen.track, Some(listen.listened_at)))
+            .collect();
+        self.submit(listen_type, payload).await
+    }
+
+    async fn submit(&self, listen_type: &str, payload: Vec<Value>) -> Result<(), SubmitError> {
+        let url = format!(
+            "{}/1/submit-listens",
+            self.config.endpoint.trim_end_matches('/')
+        );
+        let response = self
+            .client
+            .post(url)
+            .header("Authorization", format!("Token {}", self.config.token))
+            .json(&json!({ "listen_type": listen_type, "payload": payload }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        // An invalid token is a config problem: keep the listens until it is fixed
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(SubmitError::Retry(
+                "ListenBrainz rejected the token".to_string(),
+            ));
+        }
+        Err(SubmitError::from_status(status, "ListenBrainz"))
+    }
+}
+
+/// One entry of the `payload` array; "playing_now" listens have no timestamp
+fn listen_payload(track: &Track, listened_at: Option<u64>) -> Value {
+    let mut additional_info = json!({
+        "media_player": "Zarumet",
+        "submission_client": "Zarumet",
+        "submission_client_version": env!("CARGO_PKG_VERSION"),
+    });
+    if let Some(duration) = track.duration_secs {
+        additional_info["duration_ms"] = json!(duration * 1000);
+    }
+    if let Some(number) = track.track_number {
+        additional_info["tracknumber"] = json!(number);
+    }
+
+    let mut metadata = json!({
+        "artist_name": track.artist,
+        "track_name": track.title,
+        "additional_info": additional_info,
+    });
+    if let Some(album) = &track.album {
+        metadata["release_name"] = json!(album);
+    }
+
+    let mut listen = json!({ "track_metadata": metadata });
+    if let Some(listened_at) = listened_at {
+        listen["listened_at"] = json!(listened_at);
+    }
+    listen
+}
@@ -0,0 +1,435 @@
+//! Last.fm / ListenBrainz scrobbling
+//!
+//! [`ScrobbleTracker`] turns the playback state into "now playing" and scrobble
+//! events on the main loop; a background task submits them. Scrobbles go through
+//! an on-disk queue first so listens made offline are submitted later.
+
+pub mod lastfm;
+pub mod listenbrainz;
+pub mod queue;
+pub mod tracker;
+
+pub use tracker::{Listen, ScrobbleEvent, ScrobbleTracker};
+
+use crate::app::SongInfo;
+use crate::app::config::scrobbling::ScrobblingConfig;
+use crate::app::constructor::get_state_dir;
+use lastfm::LastFm;
+use listenbrainz::ListenBrainz;
+use mpd_client::responses::Status;
+use queue::ScrobbleQueue;
+use std::fmt;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+/// How often queued scrobbles are retried while a service is unreachable
+const RETRY_INTERVAL: Duration = Duration::from_secs(300);
+/// Timeout for a single API request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Why a submission failed
+#[derive(Debug)]
+pub enum SubmitError {
+    /// Network problem, server error or bad credentials: keep the listens and retry later
+    Retry(String),
+    /// The service refused the listens themselves: drop them
+    Rejected(String),
+}
+
+impl SubmitError {
+    fn from_status(status: reqwest::StatusCode, service: &str) -> Self {
+        let message = format!("{} returned {}", service, status);
+        // Auth failures are about the token or session, not the listens
+        if status.is_server_error()
+            || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::UNAUTHORIZED
+            || status == reqwest::StatusCode::FORBIDDEN
+        {
+            SubmitError::Retry(message)
+        } else {
+            SubmitError::Rejected(message)
+        }
+    }
+}
+
+impl From<reqwest::Error> for SubmitError {
+    fn from(error: reqwest::Error) -> Self {
+        SubmitError::Retry(error.to_string())
+    }
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Retry(message) | SubmitError::Rejected(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+/// Handle kept by the main loop: tracks playback and hands events to the submitter task
+pub struct Scrobbler {
+    tracker: ScrobbleTracker,
+    events: mpsc::UnboundedSender<ScrobbleEvent>,
+}
+
+impl Scrobbler {
+    /// Start the submitter task; None if no service is configured
+    pub fn spawn(config: &ScrobblingConfig) -> Option<Self> {
+        let queue_path = get_state_dir().map(|dir| dir.join("scrobble_queue.json"));
+        let submitter = Submitter::new(config, queue_path)?;
+        let (events, rx) = mpsc::unbounded_channel();
+        tokio::spawn(submitter.run(rx));
+        Some(Self {
+            tracker: ScrobbleTracker::default(),
+            events,
+        })
+    }
+
+    /// Feed the current song and MPD status to the scrobble rules
+    pub fn update(&mut self, current_song: &Option<SongInfo>, status: &Option<Status>) {
+        let wall_clock = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let events = self.tracker.update(
+            current_song.as_ref(),
+            status.as_ref().map(|s| s.state),
+            status.as_ref().and_then(|s| s.elapsed),
+            Instant::now(),
+            wall_clock,
+        );
+        for event in events {
+            let _ = self.events.send(event);
+        }
+    }
+}
+
+/// Background task state: service clients and the pending queue
+struct Submitter {
+    lastfm: Option<LastFm>,
+    listenbrainz: Option<ListenBrainz>,
+    queue: ScrobbleQueue,
+}
+
+impl Submitter {
+    fn new(config: &ScrobblingConfig, queue_path: Option<std::path::PathBuf>) -> Option<Self> {
+        if config.lastfm.is_none() && config.listenbrainz.is_none() {
+            log::warn!("Scrobbling is enabled but neither Last.fm nor ListenBrainz is configured");
+            return None;
+        }
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("zarumet/", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .inspect_err(|e| log::error!("Failed to create HTTP client for scrobbling: {}", e))
+            .ok()?;
+
+        let queue = match queue_path {
+            Some(path) => ScrobbleQueue::load(path),
+            None => ScrobbleQueue::default(),
+        };
+        Some(Self {
+            lastfm: config
+                .lastfm
+                .clone()
+                .map(|c| LastFm::new(client.clone(), c)),
+            listenbrainz: config
+                .listenbrainz
+                .clone()
+                .map(|c| ListenBrainz::new(client, c)),
+            queue,
+        })
+    }
+
+    async fn run(mut self, mut events: mpsc::UnboundedReceiver<ScrobbleEvent>) {
+        let mut retry = tokio::time::interval(RETRY_INTERVAL);
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(event) => self.handle(event).await,
+                    None => break,
+                },
+                // The first tick fires right away and submits what was left from last time
+                _ = retry.tick() => self.flush().await,
+            }
+        }
+    }
+
+    async fn handle(&mut self, event: ScrobbleEvent) {
+        match event {
+            ScrobbleEvent::NowPlaying(track) => {
+                // "Now playing" is not worth retrying, it is stale by then
+                if let Some(lastfm) = self.lastfm.as_mut()
+                    && let Err(e) = lastfm.now_playing(&track).await
+                {
+                    log::debug!("Last.fm now playing failed: {}", e);
+                }
+                if let Some(listenbrainz) = self.listenbrainz.as_ref()
+                    && let Err(e) = listenbrainz.now_playing(&track).await
+                {
+                    log::debug!("ListenBrainz now playing failed: {}", e);
+                }
+            }
+            ScrobbleEvent::Scrobble(listen) => {
+                log::info!(
+                    "Scrobbling {} - {}",
+                    listen.track.artist,
+                    listen.track.title
+                );
+                self.queue
+                    .push(&listen, self.lastfm.is_some(), self.listenbrainz.is_some());
+                self.queue.save();
+                self.flush().await;
+            }
+        }
+    }
+
+    /// Submit queued listens in batches until the queue is empty or a service is unreachable
+    async fn flush(&mut self) {
+        if let Some(lastfm) = self.lastfm.as_mut() {
+            // Listens left to send one at a time after their batch was rejected
+            let mut singles = 0;
+            while !self.queue.lastfm.is_empty() {
+                let count = if singles > 0 {
+                    1
+                } else {
+                    self.queue.lastfm.len().min(lastfm::BATCH_SIZE)
+                };
+                let result = lastfm.scrobble(&self.queue.lastfm[..count]).await;
+                // One bad listen fails the whole request, so find out which ones it was
+                if count > 1 && matches!(result, Err(SubmitError::Rejected(_))) {
+                    log::debug!(
+                        "Last.fm rejected a batch of {} scrobbles, submitting them one at a time",
+                        count
+                    );
+                    singles = count;
+                    continue;
+                }
+                singles = singles.saturating_sub(1);
+                if !settle(&mut self.queue.lastfm, count, result, "Last.fm") {
+                    break;
+                }
+                self.queue.save();
+            }
+        }
+        if let Some(listenbrainz) = self.listenbrainz.as_ref() {
+            while !self.queue.listenbrainz.is_empty() {
+                let count = self.queue.listenbrainz.len().min(listenbrainz::BATCH_SIZE);
+                let result = listenbrainz
+                    .scrobble(&self.queue.listenbrainz[..count])
+                    .await;
+                if !settle(&mut self.queue.listenbrainz, count, result, "ListenBrainz") {
+                    break;
+                }
+                self.queue.save();
+            }
+        }
+    }
+}
+
+/// Drop the first `count` pending listens unless the submission should be retried.
+/// Returns whether to go on with the next batch.
+fn settle(
+    pending: &mut Vec<Listen>,
+    count: usize,
+    result: Result<(), SubmitError>,
+    service: &str,
+) -> bool {
+    match result {
+        Ok(()) => {
+            log::debug!("Submitted {} scrobbles to {}", count, service);
+            pending.drain(..count);
+            true
+        }
+        Err(SubmitError::Rejected(e)) => {
+            log::warn!("{} rejected {} scrobbles: {}", service, count, e);
+            pending.drain(..count);
+            true
+        }
+        Err(SubmitError::Retry(e)) => {
+            log::warn!(
+                "Could not submit scrobbles to {} ({} queued): {}",
+                service,
+                pending.len(),
+                e
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::scrobbling::{LastFmConfig, ListenBrainzConfig};
+    use crate::app::scrobbler::tracker::Track;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Minimal HTTP server answering every request with `status` and `body`; requests are
+    /// sent to the channel
+    async fn mock_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, mpsc::UnboundedReceiver<String>) {
+        mock_server_with(move |_| (status, body)).await
+    }
+
+    /// Like [`mock_server`], answering each request with what `respond` returns for it
+    async fn mock_server_with(
+        respond: impl Fn(&str) -> (&'static str, &'static str) + Send + 'static,
+    ) -> (String, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read headers, then as much body as Content-Length says
+                loop {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + length || n == 0 {
+                            break;
+                        }
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).into_owned();
+                let (status, body) = respond(&request);
+                let _ = tx.send(request);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (address, rx)
+    }
+
+    fn listen() -> Listen {
+        Listen {
+            track: Track {
+                artist: "Artist".to_string(),
+                title: "Title".to_string(),
+                album: Some("Album".to_string()),
+                duration_secs: Some(200),
+                track_number: Some(1),
+            },
+            listened_at: 1700000000,
+        }
+    }
+
+    fn config(lastfm: &str, listenbrainz: &str) -> ScrobblingConfig {
+        ScrobblingConfig {
+            enabled: true,
+            lastfm: Some(LastFmConfig {
+                api_key: "key".to_string(),
+                api_secret: "secret".to_string(),
+                session_key: Some("session".to_string()),
+                username: None,
+                password: None,
+                endpoint: lastfm.to_string(),
+            }),
+            listenbrainz: Some(ListenBrainzConfig {
+                token: "token".to_string(),
+                endpoint: listenbrainz.to_string(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrobble_to_mock_servers() {
+        let (lastfm_url, mut lastfm_requests) = mock_server("200 OK", r#"{"scrobbles":{}}"#).await;
+        let (lb_url, mut lb_requests) = mock_server("200 OK", r#"{"status":"ok"}"#).await;
+        let mut submitter = Submitter::new(&config(&lastfm_url, &lb_url), None).unwrap();
+
+        submitter.handle(ScrobbleEvent::Scrobble(listen())).await;
+        assert!(submitter.queue.is_empty());
+
+        let request = lastfm_requests.recv().await.unwrap();
+        assert!(request.starts_with("POST / "));
+        assert!(request.contains("method=track.scrobble"));
+        assert!(request.contains("timestamp%5B0%5D=1700000000"));
+        assert!(request.contains("api_sig="));
+
+        let request = lb_requests.recv().await.unwrap();
+        assert!(request.starts_with("POST /1/submit-listens "));
+        assert!(request.contains("Token token"));
+        assert!(request.contains(r#""listen_type":"single""#));
+        assert!(request.contains(r#""listened_at":1700000000"#));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_service_keeps_listens_queued() {
+        // Nothing listens on a port that was just freed
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let mut submitter = Submitter::new(&config(&closed, &closed), None).unwrap();
+        submitter.handle(ScrobbleEvent::Scrobble(listen())).await;
+        assert_eq!(submitter.queue.lastfm, vec![listen()]);
+        assert_eq!(submitter.queue.listenbrainz, vec![listen()]);
+    }
+
+    #[tokio::test]
+    async fn test_bad_credentials_keep_listens_queued() {
+        // An expired token, without an error code in the body
+        let (url, _requests) = mock_server("401 Unauthorized", r#"{"code":401}"#).await;
+        let mut submitter = Submitter::new(&config(&url, &url), None).unwrap();
+        submitter.handle(ScrobbleEvent::Scrobble(listen())).await;
+        assert_eq!(submitter.queue.lastfm, vec![listen()]);
+        assert_eq!(submitter.queue.listenbrainz, vec![listen()]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_batch_drops_only_rejected_listens() {
+        // Last.fm fails the whole request over one bad listen
+        let (url, mut requests) = mock_server_with(|request| {
+            if request.contains("=Bad") {
+                (
+                    "400 Bad Request",
+                    r#"{"error":6,"message":"Invalid parameters"}"#,
+                )
+            } else {
+                ("200 OK", r#"{"scrobbles":{}}"#)
+            }
+        })
+        .await;
+        let mut submitter = Submitter::new(&config(&url, &url), None).unwrap();
+        submitter.listenbrainz = None;
+
+        let mut bad = listen();
+        bad.track.title = "Bad".to_string();
+        let mut later = listen();
+        later.listened_at += 300;
+        submitter.queue.lastfm = vec![listen(), bad, later];
+        submitter.flush().await;
+        assert!(submitter.queue.lastfm.is_empty());
+
+        // The batch, then each listen on its own
+        let mut sent = Vec::new();
+        while let Ok(request) = requests.try_recv() {
+            sent.push(request);
+        }
+        assert_eq!(sent.len(), 4);
+        assert!(sent[0].contains("timestamp%5B2%5D="));
+        assert!(sent[1..].iter().all(|r| !r.contains("timestamp%5B1%5D=")));
+    }
+}
@@ -0,0 +1,126 @@
+use super::tracker::Listen;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Oldest listens are dropped beyond this many per service
+const MAX_PENDING: usize = 5000;
+
+/// Listens waiting to be submitted, kept on disk so offline listens survive restarts
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScrobbleQueue {
+    #[serde(default)]
+    pub lastfm: Vec<Listen>,
+    #[serde(default)]
+    pub listenbrainz: Vec<Listen>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl ScrobbleQueue {
+    /// Load the queue from `path`, starting empty if it is missing or unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let mut queue: Self = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable scrobble queue {:?}: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        if !queue.is_empty() {
+            log::info!(
+                "Loaded {} pending Last.fm and {} pending ListenBrainz scrobbles",
+                queue.lastfm.len(),
+                queue.listenbrainz.len()
+            );
+        }
+        queue.path = Some(path);
+        queue
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lastfm.is_empty() && self.listenbrainz.is_empty()
+    }
+
+    /// Queue a listen for the enabled services
+    pub fn push(&mut self, listen: &Listen, lastfm: bool, listenbrainz: bool) {
+        for (enabled, pending) in [
+            (lastfm, &mut self.lastfm),
+            (listenbrainz, &mut self.listenbrainz),
+        ] {
+            if enabled {
+                pending.push(listen.clone());
+                if pending.len() > MAX_PENDING {
+                    let excess = pending.len() - MAX_PENDING;
+                    pending.drain(..excess);
+                }
+            }
+        }
+    }
+
+    /// Write the queue back to disk (the file is removed once the queue is empty)
+    pub fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = self.write(path) {
+            log::warn!("Failed to save scrobble queue to {:?}: {}", path, e);
+        }
+    }
+
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        if self.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write then rename so a crash never leaves a truncated queue
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::scrobbler::tracker::Track;
+
+    fn listen(at: u64) -> Listen {
+        Listen {
+            track: Track {
+                artist: "Artist".to_string(),
+                title: "Title".to_string(),
+                album: None,
+                duration_secs: Some(200),
+                track_number: None,
+            },
+            listened_at: at,
+        }
+    }
+
+    #[test]
+    fn test_queue_round_trip() {
+        let dir = std::env::temp_dir().join(format!("zarumet-queue-{}", std::process::id()));
+        let path = dir.join("scrobble_queue.json");
+
+        let mut queue = ScrobbleQueue::load(path.clone());
+        assert!(queue.is_empty());
+        queue.push(&listen(1), true, false);
+        queue.push(&listen(2), true, true);
+        queue.save();
+
+        let mut loaded = ScrobbleQueue::load(path.clone());
+        assert_eq!(loaded.lastfm, vec![listen(1), listen(2)]);
+        assert_eq!(loaded.listenbrainz, vec![listen(2)]);
+
+        loaded.lastfm.clear();
+        loaded.listenbrainz.clear();
+        loaded.save();
+        assert!(!path.exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
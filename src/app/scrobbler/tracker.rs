@@ -0,0 +1,297 @@
+use crate::app::SongInfo;
+use crate::app::song::song_info::{UNKNOWN_ALBUM, UNKNOWN_ARTIST, UNKNOWN_TITLE};
+use mpd_client::responses::PlayState;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Tracks shorter than this are never scrobbled
+const MIN_TRACK_LENGTH: Duration = Duration::from_secs(30);
+/// A track is scrobbled after half its length or this much listening, whichever comes first
+const MAX_LISTEN_THRESHOLD: Duration = Duration::from_secs(240);
+/// Position below which a jump back is a replay of the same song (repeat single)
+const RESTART_MARGIN: Duration = Duration::from_secs(5);
+
+/// Track details submitted to the scrobbling services
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Track {
+    pub artist: String,
+    pub title: String,
+    pub album: Option<String>,
+    pub duration_secs: Option<u64>,
+    pub track_number: Option<u64>,
+}
+
+impl Track {
+    /// Songs without artist or title tags are not scrobbled
+    pub fn from_song(song: &SongInfo) -> Option<Self> {
+        // Placeholders `SongInfo::from_song` puts in for missing tags
+        if song.artist == UNKNOWN_ARTIST || song.title == UNKNOWN_TITLE {
+            return None;
+        }
+        Some(Self {
+            artist: song.artist.clone(),
+            title: song.title.clone(),
+            album: (song.album != UNKNOWN_ALBUM).then(|| song.album.clone()),
+            duration_secs: song.duration.map(|d| d.as_secs()),
+            track_number: (song.track_number > 0).then_some(song.track_number),
+        })
+    }
+}
+
+/// A finished listen, ready to be scrobbled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Listen {
+    pub track: Track,
+    /// When playback of the track started (UNIX seconds)
+    pub listened_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScrobbleEvent {
+    NowPlaying(Track),
+    Scrobble(Listen),
+}
+
+/// Listening time needed before a track of `length` is scrobbled, None if it never is
+pub fn scrobble_threshold(length: Option<Duration>) -> Option<Duration> {
+    let length = length.filter(|length| *length >= MIN_TRACK_LENGTH)?;
+    Some((length / 2).min(MAX_LISTEN_THRESHOLD))
+}
+
+#[derive(Debug)]
+struct CurrentPlay {
+    file_path: PathBuf,
+    track: Option<Track>,
+    listened_at: u64,
+    /// Time actually spent playing, so seeking does not count as listening
+    played: Duration,
+    /// Last update while playing, None while paused or stopped
+    last_tick: Option<Instant>,
+    last_elapsed: Duration,
+    scrobbled: bool,
+}
+
+/// Turns playback state updates into "now playing" and scrobble events
+#[derive(Debug, Default)]
+pub struct ScrobbleTracker {
+    current: Option<CurrentPlay>,
+}
+
+impl ScrobbleTracker {
+    /// Feed the current playback state; `now` is a monotonic time and `wall_clock`
+    /// the UNIX time in seconds
+    pub fn update(
+        &mut self,
+        song: Option<&SongInfo>,
+        state: Option<PlayState>,
+        elapsed: Option<Duration>,
+        now: Instant,
+        wall_clock: u64,
+    ) -> Vec<ScrobbleEvent> {
+        let mut events = Vec::new();
+        let elapsed = elapsed.unwrap_or_default();
+        let playing = state == Some(PlayState::Playing);
+
+        // Count the time played since the last update
+        if let Some(play) = self.current.as_mut()
+            && let Some(last_tick) = play.last_tick
+        {
+            play.played += now.saturating_duration_since(last_tick);
+        }
+
+        let Some(song) = song else {
+            self.current = None;
+            return events;
+        };
+
+        let replayed = self.current.as_ref().is_some_and(|play| {
+            play.scrobbled
+                && elapsed < RESTART_MARGIN
+                && elapsed + RESTART_MARGIN < play.last_elapsed
+        });
+        let same_song = self
+            .current
+            .as_ref()
+            .is_some_and(|play| play.file_path == song.file_path);
+        if !same_song || replayed {
+            self.current = Some(CurrentPlay {
+                file_path: song.file_path.clone(),
+                track: Track::from_song(song),
+                listened_at: wall_clock.saturating_sub(elapsed.as_secs()),
+                played: Duration::ZERO,
+                last_tick: None,
+                last_elapsed: elapsed,
+                scrobbled: false,
+            });
+        }
+
+        let Some(play) = self.current.as_mut() else {
+            return events;
+        };
+        let Some(track) = play.track.clone() else {
+            return events;
+        };
+
+        // Announce on start and on resume, services expire "now playing" on their own
+        if playing && play.last_tick.is_none() {
+            events.push(ScrobbleEvent::NowPlaying(track.clone()));
+        }
+        play.last_tick = playing.then_some(now);
+        play.last_elapsed = elapsed;
+
+        if !play.scrobbled
+            && let Some(threshold) = scrobble_threshold(song.duration)
+            && play.played >= threshold
+        {
+            play.scrobbled = true;
+            events.push(ScrobbleEvent::Scrobble(Listen {
+                track,
+                listened_at: play.listened_at,
+            }));
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(file: &str, length_secs: u64) -> SongInfo {
+        SongInfo {
+            title: "Title".to_string(),
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            file_path: PathBuf::from(file),
+            format: None,
+            play_state: None,
+            progress: None,
+            elapsed: None,
+            duration: Some(Duration::from_secs(length_secs)),
+            disc_number: 1,
+            track_number: 2,
+            queue_id: None,
+            priority: 0,
+        }
+    }
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn test_scrobble_threshold() {
+        assert_eq!(scrobble_threshold(Some(secs(20))), None);
+        assert_eq!(scrobble_threshold(None), None);
+        assert_eq!(scrobble_threshold(Some(secs(200))), Some(secs(100)));
+        assert_eq!(scrobble_threshold(Some(secs(1200))), Some(secs(240)));
+    }
+
+    #[test]
+    fn test_now_playing_then_scrobble_at_half() {
+        let mut tracker = ScrobbleTracker::default();
+        let song = song("a.flac", 200);
+        let start = Instant::now();
+        let playing = Some(PlayState::Playing);
+
+        let events = tracker.update(Some(&song), playing, Some(secs(0)), start, 1000);
+        assert!(matches!(&events[..], [ScrobbleEvent::NowPlaying(t)] if t.title == "Title"));
+
+        let events = tracker.update(Some(&song), playing, Some(secs(99)), start + secs(99), 1099);
+        assert!(events.is_empty());
+
+        let events = tracker.update(
+            Some(&song),
+            playing,
+            Some(secs(100)),
+            start + secs(100),
+            1100,
+        );
+        assert_eq!(events.len(), 1);
+        let ScrobbleEvent::Scrobble(listen) = &events[0] else {
+            panic!("expected a scrobble");
+        };
+        assert_eq!(listen.listened_at, 1000);
+
+        // Only once per play
+        let events = tracker.update(
+            Some(&song),
+            playing,
+            Some(secs(150)),
+            start + secs(150),
+            1150,
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_paused_and_seeked_time_does_not_count() {
+        let mut tracker = ScrobbleTracker::default();
+        let song = song("a.flac", 200);
+        let start = Instant::now();
+
+        tracker.update(
+            Some(&song),
+            Some(PlayState::Playing),
+            Some(secs(0)),
+            start,
+            0,
+        );
+        tracker.update(
+            Some(&song),
+            Some(PlayState::Paused),
+            Some(secs(60)),
+            start + secs(60),
+            60,
+        );
+        // A long pause, then a seek close to the end
+        let events = tracker.update(
+            Some(&song),
+            Some(PlayState::Playing),
+            Some(secs(190)),
+            start + secs(600),
+            600,
+        );
+        assert!(matches!(&events[..], [ScrobbleEvent::NowPlaying(_)]));
+        let events = tracker.update(
+            Some(&song),
+            Some(PlayState::Playing),
+            Some(secs(199)),
+            start + secs(609),
+            609,
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_song_change_and_replay() {
+        let mut tracker = ScrobbleTracker::default();
+        let first = song("a.flac", 60);
+        let start = Instant::now();
+        let playing = Some(PlayState::Playing);
+
+        tracker.update(Some(&first), playing, Some(secs(0)), start, 0);
+        let events = tracker.update(Some(&first), playing, Some(secs(30)), start + secs(30), 30);
+        assert!(matches!(&events[..], [ScrobbleEvent::Scrobble(_)]));
+
+        // Repeat single starts the same file over
+        let events = tracker.update(Some(&first), playing, Some(secs(1)), start + secs(61), 61);
+        assert!(matches!(&events[..], [ScrobbleEvent::NowPlaying(_)]));
+
+        let second = song("b.flac", 60);
+        let events = tracker.update(Some(&second), playing, Some(secs(0)), start + secs(70), 70);
+        assert!(matches!(&events[..], [ScrobbleEvent::NowPlaying(_)]));
+
+        let mut untagged = song("c.flac", 60);
+        untagged.artist = UNKNOWN_ARTIST.to_string();
+        let events = tracker.update(
+            Some(&untagged),
+            playing,
+            Some(secs(0)),
+            start + secs(80),
+            80,
+        );
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+
+use crate::App;
+use crate::app::ui::{DisplayItem, MenuMode, PanelFocus, compute_album_display_list};
+use mpd_client::Client;
+use ratatui::widgets::ListState;
+
+/// Where the UI was when quitting, saved in the state file and restored on the next start.
+///
+/// Items are kept by name or URI rather than by index, so they are found again after
+/// the library changed; whatever is gone falls back to the default selection.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub menu_mode: MenuMode,
+    pub panel_focus: PanelFocus,
+    pub artists_panel_focus: Option<PanelFocus>,
+    pub albums_panel_focus: Option<PanelFocus>,
+    /// Selected artist in the Artists view
+    pub artist: Option<String>,
+    /// Selected album of that artist
+    pub artist_album: Option<String>,
+    /// URI of the selected song, when on a track of an expanded album
+    pub artist_song: Option<String>,
+    /// Artist and name of the expanded albums
+    pub expanded_albums: Vec<(String, String)>,
+    /// Artist and name of the album selected in the Albums view
+    pub album: Option<(String, String)>,
+    /// URI of the selected track of that album
+    pub album_song: Option<String>,
+    /// URI of the selected queue song
+    pub queue_song: Option<String>,
+    pub queue_offset: usize,
+    pub artists_offset: usize,
+    pub artist_albums_offset: usize,
+    pub albums_offset: usize,
+    pub album_tracks_offset: usize,
+}
+
+/// Scroll `state` to `offset`, keeping the selected item in view
+fn restore_offset(state: &mut ListState, offset: usize) {
+    let offset = state.selected().map_or(0, |selected| offset.min(selected));
+    *state.offset_mut() = offset;
+}
+
+impl App {
+    /// Capture where the UI is, to be restored on the next start
+    pub fn session(&self) -> Session {
+        let library = self.library.as_ref();
+        let artist = self
+            .artist_list_state
+            .selected()
+            .zip(library)
+            .and_then(|(idx, library)| library.get_artist(idx));
+
+        // Selected album or expanded track of the selected artist
+        let artist_row = artist.as_ref().and_then(|artist| {
+            let (display_items, _album_indices) =
+                compute_album_display_list(artist, &self.expanded_albums);
+            let row = self.album_display_list_state.selected()?;
+            let album = display_items
+                .get(..=row)?
+                .iter()
+                .rev()
+                .find_map(|item| match item {
+                    DisplayItem::Album(name) => Some(name.clone()),
+                    DisplayItem::Song(..) => None,
+                });
+            let song = match display_items.get(row) {
+                Some(DisplayItem::Song(_, _, file_path)) => {
+                    Some(file_path.to_string_lossy().into_owned())
+                }
+                _ => None,
+            };
+            Some((album, song))
+        });
+        let (artist_album, artist_song) = artist_row.unwrap_or_default();
+
+        let album = self
+            .all_albums_list_state
+            .selected()
+            .zip(library)
+            .and_then(|(idx, library)| library.all_albums.get(idx));
+        let album_song = album.and_then(|(_, album)| {
+            let song = album.tracks.get(self.album_tracks_list_state.selected()?)?;
+            Some(song.file_path.to_string_lossy().into_owned())
+        });
+
+        let mut expanded_albums: Vec<_> = self.expanded_albums.iter().cloned().collect();
+        expanded_albums.sort();
+
+        Session {
+            menu_mode: self.menu_mode.clone(),
+            panel_focus: self.panel_focus.clone(),
+            artists_panel_focus: Some(self.artists_panel_focus.clone()),
+            albums_panel_focus: Some(self.albums_panel_focus.clone()),
+            artist: artist.map(|artist| artist.name),
+            artist_album,
+            artist_song,
+            expanded_albums,
+            album: album.map(|(artist, album)| (artist.clone(), album.name.clone())),
+            album_song,
+            queue_song: self
+                .queue_list_state
+                .selected()
+                .and_then(|idx| self.queue.get(idx))
+                .map(|song| song.file_path.to_string_lossy().into_owned()),
+            queue_offset: self.queue_list_state.offset(),
+            artists_offset: self.artist_list_state.offset(),
+            artist_albums_offset: self.album_display_list_state.offset(),
+            albums_offset: self.all_albums_list_state.offset(),
+            album_tracks_offset: self.album_tracks_list_state.offset(),
+        }
+    }
+
+    /// Go back to a saved session, once the library and queue are loaded
+    pub async fn restore_session(&mut self, session: Session, client: &Client) {
+        if let Some(focus) = session.artists_panel_focus {
+            self.artists_panel_focus = focus;
+        }
+        if let Some(focus) = session.albums_panel_focus {
+            self.albums_panel_focus = focus;
+        }
+        self.menu_mode = session.menu_mode;
+        self.panel_focus = session.panel_focus;
+        self.expanded_albums = session.expanded_albums.into_iter().collect();
+
+        // Queue
+        if let Some(idx) = session.queue_song.and_then(|uri| {
+            self.queue
+                .iter()
+                .position(|song| song.file_path.to_string_lossy() == uri)
+        }) {
+            self.queue_list_state.select(Some(idx));
+            self.selected_queue_index = Some(idx);
+        }
+        restore_offset(&mut self.queue_list_state, session.queue_offset);
+
+        // Artists view
+        if let Some(idx) = session.artist.and_then(|name| {
+            self.library
+                .as_ref()?
+                .artists
+                .iter()
+                .position(|artist| artist.name == name)
+        }) {
+            if let Some(library) = self.library.as_mut()
+                && let Err(e) = library.load_artist(client, idx).await
+            {
+                log::warn!("Failed to load artist from the last session: {}", e);
+            }
+            self.artist_list_state.select(Some(idx));
+            self.restore_artist_row(session.artist_album, session.artist_song);
+        }
+        restore_offset(&mut self.artist_list_state, session.artists_offset);
+        restore_offset(
+            &mut self.album_display_list_state,
+            session.artist_albums_offset,
+        );
+
+        // Albums view, whose album list is only loaded when it is shown
+        if self.menu_mode == MenuMode::Albums {
+            self.preload_albums_for_view(client).await;
+            if let Some(library) = self.library.as_ref()
+                && let Some((artist, name)) = session.album
+                && let Some(idx) = library
+                    .all_albums
+                    .iter()
+                    .position(|(a, album)| *a == artist && album.name == name)
+            {
+                let track = session.album_song.and_then(|uri| {
+                    library.all_albums[idx]
+                        .1
+                        .tracks
+                        .iter()
+                        .position(|song| song.file_path.to_string_lossy() == uri)
+                });
+                self.all_albums_list_state.select(Some(idx));
+                self.album_tracks_list_state.select(track);
+            }
+            restore_offset(&mut self.all_albums_list_state, session.albums_offset);
+            restore_offset(
+                &mut self.album_tracks_list_state,
+                session.album_tracks_offset,
+            );
+        }
+
+        match self.menu_mode {
+            MenuMode::Playlists => self.refresh_playlists(client).await,
+            MenuMode::Browse => self.refresh_browse(client).await,
+            _ => {}
+        }
+        self.dirty.mark_full_redraw();
+    }
+
+    /// Select the saved album, or song of an expanded album, of the selected artist
+    pub fn restore_artist_row(&mut self, album: Option<String>, song: Option<String>) {
+        let Some(artist) = self
+            .library
+            .as_ref()
+            .zip(self.artist_list_state.selected())
+            .and_then(|(library, idx)| library.get_artist(idx))
+        else {
+            return;
+        };
+        let (display_items, _album_indices) =
+            compute_album_display_list(&artist, &self.expanded_albums);
+
+        let row = display_items
+            .iter()
+            .position(|item| match item {
+                DisplayItem::Song(_, _, file_path) => {
+                    song.as_deref() == Some(&*file_path.to_string_lossy())
+                }
+                DisplayItem::Album(_) => false,
+            })
+            .or_else(|| {
+                display_items.iter().position(
+                    |item| matches!(item, DisplayItem::Album(name) if Some(name) == album.as_ref()),
+                )
+            });
+        let Some(row) = row else {
+            return;
+        };
+
+        self.album_display_list_state.select(Some(row));
+        // Keep the legacy album_list_state on the album the row belongs to
+        let album_count = display_items[..=row]
+            .iter()
+            .filter(|item| matches!(item, DisplayItem::Album(_)))
+            .count();
+        self.album_list_state.select(album_count.checked_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip() {
+        let session = Session {
+            menu_mode: MenuMode::Albums,
+            panel_focus: PanelFocus::AlbumTracks,
+            artist: Some("Björk".to_string()),
+            expanded_albums: vec![("Björk".to_string(), "Homogenic".to_string())],
+            album: Some(("Björk".to_string(), "Post".to_string())),
+            queue_offset: 12,
+            ..Session::default()
+        };
+        let text = toml::to_string(&session).unwrap();
+        assert_eq!(toml::from_str::<Session>(&text).unwrap(), session);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let session: Session = toml::from_str("menu_mode = \"artists\"").unwrap();
+        assert_eq!(session.menu_mode, MenuMode::Artists);
+        assert_eq!(session.panel_focus, PanelFocus::Artists);
+        assert!(session.artist.is_none());
+    }
+}
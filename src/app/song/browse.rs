@@ -0,0 +1,243 @@
+use crate::app::SongInfo;
+use crate::app::song::song_info::{UNKNOWN_ALBUM, UNKNOWN_ARTIST};
+use mpd_client::{
+    client::{Client, CommandError},
+    protocol::command::Command as RawCommand,
+};
+use ratatui::widgets::ListState;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// An entry of an MPD music directory listing
+#[derive(Debug, Clone)]
+pub enum BrowseEntry {
+    /// Sub-directory (path relative to the music directory)
+    Directory(String),
+    /// Song file, with whatever tags it has
+    Song(SongInfo),
+    /// Playlist file inside the music directory
+    Playlist(String),
+}
+
+impl BrowseEntry {
+    /// Path of the entry relative to the music directory
+    pub fn path(&self) -> String {
+        match self {
+            BrowseEntry::Directory(path) | BrowseEntry::Playlist(path) => path.clone(),
+            BrowseEntry::Song(song) => song.file_path.to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Last component of the entry path
+    pub fn name(&self) -> &str {
+        match self {
+            BrowseEntry::Directory(path) | BrowseEntry::Playlist(path) => file_name(path),
+            BrowseEntry::Song(song) => song
+                .file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Browse view state: current directory listing and selection
+#[derive(Debug, Default)]
+pub struct BrowseState {
+    /// Current directory relative to the music directory ("" is the root)
+    pub path: String,
+    /// Entries of the current directory
+    pub entries: Vec<BrowseEntry>,
+    /// Selection in the current directory
+    pub list_state: ListState,
+    /// Selection in each parent directory, restored when going back up
+    pub parent_selections: Vec<Option<usize>>,
+    /// Whether the current directory has been listed yet
+    pub loaded: bool,
+}
+
+impl BrowseState {
+    pub fn selected_entry(&self) -> Option<&BrowseEntry> {
+        self.list_state
+            .selected()
+            .and_then(|idx| self.entries.get(idx))
+    }
+
+    /// Path of the parent of the current directory, None at the root
+    pub fn parent_path(&self) -> Option<String> {
+        if self.path.is_empty() {
+            return None;
+        }
+        Some(
+            self.path
+                .rsplit_once('/')
+                .map(|(parent, _)| parent.to_string())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Show a new listing, selecting `selected` (clamped) or the first entry
+    pub fn set_entries(
+        &mut self,
+        path: String,
+        entries: Vec<BrowseEntry>,
+        selected: Option<usize>,
+    ) {
+        let selected = entries
+            .len()
+            .checked_sub(1)
+            .map(|last| selected.unwrap_or(0).min(last));
+        self.path = path;
+        self.entries = entries;
+        self.list_state.select(selected);
+        self.loaded = true;
+    }
+}
+
+/// List a directory of the music database.
+/// MPD command: lsinfo "path"
+pub async fn list_directory(client: &Client, path: &str) -> Result<Vec<BrowseEntry>, CommandError> {
+    let mut command = RawCommand::new("lsinfo");
+    if !path.is_empty() {
+        command = command.argument(path);
+    }
+    let frame = client.raw_command(command).await?;
+    Ok(parse_lsinfo(&frame))
+}
+
+/// Parse the fields of an `lsinfo` response.
+/// Each `directory`, `file` or `playlist` key starts a new entry; the keys after `file`
+/// are the tags of that song.
+pub fn parse_lsinfo<'a>(fields: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<BrowseEntry> {
+    let mut entries = Vec::new();
+    let mut song: Option<SongInfo> = None;
+
+    for (key, value) in fields {
+        match key {
+            "directory" | "file" | "playlist" => {
+                entries.extend(song.take().map(finish_song));
+                match key {
+                    "directory" => entries.push(BrowseEntry::Directory(value.to_string())),
+                    "playlist" => entries.push(BrowseEntry::Playlist(value.to_string())),
+                    _ => song = Some(empty_song(value)),
+                }
+            }
+            _ => {
+                if let Some(song) = song.as_mut() {
+                    apply_song_field(song, key, value);
+                }
+            }
+        }
+    }
+    entries.extend(song.take().map(finish_song));
+    entries
+}
+
+fn empty_song(file: &str) -> SongInfo {
+    SongInfo {
+        title: String::new(),
+        artist: UNKNOWN_ARTIST.to_string(),
+        album: UNKNOWN_ALBUM.to_string(),
+        file_path: PathBuf::from(file),
+        format: None,
+        play_state: None,
+        progress: None,
+        elapsed: None,
+        duration: None,
+        disc_number: 1,
+        track_number: 0,
+        queue_id: None,
+        priority: 0,
+    }
+}
+
+fn apply_song_field(song: &mut SongInfo, key: &str, value: &str) {
+    match key {
+        "Title" => song.title = SongInfo::sanitize_string(value),
+        "Artist" => song.artist = SongInfo::sanitize_string(value),
+        "Album" => song.album = SongInfo::sanitize_string(value),
+        "Format" => song.format = Some(value.to_string()),
+        "duration" => song.duration = value.parse().ok().map(Duration::from_secs_f64),
+        // Older servers only send the rounded `Time`
+        "Time" if song.duration.is_none() => {
+            song.duration = value.parse().ok().map(Duration::from_secs);
+        }
+        "Track" => song.track_number = leading_number(value).unwrap_or(0),
+        "Disc" => song.disc_number = leading_number(value).unwrap_or(1),
+        _ => {}
+    }
+}
+
+/// Songs without a title tag are shown by file name
+fn finish_song(mut song: SongInfo) -> BrowseEntry {
+    if song.title.is_empty() {
+        song.title = song
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+    }
+    BrowseEntry::Song(song)
+}
+
+/// Number before an optional "/total" suffix, e.g. "3/12"
+fn leading_number(value: &str) -> Option<u64> {
+    value.split('/').next()?.trim().parse().ok()
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lsinfo() {
+        let fields = [
+            ("directory", "Music/Artist"),
+            ("Last-Modified", "2024-01-01T00:00:00Z"),
+            ("file", "Music/untagged.flac"),
+            ("duration", "201.5"),
+            ("file", "Music/tagged.mp3"),
+            ("Title", "Song"),
+            ("Artist", "Someone"),
+            ("Track", "3/12"),
+            ("Time", "180"),
+            ("playlist", "Music/mix.m3u"),
+        ];
+        let entries = parse_lsinfo(fields);
+        assert_eq!(entries.len(), 4);
+
+        assert!(matches!(&entries[0], BrowseEntry::Directory(p) if p == "Music/Artist"));
+        assert_eq!(entries[0].name(), "Artist");
+
+        let BrowseEntry::Song(untagged) = &entries[1] else {
+            panic!("expected a song");
+        };
+        assert_eq!(untagged.title, "untagged.flac");
+        assert_eq!(untagged.duration, Some(Duration::from_secs_f64(201.5)));
+
+        let BrowseEntry::Song(tagged) = &entries[2] else {
+            panic!("expected a song");
+        };
+        assert_eq!(tagged.title, "Song");
+        assert_eq!(tagged.artist, "Someone");
+        assert_eq!(tagged.track_number, 3);
+        assert_eq!(tagged.duration, Some(Duration::from_secs(180)));
+
+        assert!(matches!(&entries[3], BrowseEntry::Playlist(p) if p == "Music/mix.m3u"));
+        assert_eq!(entries[3].path(), "Music/mix.m3u");
+    }
+
+    #[test]
+    fn test_parent_path() {
+        let mut state = BrowseState::default();
+        assert_eq!(state.parent_path(), None);
+        state.path = "Music".to_string();
+        assert_eq!(state.parent_path(), Some(String::new()));
+        state.path = "Music/Artist/Album".to_string();
+        assert_eq!(state.parent_path(), Some("Music/Artist".to_string()));
+    }
+}
@@ -1,13 +1,20 @@
 use crate::app::{
     SongInfo,
-    song::{Album, Artist, LazyArtist, artist::ArtistData},
+    song::{
+        Album, Artist, LazyArtist,
+        artist::ArtistData,
+        library_cache,
+        tag_view::{NO_TAG_VALUE, TagLevel},
+    },
 };
 use mpd_client::{
-    client::Client,
+    client::{Client, CommandError},
     commands,
-    filter::{Filter, Operator},
+    filter::Filter,
+    protocol::command::Command as RawCommand,
     tag::Tag,
 };
+use std::collections::{HashMap, HashSet};
 
 /// Lazy-loading library that only fetches artist data when needed
 #[derive(Debug, Clone)]
@@ -22,42 +29,49 @@ pub struct LazyLibrary {
     pub all_albums_complete: bool,
     /// Flag to track if all_albums is sorted
     pub all_albums_sorted: bool,
+    /// `db_update` of the server when the library was loaded, to tell what changed since
+    pub db_update: Option<u64>,
+    /// What the entries are and how their songs are grouped, AlbumArtist → Album unless
+    /// a tag view is open
+    pub level: TagLevel,
 }
 
 impl LazyLibrary {
     /// Initialize the library by loading just the artist names.
     /// This is fast because it only fetches tag values, not full song metadata.
-    /// MPD command: list AlbumArtist
+    /// MPD commands: stats, list AlbumArtist
     pub async fn init(client: &Client) -> color_eyre::Result<Self> {
+        Self::init_level(client, TagLevel::default()).await
+    }
+
+    /// Initialize a library listing the entries of a tag view level.
+    /// MPD commands: stats, list <tag> <scope>
+    pub async fn init_level(client: &Client, level: TagLevel) -> color_eyre::Result<Self> {
         let start_time = std::time::Instant::now();
 
         log::info!("Initializing lazy library (loading artist names only)...");
 
+        // Taken first, so changes made while loading show up on the next refresh
+        let db_update = library_cache::db_update(client).await.ok();
+
         // Get all unique album artists using the List command
-        let album_artists_list = match client.command(commands::List::new(Tag::AlbumArtist)).await {
+        let artist_names = match list_entries(client, &level).await {
             Ok(list) => list,
             Err(e) => {
-                log::error!("MPD List command failed for AlbumArtist tag: {}", e);
+                log::error!("MPD List command failed for {:?} tag: {}", level.entry, e);
                 log::error!("This usually indicates:");
                 log::error!("  - MPD database corruption or inconsistency");
                 log::error!("  - Permission issues with music directory");
                 log::error!("  - Network/protocol issues with MPD server");
                 log::error!("  - Missing or invalid AlbumArtist tags in music files");
                 return Err(color_eyre::eyre::eyre!(
-                    "Failed to list album artists: {}",
+                    "Failed to list {:?} values: {}",
+                    level.entry,
                     e
                 ));
             }
         };
 
-        let mut artist_names: Vec<String> = album_artists_list
-            .into_iter()
-            .filter(|name| !name.is_empty())
-            .collect();
-
-        // Sort alphabetically
-        artist_names.sort_by_key(|a| a.to_lowercase());
-
         let artists: Vec<LazyArtist> = artist_names.into_iter().map(LazyArtist::new).collect();
 
         let duration = start_time.elapsed();
@@ -72,10 +86,104 @@ impl LazyLibrary {
             all_albums: Vec::new(),
             all_albums_complete: false,
             all_albums_sorted: false,
+            db_update,
+            level,
         })
     }
 
-    /// Load albums and songs for a specific artist by index.
+    /// Bring the library up to date after a database update, querying again only the
+    /// artists whose songs changed: songs modified since the last load, or a different
+    /// number of songs. Changed artists that were loaded are loaded again, the others
+    /// are kept as they are.
+    /// MPD commands: stats, list AlbumArtist, count group AlbumArtist,
+    /// find "(modified-since 'db_update')"
+    pub async fn refresh(&mut self, client: &Client) -> color_eyre::Result<()> {
+        let start_time = std::time::Instant::now();
+        let db_update = library_cache::db_update(client).await.ok();
+        if db_update.is_some() && db_update == self.db_update {
+            log::debug!("Library is up to date");
+            return Ok(());
+        }
+
+        let names = list_entries(client, &self.level)
+            .await
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to list album artists: {}", e))?;
+        let counts = count_artist_songs(client)
+            .await
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to count songs: {}", e))?;
+        let modified = match self.db_update {
+            Some(since) => modified_artists(client, since)
+                .await
+                .map_err(|e| color_eyre::eyre::eyre!("Failed to find modified songs: {}", e))?,
+            // Unknown age, so counts are all there is to go by
+            None => HashSet::new(),
+        };
+
+        let reload = self.apply_changes(names, &counts, &modified);
+        for &idx in &reload {
+            self.load_artist(client, idx).await?;
+        }
+        self.all_albums_complete = self.artists.iter().all(|a| a.is_loaded());
+        // Sorted now, so selections found by name in all_albums stay put
+        self.ensure_albums_sorted();
+        self.db_update = db_update;
+
+        log::info!(
+            "Library refreshed: {} artists, {} reloaded in {:?}",
+            self.artists.len(),
+            reload.len(),
+            start_time.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Replace the artist list with `names`, dropping the albums of artists that are
+    /// gone or changed. Returns the indices of the artists to load again: changed ones
+    /// that were loaded, and new ones if every artist was loaded.
+    fn apply_changes(
+        &mut self,
+        names: Vec<String>,
+        counts: &HashMap<String, u64>,
+        modified: &HashSet<String>,
+    ) -> Vec<usize> {
+        let was_complete = self.all_albums_complete;
+        let mut old: HashMap<String, LazyArtist> = std::mem::take(&mut self.artists)
+            .into_iter()
+            .map(|artist| (artist.name.clone(), artist))
+            .collect();
+
+        let mut reload = Vec::new();
+        let mut unchanged = HashSet::new();
+        for name in names {
+            let artist = match old.remove(&SongInfo::sanitize_string(&name)) {
+                Some(artist) if !is_stale(&artist, counts, modified) => {
+                    unchanged.insert(artist.name.clone());
+                    artist
+                }
+                Some(artist) => {
+                    if artist.is_loaded() {
+                        reload.push(self.artists.len());
+                    }
+                    LazyArtist::new(artist.name)
+                }
+                None => {
+                    if was_complete {
+                        reload.push(self.artists.len());
+                    }
+                    LazyArtist::new(name)
+                }
+            };
+            self.artists.push(artist);
+        }
+
+        self.all_albums
+            .retain(|(artist, _)| unchanged.contains(artist));
+        self.all_albums_complete = self.artists.iter().all(|a| a.is_loaded());
+        reload
+    }
+
+    /// Load albums and songs for a specific artist by index: the songs of the entry,
+    /// grouped by the level's group tag. Entries that open a deeper level have none.
     /// MPD command: find "(AlbumArtist == 'artist_name')" sort Album
     pub async fn load_artist(
         &mut self,
@@ -100,6 +208,11 @@ impl LazyLibrary {
             return Ok(());
         }
 
+        let Some(group) = self.level.group.clone() else {
+            self.artists[artist_index].albums = ArtistData::Loaded(Vec::new());
+            return Ok(());
+        };
+
         // Set state to Loading to prevent concurrent loads
         let artist_name = self.artists[artist_index].name.clone();
         self.artists[artist_index].albums = ArtistData::Loading;
@@ -108,8 +221,8 @@ impl LazyLibrary {
         let start_time = std::time::Instant::now();
 
         // Fetch all songs for this artist
-        let filter = Filter::new(Tag::AlbumArtist, Operator::Equal, artist_name.clone());
-        let find_cmd = commands::Find::new(filter).sort(Tag::Album);
+        let filter = self.level.entry_filter(&artist_name);
+        let find_cmd = commands::Find::new(filter).sort(group.clone());
 
         let songs = match client.command(find_cmd).await {
             Ok(songs) => songs,
@@ -136,7 +249,17 @@ impl LazyLibrary {
 
         for song in songs {
             let song_info = SongInfo::from_song(&song);
-            let album_name = song_info.album.clone();
+            let album_name = match group {
+                Tag::Album => song_info.album.clone(),
+                _ => song
+                    .tags
+                    .get(&group)
+                    .and_then(|values| values.first())
+                    .map_or_else(
+                        || NO_TAG_VALUE.to_string(),
+                        |value| SongInfo::sanitize_string(value),
+                    ),
+            };
             albums_map.entry(album_name).or_default().push(song_info);
         }
 
@@ -312,3 +435,210 @@ impl LazyLibrary {
         Ok(())
     }
 }
+
+/// Values of the level's tag within its scope, sorted alphabetically.
+/// MPD command: list <tag> <scope>
+async fn list_entries(client: &Client, level: &TagLevel) -> Result<Vec<String>, CommandError> {
+    let mut list = commands::List::new(level.entry.clone());
+    if let Some(filter) = level.scope_filter() {
+        list = list.filter(filter);
+    }
+    let mut names: Vec<String> = client
+        .command(list)
+        .await?
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort_by_key(|a| a.to_lowercase());
+    Ok(names)
+}
+
+/// Number of songs of each album artist.
+/// MPD command: count "(AlbumArtist != '')" group AlbumArtist
+async fn count_artist_songs(client: &Client) -> Result<HashMap<String, u64>, CommandError> {
+    let command = RawCommand::new("count")
+        .argument(Filter::tag_exists(Tag::AlbumArtist))
+        .argument("group")
+        .argument(Tag::AlbumArtist);
+    let frame = client.raw_command(command).await?;
+    Ok(parse_counts(&frame))
+}
+
+/// Parse a `count ... group AlbumArtist` response, where each `AlbumArtist` key is
+/// followed by the `songs` and `playtime` of that artist
+fn parse_counts<'a>(fields: impl IntoIterator<Item = (&'a str, &'a str)>) -> HashMap<String, u64> {
+    let mut counts = HashMap::new();
+    let mut artist = None;
+    for (key, value) in fields {
+        match key {
+            "AlbumArtist" => artist = Some(SongInfo::sanitize_string(value)),
+            "songs" => {
+                if let (Some(artist), Ok(songs)) = (artist.take(), value.parse()) {
+                    counts.insert(artist, songs);
+                }
+            }
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// Album artists of the songs modified since the `since` UNIX timestamp.
+/// MPD command: find "(modified-since 'since')"
+async fn modified_artists(client: &Client, since: u64) -> Result<HashSet<String>, CommandError> {
+    let command = RawCommand::new("find").argument(format!("(modified-since '{}')", since));
+    let frame = client.raw_command(command).await?;
+    Ok(frame
+        .fields()
+        .filter(|(key, _)| *key == "AlbumArtist")
+        .map(|(_, value)| SongInfo::sanitize_string(value))
+        .collect())
+}
+
+/// Whether a kept artist's albums no longer match the database
+fn is_stale(
+    artist: &LazyArtist,
+    counts: &HashMap<String, u64>,
+    modified: &HashSet<String>,
+) -> bool {
+    if modified.contains(&artist.name) {
+        return true;
+    }
+    match &artist.albums {
+        ArtistData::Loaded(albums) => {
+            let songs: usize = albums.iter().map(|album| album.tracks.len()).sum();
+            counts.get(&artist.name).copied().unwrap_or(0) != songs as u64
+        }
+        ArtistData::NotLoaded | ArtistData::Loading => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn album(artist: &str, name: &str, songs: usize) -> Album {
+        let tracks = (0..songs)
+            .map(|i| SongInfo {
+                title: format!("{} {}", name, i),
+                artist: artist.to_string(),
+                album: name.to_string(),
+                file_path: PathBuf::from(format!("{}/{}/{}.flac", artist, name, i)),
+                format: None,
+                play_state: None,
+                progress: None,
+                elapsed: None,
+                duration: None,
+                disc_number: 1,
+                track_number: i as u64 + 1,
+                queue_id: None,
+                priority: 0,
+            })
+            .collect();
+        Album::new(name.to_string(), tracks)
+    }
+
+    fn loaded(name: &str, albums: Vec<Album>) -> LazyArtist {
+        LazyArtist {
+            albums: ArtistData::Loaded(albums),
+            ..LazyArtist::new(name.to_string())
+        }
+    }
+
+    fn library(artists: Vec<LazyArtist>) -> LazyLibrary {
+        let all_albums = artists
+            .iter()
+            .flat_map(|artist| {
+                let name = artist.name.clone();
+                artist
+                    .to_artist()
+                    .albums
+                    .into_iter()
+                    .map(move |album| (name.clone(), album))
+            })
+            .collect();
+        LazyLibrary {
+            all_albums_complete: artists.iter().all(|a| a.is_loaded()),
+            artists,
+            all_albums,
+            all_albums_sorted: true,
+            db_update: Some(1),
+            level: TagLevel::default(),
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_counts() {
+        let fields = [
+            ("AlbumArtist", "Björk"),
+            ("songs", "42"),
+            ("playtime", "9000"),
+            ("AlbumArtist", "Can"),
+            ("songs", "7"),
+            ("playtime", "3000"),
+        ];
+        let counts = parse_counts(fields);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["Björk"], 42);
+        assert_eq!(counts["Can"], 7);
+    }
+
+    #[test]
+    fn test_only_changed_artists_are_reloaded() {
+        let mut library = library(vec![
+            loaded("Björk", vec![album("Björk", "Post", 2)]),
+            loaded("Can", vec![album("Can", "Tago Mago", 3)]),
+            LazyArtist::new("Dead".to_string()),
+            loaded("Faust", vec![album("Faust", "IV", 1)]),
+        ]);
+        let counts = HashMap::from([
+            ("Björk".to_string(), 2),
+            // A song was added
+            ("Can".to_string(), 4),
+            ("Dead".to_string(), 5),
+            ("Eno".to_string(), 1),
+            ("Faust".to_string(), 1),
+        ]);
+        // A song was retagged without changing the count
+        let modified = HashSet::from(["Faust".to_string()]);
+
+        let reload = library.apply_changes(
+            names(&["Björk", "Can", "Dead", "Eno", "Faust"]),
+            &counts,
+            &modified,
+        );
+
+        // Can and Faust were loaded and changed; Eno is new but not everything was loaded
+        assert_eq!(reload, [1, 4]);
+        assert!(library.artists[0].is_loaded());
+        assert!(!library.artists[1].is_loaded());
+        assert!(!library.artists[3].is_loaded());
+        let albums: Vec<&str> = library
+            .all_albums
+            .iter()
+            .map(|(_, album)| album.name.as_str())
+            .collect();
+        assert_eq!(albums, ["Post"]);
+        assert!(!library.all_albums_complete);
+    }
+
+    #[test]
+    fn test_complete_library_loads_new_artists() {
+        let mut library = library(vec![loaded("Can", vec![album("Can", "Ege Bamyasi", 2)])]);
+        let counts = HashMap::from([("Can".to_string(), 2), ("Eno".to_string(), 3)]);
+        let reload = library.apply_changes(names(&["Can", "Eno"]), &counts, &HashSet::new());
+        assert_eq!(reload, [1]);
+        assert_eq!(library.all_albums.len(), 1);
+
+        // Removed artists take their albums with them
+        let reload = library.apply_changes(names(&["Eno"]), &counts, &HashSet::new());
+        assert!(reload.is_empty());
+        assert_eq!(library.artists.len(), 1);
+        assert!(library.all_albums.is_empty());
+    }
+}
@@ -0,0 +1,322 @@
+//! Snapshot of the whole library on disk, so large libraries show up at startup without
+//! fetching every song from MPD.
+//!
+//! Snapshots are stored per server in the cache directory and are only used while MPD's
+//! `db_update` timestamp is the one they were taken at.
+
+use crate::app::SongInfo;
+use crate::app::song::{Album, LazyArtist, LazyLibrary, artist::ArtistData, tag_view::TagLevel};
+use mpd_client::{Client, client::CommandError, commands};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Bumped when the snapshot format changes, so older snapshots are ignored
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    /// MPD host the library belongs to
+    host: String,
+    /// `db_update` of the server when the snapshot was taken
+    db_update: u64,
+    artists: Vec<CachedArtist>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedArtist {
+    name: String,
+    albums: Vec<CachedAlbum>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedAlbum {
+    name: String,
+    tracks: Vec<CachedSong>,
+}
+
+/// The `SongInfo` fields a library song has; its album is the one it is stored in
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSong {
+    file: String,
+    title: String,
+    artist: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration: Option<f64>,
+    disc: u64,
+    track: u64,
+}
+
+impl CachedSong {
+    fn new(song: &SongInfo) -> Self {
+        Self {
+            file: song.file_path.to_string_lossy().into_owned(),
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            format: song.format.clone(),
+            duration: song.duration.map(|duration| duration.as_secs_f64()),
+            disc: song.disc_number,
+            track: song.track_number,
+        }
+    }
+
+    fn into_song_info(self, album: &str) -> SongInfo {
+        SongInfo {
+            title: self.title,
+            artist: self.artist,
+            album: album.to_string(),
+            file_path: PathBuf::from(self.file),
+            format: self.format,
+            play_state: None,
+            progress: None,
+            elapsed: None,
+            duration: self.duration.map(Duration::from_secs_f64),
+            disc_number: self.disc,
+            track_number: self.track,
+            queue_id: None,
+            priority: 0,
+        }
+    }
+}
+
+/// Directory holding the snapshots of every server
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("zarumet").join("library"))
+}
+
+/// Snapshot file of `host`, which may be a socket path
+fn snapshot_path(host: &str) -> Option<PathBuf> {
+    Some(cache_dir()?.join(snapshot_file_name(host)))
+}
+
+fn snapshot_file_name(host: &str) -> String {
+    let name: String = host
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}.json", name)
+}
+
+/// The server's `db_update` timestamp, which changes whenever its database does.
+/// MPD command: stats
+pub async fn db_update(client: &Client) -> Result<u64, CommandError> {
+    Ok(client.command(commands::Stats).await?.db_last_update)
+}
+
+/// Load the snapshot of `host`'s library, with the `db_update` it was taken at
+pub fn load(host: &str) -> Option<(u64, LazyLibrary)> {
+    let path = snapshot_path(host)?;
+    let contents = std::fs::read(&path).ok()?;
+    let start_time = std::time::Instant::now();
+    let loaded = parse(&contents, host);
+    match &loaded {
+        Some((_, library)) => log::info!(
+            "Library snapshot loaded: {} artists in {:?}",
+            library.artists.len(),
+            start_time.elapsed()
+        ),
+        None => log::warn!("Ignoring unusable library snapshot {:?}", path),
+    }
+    loaded
+}
+
+fn parse(contents: &[u8], host: &str) -> Option<(u64, LazyLibrary)> {
+    let snapshot: Snapshot = serde_json::from_slice(contents).ok()?;
+    if snapshot.version != FORMAT_VERSION || snapshot.host != host {
+        return None;
+    }
+    Some((snapshot.db_update, LazyLibrary::from_snapshot(snapshot)))
+}
+
+/// Save a fully loaded library as the snapshot of `host`
+pub fn save(host: &str, db_update: u64, library: &LazyLibrary) -> std::io::Result<()> {
+    let Some(path) = snapshot_path(host) else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Write then rename so a crash never leaves a truncated snapshot
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(
+        &tmp,
+        serde_json::to_vec(&library.to_snapshot(host, db_update))?,
+    )?;
+    std::fs::rename(tmp, path)
+}
+
+/// Delete the snapshots of every server. Returns whether there was anything to delete.
+pub fn purge() -> std::io::Result<bool> {
+    match cache_dir() {
+        Some(dir) if dir.exists() => std::fs::remove_dir_all(dir).map(|_| true),
+        _ => Ok(false),
+    }
+}
+
+impl LazyLibrary {
+    fn to_snapshot(&self, host: &str, db_update: u64) -> Snapshot {
+        let artists = self
+            .artists
+            .iter()
+            .map(|artist| {
+                let albums = match &artist.albums {
+                    ArtistData::Loaded(albums) => albums.as_slice(),
+                    ArtistData::NotLoaded | ArtistData::Loading => &[],
+                };
+                CachedArtist {
+                    name: artist.name.clone(),
+                    albums: albums
+                        .iter()
+                        .map(|album| CachedAlbum {
+                            name: album.name.clone(),
+                            tracks: album.tracks.iter().map(CachedSong::new).collect(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        Snapshot {
+            version: FORMAT_VERSION,
+            host: host.to_string(),
+            db_update,
+            artists,
+        }
+    }
+
+    /// Library with every artist loaded, as `preload_all_albums` leaves it
+    fn from_snapshot(snapshot: Snapshot) -> Self {
+        let mut all_albums = Vec::new();
+        let artists = snapshot
+            .artists
+            .into_iter()
+            .map(|cached| {
+                let albums: Vec<Album> = cached
+                    .albums
+                    .into_iter()
+                    .map(|album| {
+                        let tracks = album
+                            .tracks
+                            .into_iter()
+                            .map(|song| song.into_song_info(&album.name))
+                            .collect();
+                        Album::new(album.name, tracks)
+                    })
+                    .collect();
+                all_albums.extend(
+                    albums
+                        .iter()
+                        .map(|album| (cached.name.clone(), album.clone())),
+                );
+                LazyArtist {
+                    albums: ArtistData::Loaded(albums),
+                    ..LazyArtist::new(cached.name)
+                }
+            })
+            .collect();
+
+        let mut library = Self {
+            artists,
+            all_albums,
+            all_albums_complete: true,
+            all_albums_sorted: false,
+            db_update: Some(snapshot.db_update),
+            level: TagLevel::default(),
+        };
+        library.ensure_albums_sorted();
+        library
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(file: &str, album: &str, track: u64) -> SongInfo {
+        SongInfo {
+            title: format!("Title {}", track),
+            artist: "Artist".to_string(),
+            album: album.to_string(),
+            file_path: PathBuf::from(file),
+            format: Some("44100:16:2".to_string()),
+            play_state: None,
+            progress: None,
+            elapsed: None,
+            duration: Some(Duration::from_secs_f64(201.5)),
+            disc_number: 1,
+            track_number: track,
+            queue_id: None,
+            priority: 0,
+        }
+    }
+
+    fn library() -> LazyLibrary {
+        let albums = vec![
+            Album::new("Zebra".to_string(), vec![song("a/z/1.flac", "Zebra", 1)]),
+            Album::new(
+                "Apple".to_string(),
+                vec![
+                    song("a/a/1.flac", "Apple", 1),
+                    song("a/a/2.flac", "Apple", 2),
+                ],
+            ),
+        ];
+        LazyLibrary {
+            artists: vec![LazyArtist {
+                albums: ArtistData::Loaded(albums),
+                ..LazyArtist::new("Artist".to_string())
+            }],
+            all_albums: Vec::new(),
+            all_albums_complete: false,
+            all_albums_sorted: false,
+            db_update: None,
+            level: TagLevel::default(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let bytes = serde_json::to_vec(&library().to_snapshot("localhost", 1700000000)).unwrap();
+        let (db_update, loaded) = parse(&bytes, "localhost").unwrap();
+        assert_eq!(db_update, 1700000000);
+        assert!(loaded.all_albums_complete);
+        assert!(loaded.artists[0].is_loaded());
+
+        // all_albums is rebuilt and sorted by album name
+        let names: Vec<&str> = loaded
+            .all_albums
+            .iter()
+            .map(|(_, album)| album.name.as_str())
+            .collect();
+        assert_eq!(names, ["Apple", "Zebra"]);
+
+        let track = &loaded.all_albums[0].1.tracks[1];
+        assert_eq!(track.album, "Apple");
+        assert_eq!(track.file_path, PathBuf::from("a/a/2.flac"));
+        assert_eq!(track.duration, Some(Duration::from_secs_f64(201.5)));
+        assert_eq!(track.track_number, 2);
+    }
+
+    #[test]
+    fn test_other_host_or_version_is_ignored() {
+        let mut snapshot = library().to_snapshot("localhost", 1);
+        let bytes = serde_json::to_vec(&snapshot).unwrap();
+        assert!(parse(&bytes, "music.lan").is_none());
+
+        snapshot.version = FORMAT_VERSION + 1;
+        let bytes = serde_json::to_vec(&snapshot).unwrap();
+        assert!(parse(&bytes, "localhost").is_none());
+        assert!(parse(b"not json", "localhost").is_none());
+    }
+
+    #[test]
+    fn test_snapshot_file_name() {
+        assert_eq!(snapshot_file_name("localhost"), "localhost.json");
+        assert_eq!(
+            snapshot_file_name("/run/mpd/socket"),
+            "_run_mpd_socket.json"
+        );
+    }
+}
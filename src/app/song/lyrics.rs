@@ -0,0 +1,326 @@
+use mpd_client::{
+    client::{Client, CommandError},
+    protocol::command::Command as RawCommand,
+};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Comment keys holding embedded lyrics, in order of preference
+const LYRICS_KEYS: &[&str] = &[
+    "SYNCEDLYRICS",
+    "LYRICS",
+    "UNSYNCEDLYRICS",
+    "UNSYNCED LYRICS",
+];
+
+/// A line of synced lyrics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LyricLine {
+    /// When the line starts, from the beginning of the song
+    pub time: Duration,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lyrics {
+    /// Timestamped lines (LRC), sorted by time
+    Synced(Vec<LyricLine>),
+    /// Plain text without timestamps
+    Plain(Vec<String>),
+}
+
+impl Lyrics {
+    /// Parse LRC or plain text; None if there is no text at all
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut offset_ms: i64 = 0;
+        let mut synced = Vec::new();
+        let mut plain = Vec::new();
+
+        for line in text.lines() {
+            let (tags, rest) = split_tags(line.trim());
+            let mut times = Vec::new();
+            for tag in &tags {
+                if let Some(time) = parse_timestamp(tag) {
+                    times.push(time);
+                } else if let Some(value) = tag.strip_prefix("offset:") {
+                    offset_ms = value.trim().parse().unwrap_or(0);
+                }
+            }
+
+            if !times.is_empty() {
+                let text = strip_word_timestamps(rest);
+                synced.extend(times.into_iter().map(|time| (time, text.clone())));
+            } else if tags.is_empty() {
+                // Lines made only of tags ([ar:...], [ti:...]) are metadata
+                plain.push(rest.to_string());
+            }
+        }
+
+        if !synced.is_empty() {
+            // A positive offset shows the lines earlier
+            let mut lines: Vec<LyricLine> = synced
+                .into_iter()
+                .map(|(time, text)| LyricLine {
+                    time: shift(time, offset_ms),
+                    text,
+                })
+                .collect();
+            lines.sort_by_key(|line| line.time);
+            return Some(Lyrics::Synced(lines));
+        }
+
+        // Drop leading and trailing blank lines
+        let first = plain.iter().position(|line| !line.trim().is_empty())?;
+        let last = plain.iter().rposition(|line| !line.trim().is_empty())?;
+        Some(Lyrics::Plain(plain[first..=last].to_vec()))
+    }
+
+    pub fn is_synced(&self) -> bool {
+        matches!(self, Lyrics::Synced(_))
+    }
+
+    /// Number of lines to display
+    pub fn line_count(&self) -> usize {
+        match self {
+            Lyrics::Synced(lines) => lines.len(),
+            Lyrics::Plain(lines) => lines.len(),
+        }
+    }
+
+    /// Text of the line at `index`
+    pub fn line(&self, index: usize) -> &str {
+        match self {
+            Lyrics::Synced(lines) => &lines[index].text,
+            Lyrics::Plain(lines) => &lines[index],
+        }
+    }
+
+    /// Index of the line being sung at `elapsed` (None before the first line, or unsynced)
+    pub fn current_line(&self, elapsed: Duration) -> Option<usize> {
+        match self {
+            Lyrics::Synced(lines) => lines
+                .partition_point(|line| line.time <= elapsed)
+                .checked_sub(1),
+            Lyrics::Plain(_) => None,
+        }
+    }
+}
+
+/// Lyrics pane state: lyrics of the current song and whether the pane is shown
+#[derive(Debug, Default)]
+pub struct LyricsState {
+    /// Whether the lyrics pane is shown (toggled with `toggle_lyrics`)
+    pub visible: bool,
+    /// Song the lyrics were requested for
+    pub file_path: Option<PathBuf>,
+    /// Lyrics of that song, None while loading or if it has none
+    pub lyrics: Option<Lyrics>,
+}
+
+impl LyricsState {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            ..Default::default()
+        }
+    }
+
+    /// Lyrics to render, if the pane is shown and there are any
+    pub fn shown(&self) -> Option<&Lyrics> {
+        self.lyrics.as_ref().filter(|_| self.visible)
+    }
+
+    /// Forget the lyrics of the previous song when `current` changed.
+    /// Returns the song whose lyrics should be loaded now.
+    pub fn start_load(&mut self, current: Option<&Path>) -> Option<PathBuf> {
+        if !self.visible || self.file_path.as_deref() == current {
+            return None;
+        }
+        self.file_path = current.map(Path::to_path_buf);
+        self.lyrics = None;
+        self.file_path.clone()
+    }
+}
+
+/// Load lyrics for a song: a `.lrc` file next to it in the music directory first,
+/// then lyrics embedded in its tags.
+pub async fn load_lyrics(
+    client: &Client,
+    music_directory: Option<&Path>,
+    file_path: &Path,
+) -> Option<Lyrics> {
+    let uri = file_path.to_string_lossy();
+    // Streams have neither sidecar files nor readable tags
+    if uri.contains("://") {
+        return None;
+    }
+
+    if let Some(music_directory) = music_directory {
+        let sidecar = music_directory.join(file_path).with_extension("lrc");
+        if let Ok(text) = std::fs::read_to_string(&sidecar) {
+            log::debug!("Loaded lyrics from {:?}", sidecar);
+            if let Some(lyrics) = Lyrics::parse(&text) {
+                return Some(lyrics);
+            }
+        }
+    }
+
+    match read_embedded_lyrics(client, &uri).await {
+        Ok(text) => text.as_deref().and_then(Lyrics::parse),
+        Err(e) => {
+            log::debug!("Failed to read comments of {}: {}", uri, e);
+            None
+        }
+    }
+}
+
+/// Lyrics from the song's tags.
+/// MPD command: readcomments "uri"
+async fn read_embedded_lyrics(client: &Client, uri: &str) -> Result<Option<String>, CommandError> {
+    let frame = client
+        .raw_command(RawCommand::new("readcomments").argument(uri))
+        .await?;
+    let comments: Vec<(&str, &str)> = (&frame).into_iter().collect();
+    Ok(pick_lyrics_comment(comments))
+}
+
+/// Value of the preferred lyrics comment, keys compared case-insensitively
+fn pick_lyrics_comment<'a>(
+    comments: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<String> {
+    let comments: Vec<_> = comments.into_iter().collect();
+    LYRICS_KEYS.iter().find_map(|wanted| {
+        comments
+            .iter()
+            .find(|(key, value)| key.eq_ignore_ascii_case(wanted) && !value.trim().is_empty())
+            .map(|(_, value)| value.to_string())
+    })
+}
+
+/// Split the leading `[...]` tags off a line
+fn split_tags(mut line: &str) -> (Vec<&str>, &str) {
+    let mut tags = Vec::new();
+    while let Some(rest) = line.strip_prefix('[') {
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+        tags.push(&rest[..end]);
+        line = &rest[end + 1..];
+    }
+    (tags, line.trim())
+}
+
+/// `mm:ss`, `mm:ss.xx` or `mm:ss:xx`
+fn parse_timestamp(tag: &str) -> Option<Duration> {
+    let (minutes, rest) = tag.split_once(':')?;
+    let (seconds, fraction) = rest.split_once(['.', ':']).unwrap_or((rest, ""));
+    let minutes: u64 = minutes.trim().parse().ok()?;
+    let seconds: u64 = seconds.trim().parse().ok()?;
+    if seconds >= 60 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // Hundredths are the usual precision, but take whatever is there as a decimal fraction
+    let millis = format!("{:0<3}", fraction.get(..3).unwrap_or(fraction))
+        .parse::<u64>()
+        .ok()?;
+    Some(Duration::from_millis(
+        (minutes * 60 + seconds) * 1000 + millis,
+    ))
+}
+
+/// Remove enhanced LRC word timings (`<mm:ss.xx>`)
+fn strip_word_timestamps(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        if parse_timestamp(&rest[start + 1..start + end]).is_none() {
+            result.push_str(&rest[..start + end + 1]);
+        } else {
+            result.push_str(&rest[..start]);
+        }
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn shift(time: Duration, offset_ms: i64) -> Duration {
+    let offset = Duration::from_millis(offset_ms.unsigned_abs());
+    if offset_ms >= 0 {
+        time.saturating_sub(offset)
+    } else {
+        time + offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_parse_lrc() {
+        let text = "[ar:Someone]\n[ti:Song]\n[offset:+500]\n\
+                    [00:12.00]First line\n\
+                    [00:05.50][01:02.00]Chorus\n\
+                    [00:20:30]<00:20.30>Word <00:21.00>timed\n";
+        let lyrics = Lyrics::parse(text).unwrap();
+        let Lyrics::Synced(lines) = &lyrics else {
+            panic!("expected synced lyrics");
+        };
+        let parsed: Vec<_> = lines.iter().map(|l| (l.time, l.text.as_str())).collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (ms(5000), "Chorus"),
+                (ms(11500), "First line"),
+                (ms(19800), "Word timed"),
+                (ms(61500), "Chorus"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_current_line() {
+        let lyrics = Lyrics::parse("[00:01.00]a\n[00:03.00]b\n[00:05.00]c").unwrap();
+        assert_eq!(lyrics.current_line(ms(500)), None);
+        assert_eq!(lyrics.current_line(ms(1000)), Some(0));
+        assert_eq!(lyrics.current_line(ms(4999)), Some(1));
+        assert_eq!(lyrics.current_line(ms(90000)), Some(2));
+    }
+
+    #[test]
+    fn test_plain_fallback() {
+        let lyrics = Lyrics::parse("\n[ar:Someone]\nFirst\n\nSecond\n\n").unwrap();
+        assert_eq!(
+            lyrics,
+            Lyrics::Plain(vec![
+                "First".to_string(),
+                String::new(),
+                "Second".to_string()
+            ])
+        );
+        assert_eq!(lyrics.current_line(ms(1000)), None);
+        assert_eq!(Lyrics::parse("  \n\n"), None);
+    }
+
+    #[test]
+    fn test_pick_lyrics_comment() {
+        let comments = [
+            ("TITLE", "Song"),
+            ("unsyncedlyrics", "plain"),
+            ("Lyrics", "[00:01.00]synced"),
+        ];
+        assert_eq!(
+            pick_lyrics_comment(comments).as_deref(),
+            Some("[00:01.00]synced")
+        );
+        assert_eq!(pick_lyrics_comment([("TITLE", "Song")]), None);
+    }
+}
@@ -1,10 +1,21 @@
 pub mod album;
 pub mod artist;
+pub mod browse;
 pub mod library;
+pub mod library_cache;
+pub mod lyrics;
+pub mod playlists;
+pub mod search;
 pub mod song_info;
+pub mod tag_view;
 
 // Convenience re-exports
 pub use album::Album;
 pub use artist::{Artist, LazyArtist};
+pub use browse::BrowseState;
 pub use library::LazyLibrary;
+pub use lyrics::LyricsState;
+pub use playlists::PlaylistsState;
+pub use search::SearchState;
 pub use song_info::SongInfo;
+pub use tag_view::TagViewState;
@@ -0,0 +1,150 @@
+use crate::app::SongInfo;
+use mpd_client::{
+    client::{Client, CommandError},
+    commands,
+};
+use ratatui::widgets::ListState;
+
+/// Prompt shown over the current view while managing stored playlists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaylistPrompt {
+    /// Name to save the current queue under
+    SaveQueue { input: String },
+    /// New name for an existing playlist
+    Rename { from: String, input: String },
+    /// Confirmation before deleting a playlist
+    ConfirmDelete { name: String },
+    /// Pick the playlist to add songs to (entry 0 creates a new playlist)
+    AddTo { uris: Vec<String>, selected: usize },
+    /// Name of a new playlist to add songs to
+    AddToNew { uris: Vec<String>, input: String },
+}
+
+impl PlaylistPrompt {
+    /// Text being typed, for prompts that take text input
+    pub fn input_mut(&mut self) -> Option<&mut String> {
+        match self {
+            PlaylistPrompt::SaveQueue { input }
+            | PlaylistPrompt::Rename { input, .. }
+            | PlaylistPrompt::AddToNew { input, .. } => Some(input),
+            PlaylistPrompt::ConfirmDelete { .. } | PlaylistPrompt::AddTo { .. } => None,
+        }
+    }
+}
+
+/// Playlists view state: stored playlist names and the contents of the selected one
+#[derive(Debug, Default)]
+pub struct PlaylistsState {
+    /// Stored playlist names, sorted case-insensitively
+    pub names: Vec<String>,
+    /// Songs of the selected playlist, in playlist order
+    pub contents: Vec<SongInfo>,
+    /// Selection in the playlist list
+    pub list_state: ListState,
+    /// Selection in the contents of the selected playlist
+    pub tracks_list_state: ListState,
+    /// Open save/rename/delete/add prompt, if any
+    pub prompt: Option<PlaylistPrompt>,
+}
+
+impl PlaylistsState {
+    /// Name of the selected playlist
+    pub fn selected_name(&self) -> Option<&str> {
+        self.list_state
+            .selected()
+            .and_then(|idx| self.names.get(idx))
+            .map(String::as_str)
+    }
+
+    /// Replace the playlist names, keeping the selection on the same playlist if it still exists
+    pub fn set_names(&mut self, mut names: Vec<String>) {
+        names.sort_by_key(|name| name.to_lowercase());
+        let previous = self.selected_name().map(str::to_string);
+        let selected = previous
+            .and_then(|prev| names.iter().position(|name| *name == prev))
+            .or_else(|| {
+                let last = names.len().checked_sub(1)?;
+                Some(self.list_state.selected().unwrap_or(0).min(last))
+            });
+        self.names = names;
+        self.list_state.select(selected);
+    }
+
+    /// Replace the contents of the selected playlist, clamping the track selection
+    pub fn set_contents(&mut self, contents: Vec<SongInfo>) {
+        let selected = contents
+            .len()
+            .checked_sub(1)
+            .map(|last| self.tracks_list_state.selected().unwrap_or(0).min(last));
+        self.contents = contents;
+        self.tracks_list_state.select(selected);
+    }
+}
+
+/// Names of all stored playlists.
+/// MPD command: listplaylists
+pub async fn fetch_playlist_names(client: &Client) -> Result<Vec<String>, CommandError> {
+    let playlists = client.command(commands::GetPlaylists).await?;
+    Ok(playlists.into_iter().map(|p| p.name).collect())
+}
+
+/// Songs of a stored playlist.
+/// MPD command: listplaylistinfo "name"
+pub async fn fetch_playlist_contents(
+    client: &Client,
+    name: &str,
+) -> Result<Vec<SongInfo>, CommandError> {
+    let songs = client.command(commands::GetPlaylist(name)).await?;
+    Ok(songs.iter().map(SongInfo::from_song).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_set_names_sorts_and_keeps_selection() {
+        let mut state = PlaylistsState::default();
+        state.set_names(names(&["rock", "Ambient", "jazz"]));
+        assert_eq!(state.names, names(&["Ambient", "jazz", "rock"]));
+        assert_eq!(state.selected_name(), Some("Ambient"));
+
+        state.list_state.select(Some(2));
+        state.set_names(names(&["rock", "Chill", "Ambient", "jazz"]));
+        assert_eq!(state.selected_name(), Some("rock"));
+    }
+
+    #[test]
+    fn test_set_names_clamps_when_selected_is_removed() {
+        let mut state = PlaylistsState::default();
+        state.set_names(names(&["a", "b", "c"]));
+        state.list_state.select(Some(2));
+        state.set_names(names(&["a", "b"]));
+        assert_eq!(state.selected_name(), Some("b"));
+
+        state.set_names(Vec::new());
+        assert_eq!(state.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_prompt_input() {
+        let mut prompt = PlaylistPrompt::SaveQueue {
+            input: String::new(),
+        };
+        prompt.input_mut().unwrap().push_str("mix");
+        assert_eq!(
+            prompt,
+            PlaylistPrompt::SaveQueue {
+                input: "mix".to_string()
+            }
+        );
+        let mut confirm = PlaylistPrompt::ConfirmDelete {
+            name: "mix".to_string(),
+        };
+        assert!(confirm.input_mut().is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::app::{SongInfo, song::Album};
+use mpd_client::{
+    client::Client,
+    commands::{self, Command},
+    filter::{Filter, Operator},
+    protocol::{command::Command as RawCommand, response::Frame},
+    responses::{Song, TypedResponseError},
+    tag::Tag,
+};
+use ratatui::widgets::ListState;
+
+/// Fields searched by free text (terms without a `field:` prefix)
+const FREE_TEXT_FIELDS: &[&str] = &["title", "artist", "album", "albumartist", "genre", "file"];
+
+/// Search view state: query input and grouped results
+#[derive(Debug, Default)]
+pub struct SearchState {
+    /// Text typed into the search box
+    pub query: String,
+    /// Whether key presses go to the search box
+    pub input_active: bool,
+    /// Results grouped like the Albums view: (album_artist, Album)
+    pub results: Vec<(String, Album)>,
+    /// Number of songs in the last result set
+    pub song_count: usize,
+    /// Selection in the results album list
+    pub album_list_state: ListState,
+    /// Selection in the tracks of the selected result album
+    pub tracks_list_state: ListState,
+}
+
+/// A search query split into field-restricted terms and free text.
+///
+/// `artist:miles album:"kind of blue" so what` restricts artist and album,
+/// and matches "so what" against any of the searchable fields.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// (field, value) pairs that must all match
+    pub fields: Vec<(String, String)>,
+    /// Text that must match at least one searchable field
+    pub free_text: Option<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut fields = Vec::new();
+        let mut free_words = Vec::new();
+
+        for token in tokenize(input) {
+            match token.split_once(':') {
+                Some((field, value))
+                    if FREE_TEXT_FIELDS.contains(&field.to_lowercase().as_str())
+                        && !value.is_empty() =>
+                {
+                    fields.push((field.to_lowercase(), value.to_string()));
+                }
+                _ => free_words.push(token),
+            }
+        }
+
+        let free_text = if free_words.is_empty() {
+            None
+        } else {
+            Some(free_words.join(" "))
+        };
+
+        Self { fields, free_text }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.free_text.is_none()
+    }
+
+    /// Build the MPD filters for this query.
+    /// Free text needs one filter per searchable field since filters can't express OR;
+    /// they are sent as one command list and the caller merges the results.
+    pub fn filters(&self) -> Vec<Filter> {
+        let base = self
+            .fields
+            .iter()
+            .map(|(field, value)| field_filter(field, value))
+            .reduce(Filter::and);
+
+        match &self.free_text {
+            Some(text) => FREE_TEXT_FIELDS
+                .iter()
+                .map(|field| {
+                    let filter = field_filter(field, text);
+                    match &base {
+                        Some(base) => base.clone().and(filter),
+                        None => filter,
+                    }
+                })
+                .collect(),
+            None => base.into_iter().collect(),
+        }
+    }
+}
+
+/// Split input on whitespace, keeping double-quoted sections together
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Substring filter on a single field, case-insensitive when sent with `search`
+fn field_filter(field: &str, value: &str) -> Filter {
+    let tag = match field {
+        "title" => Tag::Title,
+        "artist" => Tag::Artist,
+        "album" => Tag::Album,
+        "albumartist" => Tag::AlbumArtist,
+        "genre" => Tag::Genre,
+        other => Tag::Other(other.into()),
+    };
+    Filter::new(tag, Operator::Contain, value)
+}
+
+/// MPD's `search`: like `find`, but compares case-insensitively
+struct Search(Filter);
+
+impl Command for Search {
+    type Response = Vec<Song>;
+
+    fn command(&self) -> RawCommand {
+        RawCommand::new("search").argument(&self.0)
+    }
+
+    fn response(self, frame: Frame) -> Result<Self::Response, TypedResponseError> {
+        // Same response as find
+        commands::Find::new(self.0).response(frame)
+    }
+}
+
+/// Run a search against the MPD database and group the matches by album.
+/// MPD command: search "((title contains '...') AND ...)" for each filter, in one command list
+pub async fn search_library(
+    client: &Client,
+    query: &SearchQuery,
+) -> color_eyre::Result<Vec<(String, Album)>> {
+    let start_time = std::time::Instant::now();
+    let mut songs: Vec<Song> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    let searches: Vec<Search> = query.filters().into_iter().map(Search).collect();
+    if !searches.is_empty() {
+        let found = client
+            .command_list(searches)
+            .await
+            .map_err(|e| color_eyre::eyre::eyre!("Search failed: {}", e))?;
+
+        for song in found.into_iter().flatten() {
+            if seen.insert(song.url.clone()) {
+                songs.push(song);
+            }
+        }
+    }
+
+    let results = group_by_album(songs);
+
+    log::debug!(
+        "Search {:?} returned {} albums in {:?}",
+        query,
+        results.len(),
+        start_time.elapsed()
+    );
+
+    Ok(results)
+}
+
+/// Group songs into (album_artist, Album) pairs sorted the same way as the Albums view
+fn group_by_album(songs: Vec<Song>) -> Vec<(String, Album)> {
+    let mut grouped: std::collections::HashMap<(String, String), Vec<SongInfo>> =
+        std::collections::HashMap::new();
+
+    for song in songs {
+        let song_info = SongInfo::from_song(&song);
+        // Use album artist for grouping (fall back to artist if not set)
+        let artist_name = song
+            .album_artists()
+            .first()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| song_info.artist.clone());
+        grouped
+            .entry((artist_name, song_info.album.clone()))
+            .or_default()
+            .push(song_info);
+    }
+
+    let mut results: Vec<(String, Album)> = grouped
+        .into_iter()
+        .map(|((artist_name, album_name), mut tracks)| {
+            tracks.sort_by(|a, b| {
+                a.disc_number
+                    .cmp(&b.disc_number)
+                    .then(a.track_number.cmp(&b.track_number))
+                    .then(a.title.cmp(&b.title))
+            });
+            (artist_name, Album::new(album_name, tracks))
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        a.1.name
+            .to_lowercase()
+            .cmp(&b.1.name.to_lowercase())
+            .then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase()))
+    });
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_free_text() {
+        let query = SearchQuery::parse("  kind of   blue ");
+        assert!(query.fields.is_empty());
+        assert_eq!(query.free_text.as_deref(), Some("kind of blue"));
+    }
+
+    #[test]
+    fn test_parse_fields_and_quotes() {
+        let query = SearchQuery::parse(r#"Artist:miles album:"kind of blue" so what"#);
+        assert_eq!(
+            query.fields,
+            vec![
+                ("artist".to_string(), "miles".to_string()),
+                ("album".to_string(), "kind of blue".to_string()),
+            ]
+        );
+        assert_eq!(query.free_text.as_deref(), Some("so what"));
+    }
+
+    #[test]
+    fn test_parse_unknown_field_is_free_text() {
+        let query = SearchQuery::parse("composer:bach");
+        assert!(query.fields.is_empty());
+        assert_eq!(query.free_text.as_deref(), Some("composer:bach"));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert!(SearchQuery::parse("   ").is_empty());
+        assert!(SearchQuery::parse("artist:").fields.is_empty());
+    }
+
+    #[test]
+    fn test_filter_count() {
+        assert_eq!(SearchQuery::parse("artist:a").filters().len(), 1);
+        assert_eq!(
+            SearchQuery::parse("artist:a blue").filters().len(),
+            FREE_TEXT_FIELDS.len()
+        );
+        assert!(SearchQuery::parse("").filters().is_empty());
+    }
+}
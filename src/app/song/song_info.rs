@@ -2,10 +2,17 @@ use mpd_client::{
     Client,
     client::CommandError,
     commands::SetBinaryLimit,
-    responses::{PlayState, Song},
+    responses::{PlayState, Song, SongInQueue},
 };
 use std::path::PathBuf;
 
+/// Title shown for songs without a title tag
+pub const UNKNOWN_TITLE: &str = "Unknown Title";
+/// Artist shown for songs without an artist tag
+pub const UNKNOWN_ARTIST: &str = "Unknown Artist";
+/// Album shown for songs without an album tag
+pub const UNKNOWN_ALBUM: &str = "Unknown Album";
+
 #[derive(Debug, Clone)]
 pub struct SongInfo {
     pub title: String,
@@ -19,6 +26,10 @@ pub struct SongInfo {
     pub duration: Option<std::time::Duration>,
     pub disc_number: u64,
     pub track_number: u64,
+    /// ID of the queue entry, for songs loaded from the queue
+    pub queue_id: Option<u64>,
+    /// Queue priority, which MPD plays higher first in random mode
+    pub priority: u8,
 }
 
 impl SongInfo {
@@ -51,17 +62,17 @@ impl SongInfo {
         let title = song
             .title()
             .map(Self::sanitize_string)
-            .unwrap_or_else(|| "Unknown Title".to_string());
+            .unwrap_or_else(|| UNKNOWN_TITLE.to_string());
         let artist = song
             .artists()
             .first()
             .map(|s| Self::sanitize_string(s))
-            .unwrap_or_else(|| "Unknown Artist".to_string());
+            .unwrap_or_else(|| UNKNOWN_ARTIST.to_string());
 
         let album = song
             .album()
             .map(Self::sanitize_string)
-            .unwrap_or_else(|| "Unknown Album".to_string());
+            .unwrap_or_else(|| UNKNOWN_ALBUM.to_string());
 
         let file_path = song.file_path().to_path_buf();
         let format = song.format.clone();
@@ -80,6 +91,17 @@ impl SongInfo {
             duration,
             disc_number,
             track_number,
+            queue_id: None,
+            priority: 0,
+        }
+    }
+
+    /// Build a `SongInfo` for a queue entry, keeping its ID and priority
+    pub fn from_queue_song(song_in_queue: &SongInQueue) -> Self {
+        Self {
+            queue_id: Some(song_in_queue.id.0),
+            priority: song_in_queue.priority,
+            ..Self::from_song(&song_in_queue.song)
         }
     }
     pub async fn set_max_art_size(client: &Client, size_bytes: usize) -> Result<(), CommandError> {
@@ -0,0 +1,168 @@
+use crate::app::LazyLibrary;
+use crate::app::ListState;
+use crate::app::config::browse::TagView;
+use mpd_client::{
+    filter::{Filter, Operator},
+    tag::Tag,
+};
+use std::collections::HashSet;
+
+/// Group name of songs without the grouping tag
+pub const NO_TAG_VALUE: &str = "(none)";
+
+/// One level of a tag view: the values of `entry` listed in the Artists panel, within
+/// the values picked on the levels above
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagLevel {
+    /// Tag listed in the Artists panel
+    pub entry: Tag,
+    /// Tag the songs of an entry are grouped by in the Albums panel.
+    /// None when the entries open the next level instead.
+    pub group: Option<Tag>,
+    /// Tag values picked on the levels above
+    pub scope: Vec<(Tag, String)>,
+}
+
+impl Default for TagLevel {
+    /// AlbumArtist → Album
+    fn default() -> Self {
+        Self {
+            entry: Tag::AlbumArtist,
+            group: Some(Tag::Album),
+            scope: Vec::new(),
+        }
+    }
+}
+
+impl TagLevel {
+    /// Level of a view with `tags` below the values picked in `scope`, if there is one.
+    /// All but the last tag are listed in the Artists panel and the last one groups the
+    /// songs; a single tag groups its songs by album.
+    pub fn of_view(tags: &[Tag], scope: Vec<(Tag, String)>) -> Option<Self> {
+        let depth = scope.len();
+        let (entry, group) = match tags {
+            [tag] if depth == 0 => (tag.clone(), Some(Tag::Album)),
+            _ if depth + 2 < tags.len() => (tags[depth].clone(), None),
+            _ if depth + 2 == tags.len() => (tags[depth].clone(), tags.last().cloned()),
+            _ => return None,
+        };
+        Some(Self {
+            entry,
+            group,
+            scope,
+        })
+    }
+
+    /// Songs within the scope, or None at the top level
+    pub fn scope_filter(&self) -> Option<Filter> {
+        self.scope
+            .iter()
+            .map(|(tag, value)| Filter::new(tag.clone(), Operator::Equal, value.clone()))
+            .reduce(Filter::and)
+    }
+
+    /// Songs of the entry `value` within the scope
+    pub fn entry_filter(&self, value: &str) -> Filter {
+        let filter = Filter::new(self.entry.clone(), Operator::Equal, value.to_string());
+        match self.scope_filter() {
+            Some(scope) => scope.and(filter),
+            None => filter,
+        }
+    }
+
+    /// The level below, opened on the entry `value`, or None if the entries group songs
+    pub fn open(&self, tags: &[Tag], value: &str) -> Option<Self> {
+        if self.group.is_some() {
+            return None;
+        }
+        let mut scope = self.scope.clone();
+        scope.push((self.entry.clone(), value.to_string()));
+        Self::of_view(tags, scope)
+    }
+
+    /// Picked values, e.g. "Jazz › Miles Davis"
+    pub fn breadcrumb(&self) -> String {
+        self.scope
+            .iter()
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>()
+            .join(" › ")
+    }
+}
+
+/// Panel selections of the Artists view, kept while another view is shown there
+#[derive(Debug, Default)]
+pub struct ArtistsPanels {
+    pub artist_list_state: ListState,
+    pub album_list_state: ListState,
+    pub album_display_list_state: ListState,
+    pub expanded_albums: HashSet<(String, String)>,
+}
+
+/// A `[browse]` view other than AlbumArtist → Album, open in the Artists view
+#[derive(Debug)]
+pub struct TagViewState {
+    /// Index in `[browse] views`
+    pub view: usize,
+    /// Entries of the current level, loaded lazily like the default library
+    pub library: LazyLibrary,
+    /// Levels above the current one, with the row and offset of the opened entry
+    pub parents: Vec<(LazyLibrary, usize, usize)>,
+    /// Panels of the default library, put back when the view is closed
+    pub default_panels: ArtistsPanels,
+}
+
+impl TagViewState {
+    /// Titles of the Artists and Albums panels: the view's name with the values opened
+    /// so far, and the tag the songs are grouped by
+    pub fn panel_titles(&self, views: &[TagView]) -> (String, String) {
+        let Some(view) = views.get(self.view) else {
+            return ("Artists".to_string(), "Albums".to_string());
+        };
+        let breadcrumb = self.library.level.breadcrumb();
+        let entries = if breadcrumb.is_empty() {
+            view.name.clone()
+        } else {
+            format!("{} › {}", view.name, breadcrumb)
+        };
+        let groups = match view.tags.as_slice() {
+            [_] => "Albums".to_string(),
+            tags => tags.last().cloned().unwrap_or_default(),
+        };
+        (entries, groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_of_view() {
+        let tags = [Tag::Genre, Tag::AlbumArtist, Tag::Album];
+        let top = TagLevel::of_view(&tags, Vec::new()).unwrap();
+        assert_eq!(top.entry, Tag::Genre);
+        assert_eq!(top.group, None);
+
+        let artists = top.open(&tags, "Jazz").unwrap();
+        assert_eq!(artists.entry, Tag::AlbumArtist);
+        assert_eq!(artists.group, Some(Tag::Album));
+        assert_eq!(artists.scope, [(Tag::Genre, "Jazz".to_string())]);
+        assert_eq!(
+            artists.entry_filter("Miles Davis"),
+            Filter::new(Tag::Genre, Operator::Equal, "Jazz").and(Filter::new(
+                Tag::AlbumArtist,
+                Operator::Equal,
+                "Miles Davis"
+            ))
+        );
+        // The last level groups songs instead of opening
+        assert_eq!(artists.open(&tags, "Miles Davis"), None);
+
+        // A single tag is grouped by album
+        let year = TagLevel::of_view(&[Tag::Date], Vec::new()).unwrap();
+        assert_eq!(year.group, Some(Tag::Album));
+        assert_eq!(year.open(&[Tag::Date], "1959"), None);
+        assert_eq!(TagLevel::of_view(&[], Vec::new()), None);
+    }
+}
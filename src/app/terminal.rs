@@ -1,4 +1,5 @@
 use crossterm::{
+    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -7,7 +8,12 @@ use ratatui::DefaultTerminal;
 /// Initialize the terminal for the application
 pub fn init_terminal() -> color_eyre::Result<DefaultTerminal> {
     // Initialize terminal with explicit crossterm configuration for full control
-    execute!(std::io::stdout(), EnterAlternateScreen)?;
+    execute!(
+        std::io::stdout(),
+        EnterAlternateScreen,
+        EnableFocusChange,
+        EnableMouseCapture
+    )?;
     enable_raw_mode()?;
 
     let terminal =
@@ -19,7 +25,12 @@ pub fn init_terminal() -> color_eyre::Result<DefaultTerminal> {
 /// Restore the terminal to its original state
 pub fn restore_terminal() -> color_eyre::Result<()> {
     // Restore terminal
-    execute!(std::io::stdout(), LeaveAlternateScreen)?;
+    execute!(
+        std::io::stdout(),
+        DisableMouseCapture,
+        DisableFocusChange,
+        LeaveAlternateScreen
+    )?;
     disable_raw_mode()?;
     Ok(())
 }
@@ -5,6 +5,7 @@
 //! to provide instant cover art display when tracks change.
 
 use crate::app::SongInfo;
+use crate::app::ui::cover_palette::CoverPalette;
 use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -22,6 +23,8 @@ const PREFETCH_BEHIND: usize = 1;
 pub struct CachedCover {
     /// Raw image bytes (None means no cover art available)
     pub data: Option<Vec<u8>>,
+    /// Colors of the cover for the dynamic theme, once they were extracted
+    pub palette: Option<CoverPalette>,
 }
 
 /// Thread-safe cover art cache with LRU eviction
@@ -78,7 +81,13 @@ impl CoverArtCache {
         if self.entries.contains_key(&path) {
             self.lru_order.retain(|p| p != &path);
             self.lru_order.push_back(path.clone());
-            self.entries.insert(path, CachedCover { data });
+            self.entries.insert(
+                path,
+                CachedCover {
+                    data,
+                    palette: None,
+                },
+            );
             return;
         }
 
@@ -94,7 +103,25 @@ impl CoverArtCache {
 
         // Insert new entry
         self.lru_order.push_back(path.clone());
-        self.entries.insert(path, CachedCover { data });
+        self.entries.insert(
+            path,
+            CachedCover {
+                data,
+                palette: None,
+            },
+        );
+    }
+
+    /// Extracted cover colors of a cached entry (without updating LRU or stats)
+    pub fn palette(&self, path: &PathBuf) -> Option<&CoverPalette> {
+        self.entries.get(path)?.palette.as_ref()
+    }
+
+    /// Remember the extracted cover colors of a cached entry
+    pub fn set_palette(&mut self, path: &PathBuf, palette: CoverPalette) {
+        if let Some(entry) = self.entries.get_mut(path) {
+            entry.palette = Some(palette);
+        }
     }
 
     /// Mark a path as currently being fetched
@@ -129,7 +156,6 @@ impl CoverArtCache {
     }
 
     /// Clear the cache
-    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.entries.clear();
         self.lru_order.clear();
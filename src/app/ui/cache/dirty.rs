@@ -152,6 +152,12 @@ impl DirtyFlags {
         }
     }
 
+    /// Terminal height at the last size check
+    #[inline]
+    pub fn terminal_height(&self) -> u16 {
+        self.last_height.get()
+    }
+
     // ----- Query methods (check if dirty) -----
 
     /// Check if queue needs redraw
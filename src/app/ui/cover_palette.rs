@@ -0,0 +1,316 @@
+//! Colors picked from the album cover for the dynamic theme.
+//!
+//! The cover is shrunk and its pixels are grouped with k-means. The groups are then
+//! assigned to the color roles, lightened or darkened until text stays readable on a
+//! dark terminal background.
+
+use ratatui::style::Color;
+
+use crate::app::config::theme::{ColorRole, Palette};
+
+/// Number of colors the cover is reduced to
+const CLUSTERS: usize = 6;
+const ITERATIONS: usize = 10;
+/// The cover is shrunk to at most this many pixels per side before clustering
+const SAMPLE_SIZE: u32 = 48;
+
+/// WCAG contrast ratio for text against the background
+const TEXT_CONTRAST: f32 = 4.5;
+/// WCAG contrast ratio for borders and bars
+const GRAPHIC_CONTRAST: f32 = 3.0;
+/// The bundled themes are made for dark terminals, so the dynamic one is too
+const BACKGROUND: Rgb = [0.0, 0.0, 0.0];
+/// Colors closer than this are treated as the same swatch
+const MIN_DISTANCE: f32 = 48.0;
+
+type Rgb = [f32; 3];
+
+/// Main colors of a cover, most common first
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverPalette {
+    swatches: Vec<Swatch>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Swatch {
+    rgb: Rgb,
+    /// Share of the cover's pixels
+    population: f32,
+}
+
+impl CoverPalette {
+    /// Cluster the pixels of a decoded cover. None for an empty image.
+    pub fn extract(image: &image::DynamicImage) -> Option<Self> {
+        let sample = image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8();
+        let pixels: Vec<Rgb> = sample
+            .pixels()
+            .map(|pixel| pixel.0.map(|channel| channel as f32))
+            .collect();
+        Self::from_pixels(&pixels)
+    }
+
+    fn from_pixels(pixels: &[Rgb]) -> Option<Self> {
+        if pixels.is_empty() {
+            return None;
+        }
+
+        // Start from evenly spaced brightness levels, which keeps the result stable
+        let mut by_luminance = pixels.to_vec();
+        by_luminance.sort_by(|a, b| luminance(*a).total_cmp(&luminance(*b)));
+        let mut centers: Vec<Rgb> = (0..CLUSTERS)
+            .map(|i| by_luminance[(2 * i + 1) * by_luminance.len() / (2 * CLUSTERS)])
+            .collect();
+
+        let mut counts = vec![0usize; CLUSTERS];
+        for _ in 0..ITERATIONS {
+            let mut sums = [[0.0f32; 3]; CLUSTERS];
+            counts.fill(0);
+            for pixel in pixels {
+                let nearest = nearest(&centers, *pixel);
+                counts[nearest] += 1;
+                for channel in 0..3 {
+                    sums[nearest][channel] += pixel[channel];
+                }
+            }
+            for (center, (sum, &count)) in centers.iter_mut().zip(sums.iter().zip(&counts)) {
+                if count > 0 {
+                    *center = sum.map(|channel| channel / count as f32);
+                }
+            }
+        }
+
+        let mut swatches: Vec<Swatch> = centers
+            .into_iter()
+            .zip(counts)
+            .filter(|&(_, count)| count > 0)
+            .map(|(rgb, count)| Swatch {
+                rgb: rgb.map(f32::round),
+                population: count as f32 / pixels.len() as f32,
+            })
+            .collect();
+        swatches.sort_by(|a, b| b.population.total_cmp(&a.population));
+        Some(Self { swatches })
+    }
+
+    /// Colorful swatch that stands out, for accents
+    fn accent(&self) -> Rgb {
+        self.swatches
+            .iter()
+            .max_by(|a, b| accent_score(a).total_cmp(&accent_score(b)))
+            .map_or([255.0; 3], |swatch| swatch.rgb)
+    }
+
+    /// Most common swatches that differ from each other and from `accent`
+    fn distinct(&self, accent: Rgb) -> Vec<Rgb> {
+        let mut picked: Vec<Rgb> = Vec::new();
+        for swatch in &self.swatches {
+            if distance(swatch.rgb, accent) >= MIN_DISTANCE
+                && picked
+                    .iter()
+                    .all(|&rgb| distance(rgb, swatch.rgb) >= MIN_DISTANCE)
+            {
+                picked.push(swatch.rgb);
+            }
+        }
+        picked
+    }
+
+    fn darkest(&self) -> Rgb {
+        self.swatches
+            .iter()
+            .map(|swatch| swatch.rgb)
+            .min_by(|a, b| luminance(*a).total_cmp(&luminance(*b)))
+            .unwrap_or(BACKGROUND)
+    }
+
+    /// `theme` with its colors replaced by the cover's. Modifiers are kept.
+    pub fn apply_to(&self, theme: &Palette) -> Palette {
+        let accent = self.accent();
+        let distinct = self.distinct(accent);
+        // Covers with few colors reuse tints of what they have
+        let primary = distinct
+            .first()
+            .copied()
+            .unwrap_or_else(|| mix(accent, [255.0; 3], 0.5));
+        let secondary = distinct
+            .get(1)
+            .copied()
+            .unwrap_or_else(|| mix(primary, accent, 0.5));
+        let tertiary = distinct
+            .get(2)
+            .copied()
+            .unwrap_or_else(|| mix(secondary, [255.0; 3], 0.3));
+
+        let text = |rgb: Rgb| with_contrast(rgb, BACKGROUND, TEXT_CONTRAST);
+        let graphic = |rgb: Rgb| with_contrast(rgb, BACKGROUND, GRAPHIC_CONTRAST);
+        let empty = darken_to(self.darkest(), 0.02);
+        let highlight = graphic(accent);
+        let selected_text = with_contrast(empty, highlight, TEXT_CONTRAST);
+
+        let mut palette = theme.clone();
+        for role in ColorRole::ALL {
+            let rgb = match role {
+                ColorRole::SongTitle | ColorRole::QueueSongTitle | ColorRole::Mode => text(primary),
+                ColorRole::Album | ColorRole::QueueAlbum => text(secondary),
+                ColorRole::Artist | ColorRole::QueueArtist => text(tertiary),
+                ColorRole::BorderTitle => text(mix(primary, [128.0; 3], 0.5)),
+                ColorRole::TopAccent
+                | ColorRole::Playing
+                | ColorRole::Paused
+                | ColorRole::Stopped
+                | ColorRole::TimeSeparator
+                | ColorRole::TimeDuration
+                | ColorRole::TimeElapsed
+                | ColorRole::QueuePosition
+                | ColorRole::QueueDuration
+                | ColorRole::TrackDuration => text(accent),
+                ColorRole::Border => graphic(primary),
+                ColorRole::ProgressFilled | ColorRole::Volume => graphic(secondary),
+                ColorRole::ProgressEmpty | ColorRole::VolumeEmpty => empty,
+                ColorRole::QueueSelectedHighlight => highlight,
+                ColorRole::QueueSelectedText => selected_text,
+            };
+            let mut spec = palette.get(role);
+            spec.color = to_color(rgb);
+            palette.set(role, spec);
+        }
+        palette
+    }
+}
+
+fn nearest(centers: &[Rgb], pixel: Rgb) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| distance(**a, pixel).total_cmp(&distance(**b, pixel)))
+        .map_or(0, |(i, _)| i)
+}
+
+fn distance(a: Rgb, b: Rgb) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f32>().sqrt()
+}
+
+fn saturation(rgb: Rgb) -> f32 {
+    let max = rgb.iter().copied().fold(0.0, f32::max);
+    let min = rgb.iter().copied().fold(255.0, f32::min);
+    if max == 0.0 { 0.0 } else { (max - min) / max }
+}
+
+/// Saturated, reasonably bright and not too rare
+fn accent_score(swatch: &Swatch) -> f32 {
+    let brightness = swatch.rgb.iter().copied().fold(0.0, f32::max) / 255.0;
+    saturation(swatch.rgb) * brightness * swatch.population.sqrt()
+}
+
+/// WCAG relative luminance
+fn luminance(rgb: Rgb) -> f32 {
+    let linear = rgb.map(|channel| {
+        let c = channel / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    0.2126 * linear[0] + 0.7152 * linear[1] + 0.0722 * linear[2]
+}
+
+fn contrast(a: Rgb, b: Rgb) -> f32 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Rounded like the terminal will show it, so contrast checks hold for what is drawn
+fn mix(a: Rgb, b: Rgb, t: f32) -> Rgb {
+    [0, 1, 2].map(|i| (a[i] + (b[i] - a[i]) * t).round())
+}
+
+/// Move `rgb` towards white or black, whichever gets there, until it has `min`
+/// contrast against `background`
+fn with_contrast(rgb: Rgb, background: Rgb, min: f32) -> Rgb {
+    if contrast(rgb, background) >= min {
+        return rgb;
+    }
+    let target = if contrast([255.0; 3], background) >= contrast([0.0; 3], background) {
+        [255.0; 3]
+    } else {
+        [0.0; 3]
+    };
+    (1..=20)
+        .map(|step| mix(rgb, target, step as f32 / 20.0))
+        .find(|&mixed| contrast(mixed, background) >= min)
+        .unwrap_or(target)
+}
+
+/// Move `rgb` towards black until its luminance is at most `max`
+fn darken_to(rgb: Rgb, max: f32) -> Rgb {
+    (0..=20)
+        .map(|step| mix(rgb, [0.0; 3], step as f32 / 20.0))
+        .find(|&mixed| luminance(mixed) <= max)
+        .unwrap_or([0.0; 3])
+}
+
+fn to_color(rgb: Rgb) -> Color {
+    let [r, g, b] = rgb.map(|channel| channel.round().clamp(0.0, 255.0) as u8);
+    Color::Rgb(r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb_of(color: Color) -> Rgb {
+        match color {
+            Color::Rgb(r, g, b) => [r as f32, g as f32, b as f32],
+            other => panic!("not an rgb color: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extract_finds_main_colors() {
+        // Mostly dark blue with some orange
+        let mut pixels = vec![[20.0, 30.0, 90.0]; 300];
+        pixels.extend(vec![[240.0, 140.0, 30.0]; 100]);
+        let palette = CoverPalette::from_pixels(&pixels).unwrap();
+
+        assert!(distance(palette.swatches[0].rgb, [20.0, 30.0, 90.0]) < 1.0);
+        assert!((palette.swatches[0].population - 0.75).abs() < 0.01);
+        assert!(distance(palette.accent(), [240.0, 140.0, 30.0]) < 1.0);
+        assert!(CoverPalette::from_pixels(&[]).is_none());
+    }
+
+    #[test]
+    fn test_roles_are_readable() {
+        // A nearly black cover still gives readable text
+        let pixels = vec![[10.0, 10.0, 12.0]; 100];
+        let cover = CoverPalette::from_pixels(&pixels).unwrap();
+        let palette = cover.apply_to(&Palette::default());
+
+        for role in [
+            ColorRole::SongTitle,
+            ColorRole::Artist,
+            ColorRole::TopAccent,
+        ] {
+            let rgb = rgb_of(palette.get(role).color);
+            assert!(contrast(rgb, BACKGROUND) >= TEXT_CONTRAST, "{:?}", role);
+        }
+        let highlight = rgb_of(palette.get(ColorRole::QueueSelectedHighlight).color);
+        let selected = rgb_of(palette.get(ColorRole::QueueSelectedText).color);
+        assert!(contrast(selected, highlight) >= TEXT_CONTRAST);
+    }
+
+    #[test]
+    fn test_theme_modifiers_are_kept() {
+        let mut theme = Palette::default();
+        let mut spec = theme.get(ColorRole::SongTitle);
+        spec.modifiers = ratatui::style::Modifier::BOLD;
+        theme.set(ColorRole::SongTitle, spec);
+
+        let cover = CoverPalette::from_pixels(&[[200.0, 60.0, 60.0]; 10]).unwrap();
+        let palette = cover.apply_to(&theme);
+        assert_eq!(
+            palette.get(ColorRole::SongTitle).modifiers,
+            ratatui::style::Modifier::BOLD
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use ratatui::widgets::ListState;
+
+use crate::app::ui::{MenuMode, PanelFocus, WIDTH_CACHE, rendering::utils};
+
+/// List panel a `/` filter can be applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterTarget {
+    Queue,
+    Artists,
+    AlbumList,
+    AlbumTracks,
+}
+
+impl FilterTarget {
+    /// The filterable list for the focused panel, if any
+    pub fn for_panel(menu_mode: &MenuMode, panel_focus: &PanelFocus) -> Option<Self> {
+        match (menu_mode, panel_focus) {
+            (MenuMode::Queue, _) => Some(FilterTarget::Queue),
+            (MenuMode::Artists, PanelFocus::Artists) => Some(FilterTarget::Artists),
+            (MenuMode::Albums, PanelFocus::AlbumList) => Some(FilterTarget::AlbumList),
+            (MenuMode::Albums, PanelFocus::AlbumTracks) => Some(FilterTarget::AlbumTracks),
+            _ => None,
+        }
+    }
+}
+
+/// Vim-like incremental filter for list panels.
+///
+/// While typing, the target list is narrowed to the matching items. The real
+/// list state keeps pointing into the unfiltered list, so every existing action
+/// works on the selected item unchanged; only rendering maps the selection.
+#[derive(Debug, Default)]
+pub struct ListFilter {
+    /// Panel being filtered while the input is open
+    pub target: Option<FilterTarget>,
+    /// Last typed query (kept after the input closes for `n`/`N`)
+    pub query: String,
+    /// Indices into the unfiltered list that match the query
+    pub matches: Vec<usize>,
+    /// Selection before the filter was opened, restored on Esc
+    pub saved_selection: Option<usize>,
+    /// List state for the narrowed list
+    display_state: ListState,
+}
+
+impl ListFilter {
+    /// Open the filter input on a panel
+    pub fn open(&mut self, target: FilterTarget, selection: Option<usize>, len: usize) {
+        self.target = Some(target);
+        self.query.clear();
+        self.matches = (0..len).collect();
+        self.saved_selection = selection;
+        self.display_state = ListState::default();
+    }
+
+    /// Close the filter input (the query is kept for `n`/`N`)
+    pub fn close(&mut self) {
+        self.target = None;
+        self.matches.clear();
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Recompute the matches of the current query against the list items
+    pub fn update_matches<S: AsRef<str>>(&mut self, items: &[S]) {
+        self.matches = find_matches(&self.query, items);
+    }
+
+    /// Matched indices and the state to render them with when `target` is being filtered.
+    /// The display selection follows the real selection if it is one of the matches.
+    pub fn narrowed(
+        &mut self,
+        target: FilterTarget,
+        selected: Option<usize>,
+    ) -> Option<(&[usize], &mut ListState)> {
+        if self.target != Some(target) {
+            return None;
+        }
+        let position = selected.and_then(|idx| self.matches.iter().position(|&m| m == idx));
+        self.display_state.select(position);
+        Some((&self.matches, &mut self.display_state))
+    }
+
+    /// Panel title with the query appended while `target` is being filtered
+    pub fn title(&self, target: FilterTarget, base: &str, max_width: usize) -> String {
+        if self.target != Some(target) {
+            return format!(" {} ", base);
+        }
+        let title = format!(" {} /{}▏", base, self.query);
+        WIDTH_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.get_width(&title) <= max_width {
+                title
+            } else {
+                utils::truncate_by_width_cached(&mut cache, &title, max_width)
+                    .trim_end()
+                    .to_string()
+            }
+        })
+    }
+}
+
+/// Items of `items` to display: only the matched ones when narrowed, otherwise all
+pub fn visible_items<'a, T>(items: &'a [T], matches: Option<&[usize]>) -> Vec<(usize, &'a T)> {
+    match matches {
+        Some(matches) => matches
+            .iter()
+            .filter_map(|&idx| items.get(idx).map(|item| (idx, item)))
+            .collect(),
+        None => items.iter().enumerate().collect(),
+    }
+}
+
+/// Indices of the items matching `query` (all items for an empty query)
+pub fn find_matches<S: AsRef<str>>(query: &str, items: &[S]) -> Vec<usize> {
+    let terms: Vec<Vec<char>> = fold_text(query)
+        .split_whitespace()
+        .map(|term| term.chars().collect())
+        .collect();
+
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let haystack = fold_text(item.as_ref());
+            terms.iter().all(|term| is_subsequence(term, &haystack))
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Next match after `current` (or before it when `forward` is false), wrapping around
+pub fn next_match(matches: &[usize], current: Option<usize>, forward: bool) -> Option<usize> {
+    let current = match current {
+        Some(current) => current,
+        None => return matches.first().copied(),
+    };
+    if forward {
+        matches
+            .iter()
+            .copied()
+            .find(|&idx| idx > current)
+            .or_else(|| matches.first().copied())
+    } else {
+        matches
+            .iter()
+            .rev()
+            .copied()
+            .find(|&idx| idx < current)
+            .or_else(|| matches.last().copied())
+    }
+}
+
+/// Fuzzy match: every character of `term` appears in `haystack` in order
+fn is_subsequence(term: &[char], haystack: &str) -> bool {
+    let mut term = term.iter().peekable();
+    for c in haystack.chars() {
+        if term.peek() == Some(&&c) {
+            term.next();
+        }
+    }
+    term.peek().is_none()
+}
+
+/// Lowercase and strip diacritics so "Björk" matches "bjork"
+pub fn fold_text(s: &str) -> String {
+    s.chars()
+        .flat_map(char::to_lowercase)
+        .map(strip_diacritic)
+        .collect()
+}
+
+/// Map accented Latin letters to their base letter
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_text() {
+        assert_eq!(fold_text("Björk"), "bjork");
+        assert_eq!(fold_text("Sigur Rós"), "sigur ros");
+        assert_eq!(fold_text("MOTÖRHEAD"), "motorhead");
+    }
+
+    #[test]
+    fn test_find_matches_fuzzy() {
+        let items = ["Björk", "Boards of Canada", "Bob Dylan", "Aphex Twin"];
+        assert_eq!(find_matches("bjork", &items), vec![0]);
+        assert_eq!(find_matches("boc", &items), vec![1]);
+        assert_eq!(find_matches("bo dy", &items), vec![2]);
+        assert_eq!(find_matches("", &items), vec![0, 1, 2, 3]);
+        assert!(find_matches("xyz", &items).is_empty());
+    }
+
+    #[test]
+    fn test_next_match_wraps() {
+        let matches = [2, 5, 9];
+        assert_eq!(next_match(&matches, Some(5), true), Some(9));
+        assert_eq!(next_match(&matches, Some(9), true), Some(2));
+        assert_eq!(next_match(&matches, Some(5), false), Some(2));
+        assert_eq!(next_match(&matches, Some(2), false), Some(9));
+        assert_eq!(next_match(&matches, Some(3), true), Some(5));
+        assert_eq!(next_match(&matches, None, true), Some(2));
+        assert_eq!(next_match(&[], Some(1), true), None);
+    }
+
+    #[test]
+    fn test_narrowed_maps_selection() {
+        let mut filter = ListFilter::default();
+        filter.open(FilterTarget::Artists, Some(1), 4);
+        filter.query = "b".to_string();
+        filter.update_matches(&["Abba", "Cake", "Björk", "Blur"]);
+        assert_eq!(filter.matches, vec![0, 2, 3]);
+
+        let (matches, state) = filter.narrowed(FilterTarget::Artists, Some(2)).unwrap();
+        assert_eq!(matches, &[0, 2, 3]);
+        assert_eq!(state.selected(), Some(1));
+        assert!(filter.narrowed(FilterTarget::Queue, Some(2)).is_none());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::app::ui::HIT_MAP;
+use ratatui::layout::{Margin, Position, Rect};
+use ratatui::text::Line;
+use std::ops::Range;
+
+/// Lists that can be clicked or have items marked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    Queue,
+    Artists,
+    /// Albums and expanded tracks of the selected artist
+    ArtistAlbums,
+    AllAlbums,
+    AlbumTracks,
+}
+
+/// What a region of the screen stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitTarget {
+    /// Rows of a list, `offset` being the index of the first visible row
+    List { list: ListKind, offset: usize },
+    /// Menu mode indicator in the top box
+    MenuMode,
+    /// The bar of the progress widget
+    ProgressBar,
+    /// Volume icon, bar and percentage in the top box
+    Volume,
+}
+
+/// Screen regions recorded while rendering, used to find what the mouse points at
+#[derive(Debug, Default)]
+pub struct HitMap {
+    regions: Vec<(Rect, HitTarget)>,
+}
+
+impl HitMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the regions of the previous frame
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    pub fn add(&mut self, area: Rect, target: HitTarget) {
+        if !area.is_empty() {
+            self.regions.push((area, target));
+        }
+    }
+
+    /// Target at the given cell along with its region; later regions are drawn on top
+    pub fn hit(&self, column: u16, row: u16) -> Option<(Rect, HitTarget)> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .copied()
+    }
+}
+
+impl HitTarget {
+    /// Index of the list item at `row` within `area`
+    pub fn list_index(&self, area: Rect, row: u16) -> Option<(ListKind, usize)> {
+        match *self {
+            HitTarget::List { list, offset } => {
+                Some((list, offset + row.checked_sub(area.y)? as usize))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Record a region of the frame being rendered
+pub fn record_hit(area: Rect, target: HitTarget) {
+    HIT_MAP.with(|map| map.borrow_mut().add(area, target));
+}
+
+/// Record the rows of a bordered list
+pub fn record_list(area: Rect, list: ListKind, offset: usize) {
+    record_hit(
+        area.inner(Margin::new(1, 1)),
+        HitTarget::List { list, offset },
+    );
+}
+
+/// Cells taken by `spans` of a line rendered centered in `area`, as `Paragraph` lays it out
+pub fn centered_spans_area(area: Rect, line: &Line, spans: Range<usize>) -> Rect {
+    let line_width = line.width() as u16;
+    let line_x = area.x + (area.width / 2).saturating_sub(line_width / 2);
+    let span_width = |range: Range<usize>| -> u16 {
+        line.spans[range]
+            .iter()
+            .map(|span| span.width() as u16)
+            .sum()
+    };
+    Rect::new(
+        line_x + span_width(0..spans.start),
+        area.y,
+        span_width(spans),
+        area.height.min(1),
+    )
+    .intersection(area)
+}
+
+/// Position of `column` within `area` from 0.0 (left edge) to 1.0 (right edge)
+pub fn horizontal_fraction(area: Rect, column: u16) -> f64 {
+    if area.width <= 1 {
+        return 0.0;
+    }
+    let offset = column.saturating_sub(area.x).min(area.width - 1);
+    offset as f64 / (area.width - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_prefers_later_regions() {
+        let mut map = HitMap::new();
+        map.add(Rect::new(0, 0, 20, 10), HitTarget::ProgressBar);
+        map.add(Rect::new(5, 2, 5, 5), HitTarget::Volume);
+        map.add(Rect::new(0, 0, 0, 0), HitTarget::MenuMode);
+
+        assert_eq!(map.hit(6, 3).map(|(_, t)| t), Some(HitTarget::Volume));
+        assert_eq!(map.hit(1, 1).map(|(_, t)| t), Some(HitTarget::ProgressBar));
+        assert_eq!(map.hit(20, 1), None);
+
+        map.clear();
+        assert_eq!(map.hit(1, 1), None);
+    }
+
+    #[test]
+    fn test_list_index_and_fraction() {
+        let area = Rect::new(1, 5, 10, 8);
+        let target = HitTarget::List {
+            list: ListKind::Queue,
+            offset: 30,
+        };
+        assert_eq!(target.list_index(area, 5), Some((ListKind::Queue, 30)));
+        assert_eq!(target.list_index(area, 7), Some((ListKind::Queue, 32)));
+        assert_eq!(HitTarget::Volume.list_index(area, 7), None);
+
+        assert_eq!(horizontal_fraction(area, 1), 0.0);
+        assert_eq!(horizontal_fraction(area, 10), 1.0);
+        assert!((horizontal_fraction(area, 4) - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_centered_spans_area() {
+        let line = Line::from(vec!["ab".into(), "cdef".into(), "g".into()]);
+        // 7 cells centered in 11: starts at 5 - 3 = 2
+        let area = Rect::new(10, 4, 11, 1);
+        assert_eq!(
+            centered_spans_area(area, &line, 1..2),
+            Rect::new(14, 4, 4, 1)
+        );
+        assert_eq!(
+            centered_spans_area(area, &line, 0..3),
+            Rect::new(12, 4, 7, 1)
+        );
+    }
+}
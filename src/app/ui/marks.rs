@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+
+use crate::app::ui::{ListKind, MenuMode, PanelFocus};
+
+impl ListKind {
+    /// The list of the focused panel that items can be marked in, if any
+    pub fn markable(menu_mode: &MenuMode, panel_focus: &PanelFocus) -> Option<Self> {
+        match (menu_mode, panel_focus) {
+            (MenuMode::Queue, _) => Some(ListKind::Queue),
+            (MenuMode::Artists, PanelFocus::Albums) => Some(ListKind::ArtistAlbums),
+            (MenuMode::Albums, PanelFocus::AlbumList) => Some(ListKind::AllAlbums),
+            (MenuMode::Albums, PanelFocus::AlbumTracks) => Some(ListKind::AlbumTracks),
+            _ => None,
+        }
+    }
+}
+
+/// Items marked with `space` or a visual range, for bulk operations.
+///
+/// Marks belong to one list at a time. The items of the artist albums and album
+/// tracks lists depend on the selected artist or album (the parent), so marks are
+/// only shown and used while that parent is selected. Each mark keeps the identity
+/// of its item (URI or name) so it can follow the item when the list is reloaded.
+#[derive(Debug, Default)]
+pub struct Marks {
+    list: Option<ListKind>,
+    /// Selection in the parent list when marking
+    parent: Option<usize>,
+    /// Marked indices and the identity of their items
+    items: BTreeMap<usize, String>,
+    /// Start of the visual range, which extends to the cursor
+    anchor: Option<usize>,
+}
+
+impl Marks {
+    /// Whether the marks belong to `list` as shown for `parent`
+    fn applies_to(&self, list: ListKind, parent: Option<usize>) -> bool {
+        self.list == Some(list) && self.parent == parent
+    }
+
+    /// Drop the marks of any other list before marking in `list`
+    fn switch_to(&mut self, list: ListKind, parent: Option<usize>) {
+        if !self.applies_to(list, parent) {
+            self.clear();
+            self.list = Some(list);
+            self.parent = parent;
+        }
+    }
+
+    /// Mark an item, or unmark it if it already is
+    pub fn toggle(&mut self, list: ListKind, parent: Option<usize>, index: usize, key: String) {
+        self.switch_to(list, parent);
+        if self.items.remove(&index).is_none() {
+            self.items.insert(index, key);
+        }
+    }
+
+    pub fn mark(&mut self, list: ListKind, parent: Option<usize>, index: usize, key: String) {
+        self.switch_to(list, parent);
+        self.items.insert(index, key);
+    }
+
+    /// Start a visual range at the cursor
+    pub fn start_visual(&mut self, list: ListKind, parent: Option<usize>, cursor: usize) {
+        self.switch_to(list, parent);
+        self.anchor = Some(cursor);
+    }
+
+    /// Leave visual mode, returning the range that was selected
+    pub fn end_visual(&mut self, cursor: usize) -> Option<RangeInclusive<usize>> {
+        let anchor = self.anchor.take()?;
+        Some(anchor.min(cursor)..=anchor.max(cursor))
+    }
+
+    pub fn in_visual(&self, list: ListKind, parent: Option<usize>) -> bool {
+        self.applies_to(list, parent) && self.anchor.is_some()
+    }
+
+    /// Marked indices of `list`, including the visual range up to `cursor`
+    pub fn selected(
+        &self,
+        list: ListKind,
+        parent: Option<usize>,
+        cursor: Option<usize>,
+    ) -> BTreeSet<usize> {
+        if !self.applies_to(list, parent) {
+            return BTreeSet::new();
+        }
+        let mut selected: BTreeSet<usize> = self.items.keys().copied().collect();
+        if let (Some(anchor), Some(cursor)) = (self.anchor, cursor) {
+            selected.extend(anchor.min(cursor)..=anchor.max(cursor));
+        }
+        selected
+    }
+
+    /// List the marks belong to
+    pub fn list(&self) -> Option<ListKind> {
+        self.list
+    }
+
+    /// Marked indices and item identities
+    pub fn items(&self) -> &BTreeMap<usize, String> {
+        &self.items
+    }
+
+    /// Replace the marks of `list`, e.g. after moving the marked items
+    pub fn set(&mut self, list: ListKind, parent: Option<usize>, items: BTreeMap<usize, String>) {
+        self.clear();
+        self.list = Some(list);
+        self.parent = parent;
+        self.items = items;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.anchor.is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.list = None;
+        self.parent = None;
+        self.items.clear();
+        self.anchor = None;
+    }
+
+    /// Move the marks of `list` to the new positions of their items after the list
+    /// changed. Items that are gone lose their mark.
+    pub fn sync<S: AsRef<str>>(&mut self, list: ListKind, parent: Option<usize>, keys: &[S]) {
+        if !self.applies_to(list, parent) {
+            return;
+        }
+        let old = std::mem::take(&mut self.items);
+        let mut moved = Vec::new();
+        for (index, key) in old {
+            if keys.get(index).is_some_and(|k| k.as_ref() == key) {
+                self.items.insert(index, key);
+            } else {
+                moved.push(key);
+            }
+        }
+        for key in moved {
+            let new_index = keys
+                .iter()
+                .enumerate()
+                .position(|(i, k)| k.as_ref() == key && !self.items.contains_key(&i));
+            if let Some(index) = new_index {
+                self.items.insert(index, key);
+            }
+        }
+        self.anchor = self.anchor.filter(|anchor| *anchor < keys.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marks_belong_to_one_list() {
+        let mut marks = Marks::default();
+        marks.toggle(ListKind::Queue, None, 2, "b".into());
+        marks.toggle(ListKind::Queue, None, 4, "d".into());
+        marks.toggle(ListKind::Queue, None, 2, "b".into());
+        assert_eq!(
+            marks.selected(ListKind::Queue, None, Some(0)),
+            BTreeSet::from([4])
+        );
+
+        // Marks of other albums don't apply, and marking there starts over
+        marks.toggle(ListKind::AlbumTracks, Some(1), 0, "x".into());
+        assert!(marks.selected(ListKind::Queue, None, None).is_empty());
+        assert!(
+            marks
+                .selected(ListKind::AlbumTracks, Some(2), None)
+                .is_empty()
+        );
+        assert_eq!(
+            marks.selected(ListKind::AlbumTracks, Some(1), None),
+            BTreeSet::from([0])
+        );
+    }
+
+    #[test]
+    fn test_visual_range_follows_cursor() {
+        let mut marks = Marks::default();
+        marks.toggle(ListKind::Queue, None, 0, "a".into());
+        marks.start_visual(ListKind::Queue, None, 5);
+        assert!(marks.in_visual(ListKind::Queue, None));
+        assert_eq!(
+            marks.selected(ListKind::Queue, None, Some(3)),
+            BTreeSet::from([0, 3, 4, 5])
+        );
+        assert_eq!(marks.end_visual(7), Some(5..=7));
+        assert!(!marks.in_visual(ListKind::Queue, None));
+    }
+
+    #[test]
+    fn test_sync_follows_moved_items() {
+        let mut marks = Marks::default();
+        marks.toggle(ListKind::Queue, None, 1, "b".into());
+        marks.toggle(ListKind::Queue, None, 2, "c".into());
+        marks.toggle(ListKind::Queue, None, 3, "d".into());
+
+        // "a" was removed and "c" moved to the end
+        marks.sync(ListKind::Queue, None, &["b", "d", "e", "c"]);
+        assert_eq!(
+            marks.selected(ListKind::Queue, None, None),
+            BTreeSet::from([0, 1, 3])
+        );
+
+        marks.sync(ListKind::Queue, None, &["e"]);
+        assert!(marks.selected(ListKind::Queue, None, None).is_empty());
+    }
+}
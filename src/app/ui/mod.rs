@@ -1,4 +1,8 @@
 pub mod cache;
+pub mod cover_palette;
+pub mod filter;
+pub mod hit_map;
+pub mod marks;
 pub mod rendering;
 pub mod views;
 pub mod widgets;
@@ -6,6 +10,9 @@ pub mod widgets;
 pub use cache::DirtyFlags;
 pub use cache::RenderCache;
 pub use cache::WidthCache;
+pub use filter::{FilterTarget, ListFilter};
+pub use hit_map::{HitMap, HitTarget, ListKind};
+pub use marks::Marks;
 pub use rendering::{AlbumDisplayCache, DisplayItem, Protocol, compute_album_display_list};
 pub use views::{MenuMode, PanelFocus};
 
@@ -23,4 +30,7 @@ thread_local! {
     /// Global album display list cache
     /// Caches computed display lists to avoid recomputation each frame
     pub static ALBUM_DISPLAY_CACHE: RefCell<AlbumDisplayCache> = RefCell::new(AlbumDisplayCache::new());
+
+    /// Clickable regions of the last rendered frame, for mouse handling
+    pub static HIT_MAP: RefCell<HitMap> = RefCell::new(HitMap::new());
 }
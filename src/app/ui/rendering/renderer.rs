@@ -1,7 +1,7 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
-    style::{Style, Stylize},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Clear, ListState, Paragraph},
 };
@@ -9,11 +9,21 @@ use ratatui::{
 use crate::app::Config;
 use crate::app::KeyBinds;
 use crate::app::MessageType;
-use crate::app::ui::Protocol;
+use crate::app::config::theme::ColorRole;
+use crate::app::main_loop::ConnectionStatus;
 use crate::app::ui::views::{
-    albums::render_albums_mode, artists::render_artists_mode, queue::render_queue_mode,
+    albums::render_albums_mode,
+    artists::render_artists_mode,
+    browse::render_browse_mode,
+    playlists::{render_playlist_prompt, render_playlists_mode},
+    queue::render_queue_mode,
+    search::render_search_mode,
+};
+use crate::app::ui::{HIT_MAP, ListFilter, Marks, Protocol};
+use crate::app::{
+    LazyLibrary, SongInfo,
+    song::{BrowseState, LyricsState, PlaylistsState, SearchState, TagViewState},
 };
-use crate::app::{LazyLibrary, SongInfo};
 use crate::app::{MenuMode, PanelFocus};
 use unicode_width::UnicodeWidthStr;
 
@@ -56,7 +66,7 @@ fn get_status_message_text(msg: &crate::app::StatusMessage) -> Option<String> {
             }
         }
         MessageType::Success => "Updated!  ",
-        MessageType::Error => &msg.text,
+        MessageType::Error | MessageType::Info => &msg.text,
     };
     Some(text.to_string())
 }
@@ -131,13 +141,13 @@ fn render_right_aligned_text(
         if !prefix.is_empty() {
             spans.push(Span::styled(
                 prefix,
-                Style::default().fg(config.colors.top_accent_color()),
+                config.colors.style(ColorRole::TopAccent),
             ));
         }
 
         spans.push(Span::styled(
             text,
-            Style::default().fg(config.colors.song_title_color()),
+            config.colors.style(ColorRole::SongTitle),
         ));
 
         let line = Line::from(spans);
@@ -159,6 +169,9 @@ fn render_right_aligned_text(
 fn render_config_warnings_popup(frame: &mut Frame, warnings: &[String], config: &Config) {
     let area = frame.area();
 
+    // The views record their clickable regions again while rendering
+    HIT_MAP.with(|map| map.borrow_mut().clear());
+
     // Calculate popup dimensions based on content
     let title = " Unknown Config Options ";
     let footer = "Press any key to close";
@@ -221,7 +234,7 @@ fn render_config_warnings_popup(frame: &mut Frame, warnings: &[String], config:
 
         lines.push(Line::from(Span::styled(
             format!(" {}", display_warning),
-            Style::default().fg(config.colors.song_title_color()),
+            config.colors.style(ColorRole::SongTitle),
         )));
     }
 
@@ -229,7 +242,7 @@ fn render_config_warnings_popup(frame: &mut Frame, warnings: &[String], config:
     lines.push(
         Line::from(Span::styled(
             footer,
-            Style::default().fg(config.colors.top_accent_color()),
+            config.colors.style(ColorRole::TopAccent),
         ))
         .centered(),
     );
@@ -237,8 +250,8 @@ fn render_config_warnings_popup(frame: &mut Frame, warnings: &[String], config:
     let popup_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
-        .title(Line::from(title).fg(config.colors.border_title_color()))
+        .border_style(config.colors.style(ColorRole::QueueSelectedHighlight))
+        .title(Line::from(title).style(config.colors.style(ColorRole::BorderTitle)))
         .style(Style::default().bg(ratatui::style::Color::Black));
 
     let popup_text = Paragraph::new(lines)
@@ -259,16 +272,24 @@ pub fn render(
     config: &Config,
     menu_mode: &MenuMode,
     library: &Option<LazyLibrary>,
+    tag_view: &Option<TagViewState>,
     artist_list_state: &mut ListState,
     album_list_state: &mut ListState,
     album_display_list_state: &mut ListState,
     all_albums_list_state: &mut ListState,
     album_tracks_list_state: &mut ListState,
+    search: &mut SearchState,
+    list_filter: &mut ListFilter,
+    marks: &Marks,
+    playlists: &mut PlaylistsState,
+    browse: &mut BrowseState,
+    lyrics: &LyricsState,
     panel_focus: &PanelFocus,
     expanded_albums: &std::collections::HashSet<(String, String)>,
     mpd_status: &Option<mpd_client::responses::Status>,
     key_binds: &KeyBinds,
     bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
     show_config_warnings_popup: bool,
     config_warnings: &[String],
     status_message: &Option<crate::app::StatusMessage>,
@@ -288,6 +309,9 @@ pub fn render(
         (None, None, None, None, None)
     };
 
+    // Popups are drawn over the image area, so don't draw the image underneath them
+    let skip_image_render = show_config_warnings_popup || playlists.prompt.is_some();
+
     match menu_mode {
         MenuMode::Queue => {
             render_queue_mode(
@@ -298,6 +322,9 @@ pub fn render(
                 current_song,
                 queue,
                 queue_list_state,
+                list_filter,
+                marks,
+                lyrics.shown(),
                 config,
                 &play_state,
                 progress,
@@ -306,10 +333,19 @@ pub fn render(
                 mpd_status,
                 menu_mode,
                 bit_perfect_enabled,
-                show_config_warnings_popup,
+                connection_status,
+                skip_image_render,
             );
         }
         MenuMode::Artists => {
+            // An open tag view takes the place of the library
+            let (artists_library, (entries_title, groups_title)) = match tag_view {
+                Some(open) => (Some(&open.library), open.panel_titles(&config.browse.views)),
+                None => (
+                    library.as_ref(),
+                    ("Artists".to_string(), "Albums".to_string()),
+                ),
+            };
             render_artists_mode(
                 frame,
                 protocol,
@@ -317,10 +353,13 @@ pub fn render(
                 &format,
                 current_song,
                 config,
-                library,
+                artists_library,
+                (&entries_title, &groups_title),
                 artist_list_state,
                 album_list_state,
                 album_display_list_state,
+                list_filter,
+                marks,
                 panel_focus,
                 expanded_albums,
                 &play_state,
@@ -330,7 +369,8 @@ pub fn render(
                 mpd_status,
                 menu_mode,
                 bit_perfect_enabled,
-                show_config_warnings_popup,
+                connection_status,
+                skip_image_render,
             );
         }
         MenuMode::Albums => {
@@ -344,6 +384,8 @@ pub fn render(
                 library,
                 all_albums_list_state,
                 album_tracks_list_state,
+                list_filter,
+                marks,
                 panel_focus,
                 expanded_albums,
                 &play_state,
@@ -353,7 +395,70 @@ pub fn render(
                 mpd_status,
                 menu_mode,
                 bit_perfect_enabled,
-                show_config_warnings_popup,
+                connection_status,
+                skip_image_render,
+            );
+        }
+        MenuMode::Search => {
+            render_search_mode(
+                frame,
+                protocol,
+                area,
+                &format,
+                current_song,
+                config,
+                search,
+                panel_focus,
+                &play_state,
+                progress,
+                elapsed,
+                duration,
+                mpd_status,
+                menu_mode,
+                bit_perfect_enabled,
+                connection_status,
+                skip_image_render,
+            );
+        }
+        MenuMode::Playlists => {
+            render_playlists_mode(
+                frame,
+                protocol,
+                area,
+                &format,
+                current_song,
+                config,
+                playlists,
+                panel_focus,
+                &play_state,
+                progress,
+                elapsed,
+                duration,
+                mpd_status,
+                menu_mode,
+                bit_perfect_enabled,
+                connection_status,
+                skip_image_render,
+            );
+        }
+        MenuMode::Browse => {
+            render_browse_mode(
+                frame,
+                protocol,
+                area,
+                &format,
+                current_song,
+                browse,
+                config,
+                &play_state,
+                progress,
+                elapsed,
+                duration,
+                mpd_status,
+                menu_mode,
+                bit_perfect_enabled,
+                connection_status,
+                skip_image_render,
             );
         }
     }
@@ -361,6 +466,9 @@ pub fn render(
     // Render key sequence status overlay
     render_top_right_status(frame, key_binds, status_message, area, config);
 
+    // Render playlist prompt if open
+    render_playlist_prompt(frame, playlists, config);
+
     // Render config warnings popup if showing
     if show_config_warnings_popup && !config_warnings.is_empty() {
         render_config_warnings_popup(frame, config_warnings, config);
@@ -159,7 +159,6 @@ impl AlbumDisplayCache {
     }
 
     /// Invalidate the cache (call when expanded_albums changes for current artist)
-    #[allow(dead_code)]
     pub fn invalidate(&mut self) {
         self.artist_index = None;
     }
@@ -1,3 +1,4 @@
+use crate::app::main_loop::ConnectionStatus;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
@@ -5,14 +6,19 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, ListState},
 };
+use std::collections::BTreeSet;
 
 use crate::app::Config;
+use crate::app::config::theme::ColorRole;
 use crate::app::ui::widgets::{
     create_empty_box, create_format_widget, create_left_box_bottom, create_song_widget,
     create_top_box, render_image_widget,
 };
-use crate::app::ui::{MenuMode, PanelFocus, RENDER_CACHE, WIDTH_CACHE, rendering::utils};
-use crate::app::{LazyLibrary, SongInfo};
+use crate::app::ui::{
+    FilterTarget, ListFilter, ListKind, Marks, MenuMode, PanelFocus, RENDER_CACHE, WIDTH_CACHE,
+    filter::visible_items, hit_map::record_list, rendering::utils,
+};
+use crate::app::{LazyLibrary, SongInfo, song::Album};
 use unicode_width::UnicodeWidthStr;
 
 #[allow(clippy::too_many_arguments)]
@@ -26,6 +32,8 @@ pub fn render_albums_mode(
     library: &Option<LazyLibrary>,
     all_albums_list_state: &mut ListState,
     album_tracks_list_state: &mut ListState,
+    list_filter: &mut ListFilter,
+    marks: &Marks,
     panel_focus: &PanelFocus,
     _expanded_albums: &std::collections::HashSet<(String, String)>,
     play_state: &Option<mpd_client::responses::PlayState>,
@@ -35,6 +43,7 @@ pub fn render_albums_mode(
     mpd_status: &Option<mpd_client::responses::Status>,
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
     skip_image_render: bool,
 ) {
     // Same layout as tracks mode but for albums
@@ -78,63 +87,175 @@ pub fn render_albums_mode(
         menu_mode,
         bit_perfect_enabled,
         config.pipewire.is_available(),
+        connection_status,
+        main_vertical_chunks[1],
     );
     frame.render_widget(middle_box, main_vertical_chunks[1]);
 
     // Render albums list
     if let Some(library) = library {
-        let albums_list: Vec<ratatui::widgets::ListItem> = library
-            .all_albums
-            .iter()
-            .map(|(artist_name, album)| {
-                // Calculate available width for album name
-                let available_width = left_horizontal_chunks[0].width.saturating_sub(4) as usize;
-
-                // Create display text with album name and artist
-                let display_text = format!("{} - {}", album.name, artist_name);
-                let truncated_text = WIDTH_CACHE.with(|cache| {
-                    let mut cache = cache.borrow_mut();
-                    utils::truncate_by_width_cached(&mut cache, &display_text, available_width)
-                });
-
-                ratatui::widgets::ListItem::new(vec![Line::from(truncated_text)])
-            })
-            .collect();
-
-        let albums_border_style = if panel_focus == &PanelFocus::AlbumList {
-            Style::default().fg(config.colors.queue_selected_highlight_color())
-        } else {
-            Style::default().fg(config.colors.border_color())
-        };
-
-        let albums_list_widget = ratatui::widgets::List::new(albums_list)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .title(Line::from(" Albums ").fg(config.colors.border_title_color()))
-                    .border_style(albums_border_style),
-            )
-            .highlight_style(
-                Style::default()
-                    .fg(config.colors.queue_selected_text_color())
-                    .bg(config.colors.queue_selected_highlight_color()),
-            );
-        frame.render_stateful_widget(
-            albums_list_widget,
+        let marked_albums =
+            marks.selected(ListKind::AllAlbums, None, all_albums_list_state.selected());
+        let marked_tracks = marks.selected(
+            ListKind::AlbumTracks,
+            all_albums_list_state.selected(),
+            album_tracks_list_state.selected(),
+        );
+        render_album_lists(
+            frame,
+            left_horizontal_chunks[0],
+            left_horizontal_chunks[1],
+            &library.all_albums,
+            all_albums_list_state,
+            album_tracks_list_state,
+            panel_focus,
+            "Albums",
+            Some(list_filter),
+            &marked_albums,
+            &marked_tracks,
+            config,
+        );
+        record_list(
             left_horizontal_chunks[0],
-            all_albums_list_state, // Using all_albums_list_state for album list navigation in Albums mode
+            ListKind::AllAlbums,
+            all_albums_list_state.offset(),
+        );
+        record_list(
+            left_horizontal_chunks[1],
+            ListKind::AlbumTracks,
+            album_tracks_list_state.offset(),
+        );
+    } else {
+        let albums_box = create_empty_box("Albums", config);
+        frame.render_widget(albums_box, left_horizontal_chunks[0]);
+        let tracks_box = create_empty_box("Tracks", config);
+        frame.render_widget(tracks_box, left_horizontal_chunks[1]);
+    }
+
+    // Render progress bar under the two boxes
+    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
+    frame.render_widget(progress_widget, left_vertical_chunks[1]);
+
+    // Split the right area vertically: image on top, song info at bottom
+    let right_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Image takes most space
+        Constraint::Length(4),       // Song info takes 4 lines
+    ])
+    .split(bottom_horizontal_chunks[1]);
+
+    let image_area = right_vertical_chunks[0];
+
+    // Render image or placeholder
+    render_image_widget(frame, protocol, image_area, skip_image_render);
+
+    // Render the song information
+    let song_widget = create_song_widget(current_song, config);
+    frame.render_widget(song_widget, right_vertical_chunks[1]);
+}
+
+/// Render the album list and the tracks of the selected album side by side.
+/// Shared by the Albums and Search views.
+#[allow(clippy::too_many_arguments)]
+pub fn render_album_lists(
+    frame: &mut Frame<'_>,
+    albums_area: Rect,
+    tracks_area: Rect,
+    albums: &[(String, Album)],
+    album_list_state: &mut ListState,
+    tracks_list_state: &mut ListState,
+    panel_focus: &PanelFocus,
+    albums_title: &str,
+    mut filter: Option<&mut ListFilter>,
+    marked_albums: &BTreeSet<usize>,
+    marked_tracks: &BTreeSet<usize>,
+    config: &Config,
+) {
+    let albums_title = match filter.as_deref() {
+        Some(filter) => filter.title(
+            FilterTarget::AlbumList,
+            albums_title,
+            albums_area.width.saturating_sub(2) as usize,
+        ),
+        None => format!(" {} ", albums_title),
+    };
+    let tracks_title = match filter.as_deref() {
+        Some(filter) => filter.title(
+            FilterTarget::AlbumTracks,
+            "Tracks",
+            tracks_area.width.saturating_sub(2) as usize,
+        ),
+        None => " Tracks ".to_string(),
+    };
+
+    // Narrow the album list to the matches while filtering
+    let selected_album = album_list_state.selected();
+    let (album_matches, albums_state) = match filter
+        .as_deref_mut()
+        .and_then(|filter| filter.narrowed(FilterTarget::AlbumList, selected_album))
+    {
+        Some((matches, state)) => (Some(matches), state),
+        None => (None, &mut *album_list_state),
+    };
+
+    let albums_list: Vec<ratatui::widgets::ListItem> = visible_items(albums, album_matches)
+        .into_iter()
+        .map(|(index, (artist_name, album))| {
+            // Calculate available width for album name
+            let available_width = albums_area.width.saturating_sub(4) as usize;
+
+            // Create display text with album name and artist
+            let display_text = format!("{} - {}", album.name, artist_name);
+            let truncated_text = WIDTH_CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                utils::truncate_by_width_cached(&mut cache, &display_text, available_width)
+            });
+
+            let item = ratatui::widgets::ListItem::new(vec![Line::from(truncated_text)]);
+            if marked_albums.contains(&index) {
+                item.reversed()
+            } else {
+                item
+            }
+        })
+        .collect();
+
+    let albums_border_style = if panel_focus == &PanelFocus::AlbumList {
+        config.colors.style(ColorRole::QueueSelectedHighlight)
+    } else {
+        config.colors.style(ColorRole::Border)
+    };
+
+    let albums_list_widget = ratatui::widgets::List::new(albums_list)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(Line::from(albums_title).style(config.colors.style(ColorRole::BorderTitle)))
+                .border_style(albums_border_style),
+        )
+        .highlight_style(
+            config
+                .colors
+                .style(ColorRole::QueueSelectedText)
+                .bg(config.colors.queue_selected_highlight_color()),
         );
+    frame.render_stateful_widget(albums_list_widget, albums_area, albums_state);
 
-        // Show tracks for selected album
-        if let Some(selected_album_index) = all_albums_list_state.selected() {
-            if let Some((_artist_name, selected_album)) =
-                library.all_albums.get(selected_album_index)
-            {
-                let tracks_list: Vec<ratatui::widgets::ListItem> = selected_album
-                    .tracks
-                    .iter()
-                    .map(|track| {
+    // Show tracks for selected album
+    if let Some(selected_album_index) = selected_album {
+        if let Some((_artist_name, selected_album)) = albums.get(selected_album_index) {
+            // Narrow the tracks to the matches while filtering
+            let (track_matches, tracks_state) = match filter.and_then(|filter| {
+                filter.narrowed(FilterTarget::AlbumTracks, tracks_list_state.selected())
+            }) {
+                Some((matches, state)) => (Some(matches), state),
+                None => (None, &mut *tracks_list_state),
+            };
+
+            let tracks_list: Vec<ratatui::widgets::ListItem> =
+                visible_items(&selected_album.tracks, track_matches)
+                    .into_iter()
+                    .map(|(index, track)| {
                         let track_duration_str = RENDER_CACHE.with(|cache| match track.duration {
                             Some(duration) => {
                                 let mut cache = cache.borrow_mut();
@@ -143,8 +264,7 @@ pub fn render_albums_mode(
                             None => "  --:--".to_owned(),
                         });
 
-                        let available_width =
-                            left_horizontal_chunks[1].width.saturating_sub(4) as usize;
+                        let available_width = tracks_area.width.saturating_sub(4) as usize;
                         let track_duration_width = track_duration_str.width();
                         let max_track_title_width =
                             available_width.saturating_sub(track_duration_width + 3);
@@ -172,79 +292,55 @@ pub fn render_albums_mode(
                         let track_text = format!("   {}{}", truncated_track_title, filler,);
                         let mut spans = vec![Span::styled(
                             track_text,
-                            config.colors.queue_song_title_color(),
+                            config.colors.style(ColorRole::QueueSongTitle),
                         )];
                         spans.push(Span::styled(
                             track_duration_str.clone(),
-                            Style::default().fg(config.colors.track_duration_color()),
+                            config.colors.style(ColorRole::TrackDuration),
                         ));
-                        ratatui::widgets::ListItem::new(vec![Line::from(spans)])
+                        let item = ratatui::widgets::ListItem::new(vec![Line::from(spans)]);
+                        if marked_tracks.contains(&index) {
+                            item.reversed()
+                        } else {
+                            item
+                        }
                     })
                     .collect();
 
-                let tracks_border_color = if panel_focus == &PanelFocus::AlbumTracks {
-                    config.colors.queue_selected_highlight_color()
-                } else {
-                    config.colors.border_color()
-                };
-
-                let tracks_title_color = config.colors.border_title_color();
-
-                // Only show highlight when tracks panel is focused
-                let tracks_highlight_style = if panel_focus == &PanelFocus::AlbumTracks {
-                    Style::default()
-                        .fg(config.colors.queue_selected_text_color())
-                        .bg(config.colors.queue_selected_highlight_color())
-                } else {
-                    Style::default()
-                };
-
-                let tracks_list_widget = ratatui::widgets::List::new(tracks_list)
-                    .block(
-                        Block::default()
-                            .borders(Borders::ALL)
-                            .border_type(BorderType::Rounded)
-                            .title(Line::from(" Tracks ").fg(tracks_title_color))
-                            .border_style(Style::default().fg(tracks_border_color)),
-                    )
-                    .highlight_style(tracks_highlight_style);
-                frame.render_stateful_widget(
-                    tracks_list_widget,
-                    left_horizontal_chunks[1],
-                    album_tracks_list_state, // Using album_tracks_list_state for tracks navigation in Albums mode
-                );
+            let tracks_border_color = if panel_focus == &PanelFocus::AlbumTracks {
+                config.colors.queue_selected_highlight_color()
             } else {
-                let tracks_box = create_empty_box("Tracks", config);
-                frame.render_widget(tracks_box, left_horizontal_chunks[1]);
-            }
+                config.colors.border_color()
+            };
+
+            let tracks_title_style = config.colors.style(ColorRole::BorderTitle);
+
+            // Only show highlight when tracks panel is focused
+            let tracks_highlight_style = if panel_focus == &PanelFocus::AlbumTracks {
+                config
+                    .colors
+                    .style(ColorRole::QueueSelectedText)
+                    .bg(config.colors.queue_selected_highlight_color())
+            } else {
+                Style::default()
+            };
+
+            let tracks_list_widget = ratatui::widgets::List::new(tracks_list)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .title(Line::from(tracks_title).style(tracks_title_style))
+                        .border_style(Style::default().fg(tracks_border_color)),
+                )
+                .highlight_style(tracks_highlight_style);
+            frame.render_stateful_widget(tracks_list_widget, tracks_area, tracks_state);
         } else {
             let tracks_box = create_empty_box("Tracks", config);
-            frame.render_widget(tracks_box, left_horizontal_chunks[1]);
+            frame.render_widget(tracks_box, tracks_area);
         }
     } else {
-        let albums_box = create_empty_box("Albums", config);
-        frame.render_widget(albums_box, left_horizontal_chunks[0]);
         let tracks_box = create_empty_box("Tracks", config);
-        frame.render_widget(tracks_box, left_horizontal_chunks[1]);
+        frame.render_widget(tracks_box, tracks_area);
     }
-
-    // Render progress bar under the two boxes
-    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
-    frame.render_widget(progress_widget, left_vertical_chunks[1]);
-
-    // Split the right area vertically: image on top, song info at bottom
-    let right_vertical_chunks = Layout::vertical([
-        Constraint::Percentage(100), // Image takes most space
-        Constraint::Length(4),       // Song info takes 4 lines
-    ])
-    .split(bottom_horizontal_chunks[1]);
-
-    let image_area = right_vertical_chunks[0];
-
-    // Render image or placeholder
-    render_image_widget(frame, protocol, image_area, skip_image_render);
-
-    // Render the song information
-    let song_widget = create_song_widget(current_song, config);
-    frame.render_widget(song_widget, right_vertical_chunks[1]);
 }
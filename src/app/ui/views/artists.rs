@@ -1,7 +1,12 @@
+use crate::app::config::theme::ColorRole;
+use crate::app::main_loop::ConnectionStatus;
 use crate::app::{
     Config, LazyLibrary, ListState, MenuMode, PanelFocus, SongInfo,
     ui::{
-        ALBUM_DISPLAY_CACHE, DisplayItem, Protocol, RENDER_CACHE, WIDTH_CACHE,
+        ALBUM_DISPLAY_CACHE, DisplayItem, FilterTarget, ListFilter, ListKind, Marks, Protocol,
+        RENDER_CACHE, WIDTH_CACHE,
+        filter::visible_items,
+        hit_map::record_list,
         rendering::utils,
         widgets::{
             create_empty_box, create_format_widget, create_left_box_bottom, create_song_widget,
@@ -28,10 +33,13 @@ pub fn render_artists_mode(
     format: &Option<String>,
     current_song: &Option<SongInfo>,
     config: &Config,
-    library: &Option<LazyLibrary>,
+    library: Option<&LazyLibrary>,
+    (entries_title, groups_title): (&str, &str),
     artist_list_state: &mut ListState,
     album_list_state: &mut ListState,
     album_display_list_state: &mut ListState,
+    list_filter: &mut ListFilter,
+    marks: &Marks,
     panel_focus: &PanelFocus,
     expanded_albums: &std::collections::HashSet<(String, String)>,
     play_state: &Option<mpd_client::responses::PlayState>,
@@ -41,6 +49,7 @@ pub fn render_artists_mode(
     mpd_status: &Option<mpd_client::responses::Status>,
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
     skip_image_render: bool,
 ) {
     // Same as original layout, but replace queue box with 2 side-by-side boxes
@@ -84,29 +93,43 @@ pub fn render_artists_mode(
         menu_mode,
         bit_perfect_enabled,
         config.pipewire.is_available(),
+        connection_status,
+        main_vertical_chunks[1],
     );
     frame.render_widget(middle_box, main_vertical_chunks[1]);
 
-    // Render artists list
+    // Render artists list (narrowed to the matches while filtering)
     if let Some(library) = library {
-        let artists_list: Vec<ratatui::widgets::ListItem> = library
-            .artists
-            .iter()
-            .map(|artist| {
-                // Calculate available width for artist name (subtract borders and padding)
-                let available_width = left_horizontal_chunks[0].width.saturating_sub(4) as usize;
-                let truncated_name = WIDTH_CACHE.with(|cache| {
-                    let mut cache = cache.borrow_mut();
-                    utils::truncate_by_width_cached(&mut cache, &artist.name, available_width)
-                });
-                ratatui::widgets::ListItem::new(vec![Line::from(truncated_name)])
-            })
-            .collect();
+        let artists_title = list_filter.title(
+            FilterTarget::Artists,
+            entries_title,
+            left_horizontal_chunks[0].width.saturating_sub(2) as usize,
+        );
+        let (matches, artists_state) =
+            match list_filter.narrowed(FilterTarget::Artists, artist_list_state.selected()) {
+                Some((matches, state)) => (Some(matches), state),
+                None => (None, &mut *artist_list_state),
+            };
+
+        let artists_list: Vec<ratatui::widgets::ListItem> =
+            visible_items(&library.artists, matches)
+                .into_iter()
+                .map(|(_, artist)| {
+                    // Calculate available width for artist name (subtract borders and padding)
+                    let available_width =
+                        left_horizontal_chunks[0].width.saturating_sub(4) as usize;
+                    let truncated_name = WIDTH_CACHE.with(|cache| {
+                        let mut cache = cache.borrow_mut();
+                        utils::truncate_by_width_cached(&mut cache, &artist.name, available_width)
+                    });
+                    ratatui::widgets::ListItem::new(vec![Line::from(truncated_name)])
+                })
+                .collect();
 
         let artists_border_style = if panel_focus == &PanelFocus::Artists {
-            Style::default().fg(config.colors.queue_selected_highlight_color())
+            config.colors.style(ColorRole::QueueSelectedHighlight)
         } else {
-            Style::default().fg(config.colors.border_color())
+            config.colors.style(ColorRole::Border)
         };
 
         let artists_list_widget = ratatui::widgets::List::new(artists_list)
@@ -114,21 +137,30 @@ pub fn render_artists_mode(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .title(Line::from(" Artists ").fg(config.colors.border_title_color()))
+                    .title(
+                        Line::from(artists_title)
+                            .style(config.colors.style(ColorRole::BorderTitle)),
+                    )
                     .border_style(artists_border_style),
             )
             .highlight_style(
-                Style::default()
-                    .fg(config.colors.queue_selected_text_color())
+                config
+                    .colors
+                    .style(ColorRole::QueueSelectedText)
                     .bg(config.colors.queue_selected_highlight_color()),
             );
         frame.render_stateful_widget(
             artists_list_widget,
             left_horizontal_chunks[0],
-            artist_list_state,
+            artists_state,
+        );
+        record_list(
+            left_horizontal_chunks[0],
+            ListKind::Artists,
+            artists_state.offset(),
         );
     } else {
-        let artists_box = create_empty_box("Artists", config);
+        let artists_box = create_empty_box(entries_title, config);
         frame.render_widget(artists_box, left_horizontal_chunks[0]);
     }
 
@@ -152,10 +184,16 @@ pub fn render_artists_mode(
                 items.to_vec()
             });
 
+            let marked = marks.selected(
+                ListKind::ArtistAlbums,
+                Some(selected_artist_index),
+                album_display_list_state.selected(),
+            );
             let albums_list: Vec<ratatui::widgets::ListItem> = display_items
                 .iter()
-                .map(|item| {
-                    match item {
+                .enumerate()
+                .map(|(index, item)| {
+                    let list_item = match item {
                         DisplayItem::Album(album_name) => {
                             // Find the actual album to get duration
                             let album = selected_artist
@@ -213,7 +251,7 @@ pub fn render_artists_mode(
 
                             ratatui::widgets::ListItem::new(vec![
                                 Line::from(display_text)
-                                    .style(Style::default().fg(config.colors.album_color())),
+                                    .style(config.colors.style(ColorRole::Album)),
                             ])
                         }
                         DisplayItem::Song(song_title, duration, _file_path) => {
@@ -257,14 +295,19 @@ pub fn render_artists_mode(
                             let song_text = format!("   {}{}", truncated_song_title, filler,);
                             let mut spans = vec![Span::styled(
                                 song_text,
-                                config.colors.queue_song_title_color(),
+                                config.colors.style(ColorRole::QueueSongTitle),
                             )];
                             spans.push(Span::styled(
                                 song_duration_str.clone(),
-                                Style::default().fg(config.colors.track_duration_color()),
+                                config.colors.style(ColorRole::TrackDuration),
                             ));
                             ratatui::widgets::ListItem::new(vec![Line::from(spans)])
                         }
+                    };
+                    if marked.contains(&index) {
+                        list_item.reversed()
+                    } else {
+                        list_item
                     }
                 })
                 .collect();
@@ -275,12 +318,13 @@ pub fn render_artists_mode(
                 config.colors.border_color()
             };
 
-            let albums_title_color = config.colors.border_title_color();
+            let albums_title_style = config.colors.style(ColorRole::BorderTitle);
 
             // Only show highlight when albums panel is focused
             let albums_highlight_style = if panel_focus == &PanelFocus::Albums {
-                Style::default()
-                    .fg(config.colors.queue_selected_text_color())
+                config
+                    .colors
+                    .style(ColorRole::QueueSelectedText)
                     .bg(config.colors.queue_selected_highlight_color())
             } else {
                 Style::default()
@@ -291,7 +335,7 @@ pub fn render_artists_mode(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_type(BorderType::Rounded)
-                        .title(Line::from(" Albums ").fg(albums_title_color))
+                        .title(Line::from(format!(" {} ", groups_title)).style(albums_title_style))
                         .border_style(Style::default().fg(albums_border_color)),
                 )
                 .highlight_style(albums_highlight_style);
@@ -300,12 +344,17 @@ pub fn render_artists_mode(
                 left_horizontal_chunks[1],
                 album_display_list_state,
             );
+            record_list(
+                left_horizontal_chunks[1],
+                ListKind::ArtistAlbums,
+                album_display_list_state.offset(),
+            );
         } else {
-            let tracks_box = create_empty_box("Albums", config);
+            let tracks_box = create_empty_box(groups_title, config);
             frame.render_widget(tracks_box, left_horizontal_chunks[1]);
         }
     } else {
-        let tracks_box = create_empty_box("Albums", config);
+        let tracks_box = create_empty_box(groups_title, config);
         frame.render_widget(tracks_box, left_horizontal_chunks[1]);
     }
 
@@ -0,0 +1,114 @@
+use crate::app::main_loop::ConnectionStatus;
+use crate::app::{
+    MenuMode,
+    config::Config,
+    song::{BrowseState, SongInfo},
+    ui::{
+        Protocol, WIDTH_CACHE,
+        rendering::utils,
+        widgets::{
+            create_browse_widget, create_format_widget, create_left_box_bottom, create_song_widget,
+            create_top_box, render_image_widget,
+        },
+    },
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_browse_mode(
+    frame: &mut Frame<'_>,
+    protocol: &mut Protocol,
+    area: Rect,
+    format: &Option<String>,
+    current_song: &Option<SongInfo>,
+    browse: &mut BrowseState,
+    config: &Config,
+    play_state: &Option<mpd_client::responses::PlayState>,
+    progress: Option<f64>,
+    elapsed: Option<std::time::Duration>,
+    duration: Option<std::time::Duration>,
+    mpd_status: &Option<mpd_client::responses::Status>,
+    menu_mode: &MenuMode,
+    bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
+    skip_image_render: bool,
+) {
+    // Same layout as Queue mode with the directory listing in place of the queue
+    let main_vertical_chunks = Layout::vertical([
+        Constraint::Length(1),       // Format info takes 1 line
+        Constraint::Length(3),       // New middle box takes 3 lines
+        Constraint::Percentage(100), // Remaining content takes rest
+    ])
+    .split(area);
+
+    // Split bottom section horizontally: left box, right content
+    let bottom_horizontal_chunks = Layout::horizontal([
+        Constraint::Percentage(50), // Left box takes 50% of width
+        Constraint::Percentage(50), // Right content takes 50% of width
+    ])
+    .split(main_vertical_chunks[2]);
+
+    let left_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Listing takes most of the space
+        Constraint::Length(3),       // Progress bar takes 3 lines
+    ])
+    .split(bottom_horizontal_chunks[0]);
+
+    // Render format info widget at top
+    let format_widget = create_format_widget(format, current_song, config);
+    frame.render_widget(format_widget, main_vertical_chunks[0]);
+
+    // Render middle box that spans both splits
+    let middle_box = create_top_box(
+        config,
+        mpd_status.as_ref(),
+        menu_mode,
+        bit_perfect_enabled,
+        config.pipewire.is_available(),
+        connection_status,
+        main_vertical_chunks[1],
+    );
+    frame.render_widget(middle_box, main_vertical_chunks[1]);
+
+    // Render the directory listing, titled with the current path
+    let list_area = left_vertical_chunks[0];
+    let title = format!(" Browse /{} ", browse.path);
+    let title = WIDTH_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        utils::truncate_by_width_cached(
+            &mut cache,
+            &title,
+            list_area.width.saturating_sub(2) as usize,
+        )
+    });
+    let browse_widget = create_browse_widget(
+        &browse.entries,
+        browse.list_state.selected(),
+        current_song,
+        config,
+        &title,
+        list_area,
+    );
+    frame.render_stateful_widget(browse_widget, list_area, &mut browse.list_state);
+
+    // Render progress bar under the listing
+    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
+    frame.render_widget(progress_widget, left_vertical_chunks[1]);
+
+    // Split the right area vertically: image on top, song info at bottom
+    let right_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Image takes most space
+        Constraint::Length(4),       // Song info takes 4 lines
+    ])
+    .split(bottom_horizontal_chunks[1]);
+
+    // Render image or placeholder
+    render_image_widget(frame, protocol, right_vertical_chunks[0], skip_image_render);
+
+    // Render the song information
+    let song_widget = create_song_widget(current_song, config);
+    frame.render_widget(song_widget, right_vertical_chunks[1]);
+}
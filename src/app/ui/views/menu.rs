@@ -1,14 +1,23 @@
+use serde::{Deserialize, Serialize};
+
 /// Menu modes for the application
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MenuMode {
+    #[default]
     Queue,
     Artists,
     Albums,
+    Search,
+    Playlists,
+    Browse,
 }
 
 /// Panel focus for Tracks mode
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PanelFocus {
+    #[default]
     Artists,
     Albums,
     AlbumList,
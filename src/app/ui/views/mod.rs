@@ -1,6 +1,9 @@
 pub mod albums;
 pub mod artists;
+pub mod browse;
 pub mod menu;
+pub mod playlists;
 pub mod queue;
+pub mod search;
 
 pub use menu::{MenuMode, PanelFocus};
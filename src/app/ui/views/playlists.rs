@@ -0,0 +1,367 @@
+use crate::app::config::theme::ColorRole;
+use crate::app::main_loop::ConnectionStatus;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::song::PlaylistsState;
+use crate::app::song::playlists::PlaylistPrompt;
+use crate::app::ui::rendering::utils::{self, center_area};
+use crate::app::ui::widgets::{
+    create_format_widget, create_left_box_bottom, create_song_widget, create_top_box,
+    render_image_widget,
+};
+use crate::app::ui::{MenuMode, PanelFocus, RENDER_CACHE, WIDTH_CACHE};
+use crate::app::{Config, SongInfo};
+use unicode_width::UnicodeWidthStr;
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_playlists_mode(
+    frame: &mut Frame<'_>,
+    protocol: &mut crate::app::ui::Protocol,
+    area: Rect,
+    format: &Option<String>,
+    current_song: &Option<SongInfo>,
+    config: &Config,
+    playlists: &mut PlaylistsState,
+    panel_focus: &PanelFocus,
+    play_state: &Option<mpd_client::responses::PlayState>,
+    progress: Option<f64>,
+    elapsed: Option<std::time::Duration>,
+    duration: Option<std::time::Duration>,
+    mpd_status: &Option<mpd_client::responses::Status>,
+    menu_mode: &MenuMode,
+    bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
+    skip_image_render: bool,
+) {
+    // Same layout as Albums mode: playlists on the left, their contents next to them
+    let main_vertical_chunks = Layout::vertical([
+        Constraint::Length(1),       // Format info takes 1 line
+        Constraint::Length(3),       // New middle box takes 3 lines
+        Constraint::Percentage(100), // Remaining content takes rest
+    ])
+    .split(area);
+
+    // Split bottom section horizontally: left boxes, right content
+    let bottom_horizontal_chunks = Layout::horizontal([
+        Constraint::Percentage(50), // Left boxes take 50% of width
+        Constraint::Percentage(50), // Right content takes 50% of width
+    ])
+    .split(main_vertical_chunks[2]);
+
+    // Split left side into lists and progress bar
+    let left_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Lists take most of space
+        Constraint::Length(3),       // Progress bar takes 3 lines
+    ])
+    .split(bottom_horizontal_chunks[0]);
+
+    let left_horizontal_chunks = Layout::horizontal([
+        Constraint::Percentage(50), // Playlist names take 50% of left space
+        Constraint::Percentage(50), // Playlist contents take 50% of left space
+    ])
+    .split(left_vertical_chunks[0]);
+
+    // Render format info widget at top
+    let format_widget = create_format_widget(format, current_song, config);
+    frame.render_widget(format_widget, main_vertical_chunks[0]);
+
+    // Render middle box that spans both splits
+    let middle_box = create_top_box(
+        config,
+        mpd_status.as_ref(),
+        menu_mode,
+        bit_perfect_enabled,
+        config.pipewire.is_available(),
+        connection_status,
+        main_vertical_chunks[1],
+    );
+    frame.render_widget(middle_box, main_vertical_chunks[1]);
+
+    render_playlist_names(
+        frame,
+        left_horizontal_chunks[0],
+        playlists,
+        panel_focus,
+        config,
+    );
+    render_playlist_contents(
+        frame,
+        left_horizontal_chunks[1],
+        playlists,
+        panel_focus,
+        config,
+    );
+
+    // Render progress bar under the lists
+    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
+    frame.render_widget(progress_widget, left_vertical_chunks[1]);
+
+    // Split the right area vertically: image on top, song info at bottom
+    let right_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Image takes most space
+        Constraint::Length(4),       // Song info takes 4 lines
+    ])
+    .split(bottom_horizontal_chunks[1]);
+
+    // Render image or placeholder
+    render_image_widget(frame, protocol, right_vertical_chunks[0], skip_image_render);
+
+    // Render the song information
+    let song_widget = create_song_widget(current_song, config);
+    frame.render_widget(song_widget, right_vertical_chunks[1]);
+}
+
+/// Border style for a panel, highlighted while it has focus
+fn panel_border_style(focused: bool, config: &Config) -> Style {
+    if focused {
+        config.colors.style(ColorRole::QueueSelectedHighlight)
+    } else {
+        config.colors.style(ColorRole::Border)
+    }
+}
+
+fn render_playlist_names(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    playlists: &mut PlaylistsState,
+    panel_focus: &PanelFocus,
+    config: &Config,
+) {
+    let available_width = area.width.saturating_sub(4) as usize;
+    let items: Vec<ListItem> = playlists
+        .names
+        .iter()
+        .map(|name| {
+            let truncated = WIDTH_CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                utils::truncate_by_width_cached(&mut cache, name, available_width)
+            });
+            ListItem::new(Line::from(truncated))
+        })
+        .collect();
+
+    let title = format!(" Playlists ({}) ", playlists.names.len());
+    let focused = panel_focus != &PanelFocus::AlbumTracks;
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(Line::from(title).style(config.colors.style(ColorRole::BorderTitle)))
+                .border_style(panel_border_style(focused, config)),
+        )
+        .highlight_style(
+            config
+                .colors
+                .style(ColorRole::QueueSelectedText)
+                .bg(config.colors.queue_selected_highlight_color()),
+        );
+    frame.render_stateful_widget(list, area, &mut playlists.list_state);
+}
+
+fn render_playlist_contents(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    playlists: &mut PlaylistsState,
+    panel_focus: &PanelFocus,
+    config: &Config,
+) {
+    let available_width = area.width.saturating_sub(4) as usize;
+    let items: Vec<ListItem> = playlists
+        .contents
+        .iter()
+        .map(|song| {
+            let duration_str = RENDER_CACHE.with(|cache| match song.duration {
+                Some(duration) => {
+                    let mut cache = cache.borrow_mut();
+                    format!("  {}", cache.durations.format_short(duration.as_secs()))
+                }
+                None => "  --:--".to_owned(),
+            });
+            let max_text_width = available_width.saturating_sub(duration_str.width() + 1);
+
+            // Truncate "title - artist" to keep the duration aligned
+            let text = format!(" {} - {}", song.title, song.artist);
+            let truncated = WIDTH_CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                utils::truncate_by_width_cached(&mut cache, &text, max_text_width)
+            });
+            let filler_width = max_text_width.saturating_sub(truncated.width());
+            let filler =
+                RENDER_CACHE.with(|cache| cache.borrow().fillers.spaces(filler_width).to_owned());
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}{}", truncated, filler),
+                    config.colors.style(ColorRole::QueueSongTitle),
+                ),
+                Span::styled(duration_str, config.colors.style(ColorRole::TrackDuration)),
+            ]))
+        })
+        .collect();
+
+    let title = match playlists.selected_name() {
+        Some(name) => format!(" {} ({}) ", name, playlists.contents.len()),
+        None => " Tracks ".to_string(),
+    };
+    let title = WIDTH_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        utils::truncate_by_width_cached(&mut cache, &title, area.width.saturating_sub(2) as usize)
+    });
+
+    // Only show highlight when the contents panel is focused
+    let focused = panel_focus == &PanelFocus::AlbumTracks;
+    let highlight_style = if focused {
+        config
+            .colors
+            .style(ColorRole::QueueSelectedText)
+            .bg(config.colors.queue_selected_highlight_color())
+    } else {
+        Style::default()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(Line::from(title).style(config.colors.style(ColorRole::BorderTitle)))
+                .border_style(panel_border_style(focused, config)),
+        )
+        .highlight_style(highlight_style);
+    frame.render_stateful_widget(list, area, &mut playlists.tracks_list_state);
+}
+
+/// Render the open save/rename/delete/add-to prompt centered over the current view
+pub fn render_playlist_prompt(frame: &mut Frame<'_>, playlists: &PlaylistsState, config: &Config) {
+    let Some(prompt) = &playlists.prompt else {
+        return;
+    };
+    let text_style = config.colors.style(ColorRole::SongTitle);
+    let accent_style = config.colors.style(ColorRole::TopAccent);
+
+    let (title, lines, footer): (&str, Vec<Line>, &str) = match prompt {
+        PlaylistPrompt::SaveQueue { input } => (
+            " Save Queue as Playlist ",
+            vec![input_line(input, text_style, accent_style)],
+            "enter: save  esc: cancel",
+        ),
+        PlaylistPrompt::Rename { input, .. } => (
+            " Rename Playlist ",
+            vec![input_line(input, text_style, accent_style)],
+            "enter: rename  esc: cancel",
+        ),
+        PlaylistPrompt::AddToNew { input, uris } => (
+            if uris.len() == 1 {
+                " Add Song to New Playlist "
+            } else {
+                " Add Songs to New Playlist "
+            },
+            vec![input_line(input, text_style, accent_style)],
+            "enter: add  esc: cancel",
+        ),
+        PlaylistPrompt::ConfirmDelete { name } => (
+            " Delete Playlist ",
+            vec![Line::from(Span::styled(
+                format!(" Delete \"{}\"?", name),
+                text_style,
+            ))],
+            "y: delete  any other key: cancel",
+        ),
+        PlaylistPrompt::AddTo { selected, uris } => {
+            render_playlist_picker(frame, playlists, *selected, uris.len(), config);
+            return;
+        }
+    };
+
+    let width = 50.min(frame.area().width.saturating_sub(4));
+    let height = lines.len() as u16 + 4;
+    let popup_area = center_area(
+        frame.area(),
+        Constraint::Length(width),
+        Constraint::Length(height),
+    );
+
+    let mut content = vec![Line::from("")];
+    content.extend(lines);
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(footer, accent_style)).centered());
+
+    let popup = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(config.colors.style(ColorRole::QueueSelectedHighlight))
+            .title(Line::from(title).style(config.colors.style(ColorRole::BorderTitle)))
+            .style(Style::default().bg(ratatui::style::Color::Black)),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(popup, popup_area);
+}
+
+/// A text input line with a block cursor
+fn input_line<'a>(input: &'a str, text_style: Style, cursor_style: Style) -> Line<'a> {
+    Line::from(vec![
+        Span::raw(" "),
+        Span::styled(input, text_style),
+        Span::styled("█", cursor_style),
+    ])
+}
+
+/// Render the list of playlists to add the selected songs to
+fn render_playlist_picker(
+    frame: &mut Frame<'_>,
+    playlists: &PlaylistsState,
+    selected: usize,
+    song_count: usize,
+    config: &Config,
+) {
+    let mut items = vec![ListItem::new(Line::from(Span::styled(
+        " + New playlist",
+        config.colors.style(ColorRole::TopAccent),
+    )))];
+    items.extend(
+        playlists
+            .names
+            .iter()
+            .map(|name| ListItem::new(Line::from(format!(" {}", name)))),
+    );
+
+    let width = 50.min(frame.area().width.saturating_sub(4));
+    let height = (items.len() as u16 + 2).min(frame.area().height.saturating_sub(4));
+    let popup_area = center_area(
+        frame.area(),
+        Constraint::Length(width),
+        Constraint::Length(height),
+    );
+
+    let title = if song_count == 1 {
+        " Add Song to Playlist ".to_string()
+    } else {
+        format!(" Add {} Songs to Playlist ", song_count)
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(config.colors.style(ColorRole::QueueSelectedHighlight))
+                .title(Line::from(title).style(config.colors.style(ColorRole::BorderTitle)))
+                .style(Style::default().bg(ratatui::style::Color::Black)),
+        )
+        .highlight_style(
+            config
+                .colors
+                .style(ColorRole::QueueSelectedText)
+                .bg(config.colors.queue_selected_highlight_color()),
+        );
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}
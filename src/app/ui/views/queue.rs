@@ -1,12 +1,14 @@
+use crate::app::main_loop::ConnectionStatus;
 use crate::app::{
     ListState, MenuMode,
     config::Config,
-    song::SongInfo,
+    song::{SongInfo, lyrics::Lyrics},
     ui::{
-        Protocol,
+        FilterTarget, ListFilter, ListKind, Marks, Protocol,
+        hit_map::record_list,
         widgets::{
-            create_format_widget, create_left_box_bottom, create_left_box_top, create_song_widget,
-            create_top_box, render_image_widget,
+            create_format_widget, create_left_box_bottom, create_left_box_top,
+            create_lyrics_widget, create_song_widget, create_top_box, render_image_widget,
         },
     },
 };
@@ -24,6 +26,9 @@ pub fn render_queue_mode(
     current_song: &Option<SongInfo>,
     queue: &[SongInfo],
     queue_list_state: &mut ListState,
+    list_filter: &mut ListFilter,
+    marks: &Marks,
+    lyrics: Option<&Lyrics>,
     config: &Config,
     play_state: &Option<mpd_client::responses::PlayState>,
     progress: Option<f64>,
@@ -32,6 +37,7 @@ pub fn render_queue_mode(
     mpd_status: &Option<mpd_client::responses::Status>,
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
     skip_image_render: bool,
 ) {
     // Original layout - restore exactly as it was before changes
@@ -68,18 +74,36 @@ pub fn render_queue_mode(
         menu_mode,
         bit_perfect_enabled,
         config.pipewire.is_available(),
+        connection_status,
+        main_vertical_chunks[1],
     );
     frame.render_widget(middle_box, main_vertical_chunks[1]);
 
-    // Render widgets in left vertical split
+    // Render widgets in left vertical split (narrowed to the matches while filtering)
+    let queue_area = left_vertical_chunks[0];
+    let title = list_filter.title(
+        FilterTarget::Queue,
+        "Queue",
+        queue_area.width.saturating_sub(2) as usize,
+    );
+    let selected = queue_list_state.selected();
+    let marked = marks.selected(ListKind::Queue, None, selected);
+    let (matches, list_state) = match list_filter.narrowed(FilterTarget::Queue, selected) {
+        Some((matches, state)) => (Some(matches), state),
+        None => (None, queue_list_state),
+    };
     let left_box_top = create_left_box_top(
         queue,
-        queue_list_state,
+        matches,
+        selected,
+        &marked,
         current_song,
         config,
-        left_vertical_chunks[0],
+        &title,
+        queue_area,
     );
-    frame.render_stateful_widget(left_box_top, left_vertical_chunks[0], queue_list_state);
+    frame.render_stateful_widget(left_box_top, queue_area, list_state);
+    record_list(queue_area, ListKind::Queue, list_state.offset());
 
     // Render widgets in left vertical split
     let left_box_bottom = create_left_box_bottom(play_state, progress, elapsed, duration, config);
@@ -92,7 +116,18 @@ pub fn render_queue_mode(
     ])
     .split(bottom_horizontal_chunks[1]);
 
-    let image_area = right_vertical_chunks[0];
+    let mut image_area = right_vertical_chunks[0];
+
+    // Lyrics pane next to the cover art
+    if let Some(lyrics) = lyrics {
+        let cover_lyrics_chunks =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(image_area);
+        image_area = cover_lyrics_chunks[0];
+        let lyrics_area = cover_lyrics_chunks[1];
+        let lyrics_widget = create_lyrics_widget(lyrics, elapsed, progress, config, lyrics_area);
+        frame.render_widget(lyrics_widget, lyrics_area);
+    }
 
     // Render image or placeholder
     render_image_widget(frame, protocol, image_area, skip_image_render);
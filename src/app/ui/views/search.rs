@@ -0,0 +1,153 @@
+use crate::app::config::theme::ColorRole;
+use crate::app::main_loop::ConnectionStatus;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use std::collections::BTreeSet;
+
+use crate::app::song::SearchState;
+use crate::app::ui::views::albums::render_album_lists;
+use crate::app::ui::widgets::{
+    create_format_widget, create_left_box_bottom, create_song_widget, create_top_box,
+    render_image_widget,
+};
+use crate::app::ui::{MenuMode, PanelFocus};
+use crate::app::{Config, SongInfo};
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_search_mode(
+    frame: &mut Frame<'_>,
+    protocol: &mut crate::app::ui::Protocol,
+    area: Rect,
+    format: &Option<String>,
+    current_song: &Option<SongInfo>,
+    config: &Config,
+    search: &mut SearchState,
+    panel_focus: &PanelFocus,
+    play_state: &Option<mpd_client::responses::PlayState>,
+    progress: Option<f64>,
+    elapsed: Option<std::time::Duration>,
+    duration: Option<std::time::Duration>,
+    mpd_status: &Option<mpd_client::responses::Status>,
+    menu_mode: &MenuMode,
+    bit_perfect_enabled: bool,
+    connection_status: &ConnectionStatus,
+    skip_image_render: bool,
+) {
+    // Same layout as Albums mode with a search box above the two lists
+    let main_vertical_chunks = Layout::vertical([
+        Constraint::Length(1),       // Format info takes 1 line
+        Constraint::Length(3),       // New middle box takes 3 lines
+        Constraint::Percentage(100), // Remaining content takes rest
+    ])
+    .split(area);
+
+    // Split bottom section horizontally: left boxes, right content
+    let bottom_horizontal_chunks = Layout::horizontal([
+        Constraint::Percentage(50), // Left boxes take 50% of width
+        Constraint::Percentage(50), // Right content takes 50% of width
+    ])
+    .split(main_vertical_chunks[2]);
+
+    // Split left side into search box, result lists and progress bar
+    let left_vertical_chunks = Layout::vertical([
+        Constraint::Length(3),       // Search box takes 3 lines
+        Constraint::Percentage(100), // Result lists take most of space
+        Constraint::Length(3),       // Progress bar takes 3 lines
+    ])
+    .split(bottom_horizontal_chunks[0]);
+
+    // Split the results into two side-by-side boxes
+    let left_horizontal_chunks = Layout::horizontal([
+        Constraint::Percentage(50), // Albums list takes 50% of left space
+        Constraint::Percentage(50), // Album tracks take 50% of left space
+    ])
+    .split(left_vertical_chunks[1]);
+
+    // Render format info widget at top
+    let format_widget = create_format_widget(format, current_song, config);
+    frame.render_widget(format_widget, main_vertical_chunks[0]);
+
+    // Render middle box that spans both splits
+    let middle_box = create_top_box(
+        config,
+        mpd_status.as_ref(),
+        menu_mode,
+        bit_perfect_enabled,
+        config.pipewire.is_available(),
+        connection_status,
+        main_vertical_chunks[1],
+    );
+    frame.render_widget(middle_box, main_vertical_chunks[1]);
+
+    // Render the search box, highlighted while it has keyboard focus
+    let input_border_color = if search.input_active {
+        config.colors.queue_selected_highlight_color()
+    } else {
+        config.colors.border_color()
+    };
+    let mut input_spans = vec![Span::styled(
+        search.query.clone(),
+        config.colors.style(ColorRole::SongTitle),
+    )];
+    if search.input_active {
+        input_spans.push(Span::styled("█", config.colors.style(ColorRole::TopAccent)));
+    }
+    let input_widget = Paragraph::new(Line::from(input_spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(Line::from(" Search ").style(config.colors.style(ColorRole::BorderTitle)))
+            .border_style(Style::default().fg(input_border_color)),
+    );
+    frame.render_widget(input_widget, left_vertical_chunks[0]);
+
+    // Render result albums and tracks the same way as the Albums view
+    let results_title = if search.results.is_empty() {
+        "Results".to_string()
+    } else {
+        format!(
+            "Results ({} albums, {} songs)",
+            search.results.len(),
+            search.song_count
+        )
+    };
+    render_album_lists(
+        frame,
+        left_horizontal_chunks[0],
+        left_horizontal_chunks[1],
+        &search.results,
+        &mut search.album_list_state,
+        &mut search.tracks_list_state,
+        panel_focus,
+        &results_title,
+        None,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        config,
+    );
+
+    // Render progress bar under the result lists
+    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
+    frame.render_widget(progress_widget, left_vertical_chunks[2]);
+
+    // Split the right area vertically: image on top, song info at bottom
+    let right_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Image takes most space
+        Constraint::Length(4),       // Song info takes 4 lines
+    ])
+    .split(bottom_horizontal_chunks[1]);
+
+    let image_area = right_vertical_chunks[0];
+
+    // Render image or placeholder
+    render_image_widget(frame, protocol, image_area, skip_image_render);
+
+    // Render the song information
+    let song_widget = create_song_widget(current_song, config);
+    frame.render_widget(song_widget, right_vertical_chunks[1]);
+}
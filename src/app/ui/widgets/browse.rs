@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Span,
+    widgets::{Block, BorderType, Borders, List, ListItem},
+};
+
+use crate::app::Config;
+use crate::app::SongInfo;
+use crate::app::config::theme::ColorRole;
+use crate::app::song::browse::BrowseEntry;
+use crate::app::ui::RENDER_CACHE;
+use crate::app::ui::widgets::queue::create_song_row;
+
+pub fn create_browse_widget<'a>(
+    entries: &[BrowseEntry],
+    selected: Option<usize>,
+    current_song: &Option<SongInfo>,
+    config: &Config,
+    title: &str,
+    area: Rect,
+) -> List<'a> {
+    let border_style = config.colors.style(ColorRole::Border);
+    let border_title_style = config.colors.style(ColorRole::BorderTitle);
+
+    // Same columns as the queue, with an entry type icon in place of the position
+    let items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let is_selected = selected == Some(i);
+            match entry {
+                BrowseEntry::Directory(_) => create_song_row(
+                    "󰉋 ".to_string(),
+                    [entry.name(), "", ""],
+                    String::new(),
+                    is_selected,
+                    false,
+                    config,
+                    area,
+                ),
+                BrowseEntry::Playlist(_) => create_song_row(
+                    "󰲸 ".to_string(),
+                    [entry.name(), "", ""],
+                    String::new(),
+                    is_selected,
+                    false,
+                    config,
+                    area,
+                ),
+                BrowseEntry::Song(song) => {
+                    let duration_str = RENDER_CACHE.with(|cache| match song.duration {
+                        Some(duration) => {
+                            let mut cache = cache.borrow_mut();
+                            cache.durations.format_short(duration.as_secs()).to_owned()
+                        }
+                        None => " (--:--)".to_owned(),
+                    });
+                    let is_currently_playing = current_song
+                        .as_ref()
+                        .is_some_and(|current| current.file_path == song.file_path);
+                    create_song_row(
+                        "󰎈 ".to_string(),
+                        [&song.title, &song.artist, &song.album],
+                        duration_str,
+                        is_selected,
+                        is_currently_playing,
+                        config,
+                        area,
+                    )
+                }
+            }
+        })
+        .collect();
+
+    List::new(items)
+        .block(
+            Block::default()
+                .border_type(BorderType::Rounded)
+                .borders(Borders::ALL)
+                .title(Span::styled(title.to_owned(), border_title_style))
+                .border_style(border_style),
+        )
+        .style(border_style)
+        .highlight_style(Style::default())
+}
@@ -1,27 +1,24 @@
 use ratatui::{
-    style::Style,
     text::Span,
     widgets::{Block, BorderType, Borders, Paragraph},
 };
 
 use crate::app::Config;
+use crate::app::config::theme::ColorRole;
 
 pub fn create_empty_box<'a>(title: &'a str, config: &Config) -> Paragraph<'a> {
-    let border_color = config.colors.border_color();
-    let border_title_color = config.colors.border_title_color();
-    let text_color = config.colors.song_title_color();
+    let border_style = config.colors.style(ColorRole::Border);
+    let border_title_style = config.colors.style(ColorRole::BorderTitle);
+    let text_style = config.colors.style(ColorRole::SongTitle);
 
     Paragraph::new("")
         .block(
             Block::default()
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
-                .title(Span::styled(
-                    format!(" {} ", title),
-                    Style::default().fg(border_title_color),
-                ))
-                .border_style(Style::default().fg(border_color)),
+                .title(Span::styled(format!(" {} ", title), border_title_style))
+                .border_style(border_style),
         )
-        .style(Style::default().fg(text_color))
+        .style(text_style)
         .centered()
 }
@@ -0,0 +1,72 @@
+use ratatui::{
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use std::time::Duration;
+
+use crate::app::Config;
+use crate::app::config::theme::ColorRole;
+use crate::app::song::lyrics::Lyrics;
+
+pub fn create_lyrics_widget<'a>(
+    lyrics: &'a Lyrics,
+    elapsed: Option<Duration>,
+    progress: Option<f64>,
+    config: &Config,
+    area: Rect,
+) -> Paragraph<'a> {
+    let border_title_style = config.colors.style(ColorRole::BorderTitle);
+    let border_style = config.colors.style(ColorRole::Border);
+    let current_style = config.colors.style(ColorRole::SongTitle);
+    let other_style = config.colors.style(ColorRole::QueueSongTitle);
+
+    let current = elapsed.and_then(|elapsed| lyrics.current_line(elapsed));
+    let height = area.height.saturating_sub(2) as usize;
+    let top = scroll_offset(lyrics.line_count(), height, current, progress);
+
+    let lines: Vec<Line> = (0..lyrics.line_count())
+        .map(|index| {
+            let style = if Some(index) == current {
+                current_style.add_modifier(Modifier::BOLD)
+            } else {
+                other_style
+            };
+            Line::from(Span::styled(lyrics.line(index), style))
+        })
+        .collect();
+
+    let title = if lyrics.is_synced() {
+        " Lyrics "
+    } else {
+        " Lyrics (unsynced) "
+    };
+
+    Paragraph::new(lines)
+        .block(
+            Block::default()
+                .border_type(BorderType::Rounded)
+                .borders(Borders::ALL)
+                .title(Span::styled(title, border_title_style))
+                .border_style(border_style),
+        )
+        .scroll((top as u16, 0))
+        .centered()
+}
+
+/// First visible line: keeps the current line in the middle of synced lyrics,
+/// and follows the song progress through unsynced ones
+fn scroll_offset(
+    len: usize,
+    height: usize,
+    current: Option<usize>,
+    progress: Option<f64>,
+) -> usize {
+    let max_top = len.saturating_sub(height);
+    let top = match current {
+        Some(current) => current.saturating_sub(height / 2),
+        None => (progress.unwrap_or(0.0).clamp(0.0, 1.0) * max_top as f64) as usize,
+    };
+    top.min(max_top)
+}
@@ -1,13 +1,17 @@
+pub mod browse;
 pub mod generic;
 pub mod image;
+pub mod lyrics;
 pub mod progress;
 pub mod queue;
 pub mod song;
 pub mod top_box;
 
 // Re-export all widget functions from separate modules
+pub use self::browse::create_browse_widget;
 pub use self::generic::create_empty_box;
 pub use self::image::render_image_widget;
+pub use self::lyrics::create_lyrics_widget;
 pub use self::song::create_format_widget;
 pub use self::top_box::create_top_box;
 
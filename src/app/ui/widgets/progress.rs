@@ -5,7 +5,9 @@ use ratatui::{
 };
 
 use crate::app::Config;
+use crate::app::config::theme::ColorRole;
 use crate::app::ui::RENDER_CACHE;
+use crate::app::ui::hit_map::{HitTarget, centered_spans_area, record_hit};
 
 pub fn create_progress_bar(
     play_state: &Option<mpd_client::responses::PlayState>,
@@ -14,12 +16,6 @@ pub fn create_progress_bar(
     duration: Option<std::time::Duration>,
     config: &Config,
 ) -> impl ratatui::widgets::Widget {
-    let border_color = config.colors.border_color();
-    let border_title_color = config.colors.border_title_color();
-    let song_title_color = config.colors.song_title_color();
-    let progress_filled_color = config.colors.progress_filled_color();
-    let progress_empty_color = config.colors.progress_empty_color();
-
     let state_text = match play_state {
         Some(mpd_client::responses::PlayState::Playing) => "⏸",
         Some(mpd_client::responses::PlayState::Paused) => "▶",
@@ -27,19 +23,18 @@ pub fn create_progress_bar(
         None => "⏹",
     };
 
-    let state_color = match play_state {
-        Some(mpd_client::responses::PlayState::Playing) => config.colors.playing(),
-        Some(mpd_client::responses::PlayState::Paused) => config.colors.paused(),
-        Some(mpd_client::responses::PlayState::Stopped) => config.colors.stopped(),
-        None => config.colors.stopped(),
+    let state_role = match play_state {
+        Some(mpd_client::responses::PlayState::Playing) => ColorRole::Playing,
+        Some(mpd_client::responses::PlayState::Paused) => ColorRole::Paused,
+        Some(mpd_client::responses::PlayState::Stopped) => ColorRole::Stopped,
+        None => ColorRole::Stopped,
     };
 
-    let progress_value = progress.unwrap_or(0.0);
-    let progress_percentage = (progress_value * 100.0) as u16;
+    let progress = progress.unwrap_or(0.0).clamp(0.0, 1.0);
 
     struct DynamicProgressBar {
         state_text: String,
-        progress_percentage: u16,
+        progress: f64,
         border_title_color: Style,
         border_color: Style,
         song_title_color: Style,
@@ -110,8 +105,13 @@ pub fn create_progress_bar(
             let total_text_width = state_width + spacing_width + time_width;
 
             let bar_width = inner.width.saturating_sub(total_text_width as u16) as usize;
-            let filled = (self.progress_percentage as usize * bar_width / 100).min(bar_width);
-            let empty = bar_width.saturating_sub(filled);
+            // Counted in half cells, the last filled one drawn as ╸
+            let halves =
+                ((self.progress * bar_width as f64 * 2.0).round() as usize).min(bar_width * 2);
+            let filled = halves / 2;
+            let has_half = halves % 2 == 1;
+            let half = if has_half { "╸" } else { "" };
+            let empty = bar_width - filled - usize::from(has_half);
 
             // Use cached progress bar strings
             let (filled_str, empty_str) = RENDER_CACHE.with(|cache| {
@@ -126,10 +126,15 @@ pub fn create_progress_bar(
                 Span::styled(&self.state_text, self.state_color),
                 Span::styled(" ", self.state_color),
                 Span::styled(filled_str, self.progress_filled_color),
+                Span::styled(half, self.progress_filled_color),
                 Span::styled(empty_str, self.progress_empty_color),
             ];
             content_spans.extend(time_spans);
             let content = Line::from(content_spans);
+            record_hit(
+                centered_spans_area(inner, &content, 2..5),
+                HitTarget::ProgressBar,
+            );
 
             let paragraph = Paragraph::new(content).centered();
             paragraph.render(inner, buf);
@@ -138,16 +143,16 @@ pub fn create_progress_bar(
 
     DynamicProgressBar {
         state_text: state_text.to_string(),
-        progress_percentage,
-        border_title_color: Style::default().fg(border_title_color),
-        border_color: Style::default().fg(border_color),
-        song_title_color: Style::default().fg(song_title_color),
-        progress_filled_color: Style::default().fg(progress_filled_color),
-        progress_empty_color: Style::default().fg(progress_empty_color),
-        state_color: Style::default().fg(state_color),
-        time_elapsed_color: Style::default().fg(config.colors.time_elapsed()),
-        time_duration_color: Style::default().fg(config.colors.time_duration()),
-        time_separator_color: Style::default().fg(config.colors.time_separator()),
+        progress,
+        border_title_color: config.colors.style(ColorRole::BorderTitle),
+        border_color: config.colors.style(ColorRole::Border),
+        song_title_color: config.colors.style(ColorRole::SongTitle),
+        progress_filled_color: config.colors.style(ColorRole::ProgressFilled),
+        progress_empty_color: config.colors.style(ColorRole::ProgressEmpty),
+        state_color: config.colors.style(state_role),
+        time_elapsed_color: config.colors.style(ColorRole::TimeElapsed),
+        time_duration_color: config.colors.style(ColorRole::TimeDuration),
+        time_separator_color: config.colors.style(ColorRole::TimeSeparator),
         elapsed,
         duration,
     }
@@ -2,30 +2,30 @@ use ratatui::{
     layout::Rect,
     style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, ListItem, ListState},
+    widgets::{Block, BorderType, Borders, List, ListItem},
 };
 
+use std::collections::BTreeSet;
+
 use crate::app::Config;
 use crate::app::SongInfo;
+use crate::app::config::theme::ColorRole;
+use crate::app::ui::filter::visible_items;
 use crate::app::ui::{RENDER_CACHE, WIDTH_CACHE, rendering::utils};
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_queue_widget<'a>(
     queue: &[SongInfo],
-    queue_list_state: &ListState,
+    matches: Option<&[usize]>,
+    selected: Option<usize>,
+    marked: &BTreeSet<usize>,
     current_song: &Option<SongInfo>,
     config: &Config,
+    title: &str,
     area: Rect,
 ) -> List<'a> {
     let border_color = config.colors.border_color();
-    let border_title_color = config.colors.border_title_color();
-    let queue_album_color = config.colors.queue_album_color();
-    let queue_artist_color = config.colors.queue_artist_color();
-    let queue_song_title_color = config.colors.queue_song_title_color();
-    let queue_position_color = config.colors.queue_position_color();
-    let queue_duration_color = config.colors.queue_duration_color();
-
-    // Calculate available width inside the box (minus borders and padding)
-    let inner_width = area.width.saturating_sub(4) as usize; // 2 for borders, 2 for padding
+    let border_title_style = config.colors.style(ColorRole::BorderTitle);
 
     let queue_items: Vec<ListItem> = if queue.is_empty() {
         vec![]
@@ -43,19 +43,17 @@ pub fn create_queue_widget<'a>(
                 .unwrap_or(3) // fallback to 3 for single digit
         });
 
-        queue
+        // Prioritized songs get a marker with their priority after the position
+        let priority_width = queue
             .iter()
-            .enumerate()
-            .map(|(i, song)| {
-                // Calculate available width for entire line using consistent max_num_width
-                let separator_width = 3; // " ║ "
-                let duration_display_width = 4; // "M:SS"
-                let remaining_width = inner_width
-                    .saturating_sub(max_num_width + separator_width * 2 + duration_display_width);
-
-                // Split remaining width into 3 equal parts for title, artist, album
-                let field_width = remaining_width / 3;
+            .filter(|song| song.priority > 0)
+            .map(|song| priority_marker(song.priority).chars().count())
+            .max()
+            .unwrap_or(0);
 
+        visible_items(queue, matches)
+            .into_iter()
+            .map(|(i, song)| {
                 // Format duration if available using cache
                 let duration_str = RENDER_CACHE.with(|cache| match song.duration {
                     Some(duration) => {
@@ -65,107 +63,43 @@ pub fn create_queue_widget<'a>(
                     None => " (--:--)".to_owned(),
                 });
 
-                // Truncate each field to its allocated width using Unicode-aware width with caching
-                let field_width_max = field_width.max(8);
-                let (title, artist, album) = WIDTH_CACHE.with(|cache| {
-                    let mut cache = cache.borrow_mut();
-                    (
-                        utils::left_align_cached(&mut cache, &song.title, field_width_max),
-                        utils::left_align_cached(&mut cache, &song.artist, field_width_max),
-                        utils::left_align_cached(&mut cache, &song.album, field_width_max),
-                    )
-                });
-
                 // Check if this is the currently playing song
                 let is_currently_playing = current_song
                     .as_ref()
                     .map(|current| current.file_path == song.file_path)
                     .unwrap_or(false);
 
-                // Check if this is the selected song
-                let is_selected = queue_list_state.selected() == Some(i);
-
-                // Create base style
-                let mut queue_album_color = Style::default().fg(queue_album_color);
-                let mut queue_song_title_color = Style::default().fg(queue_song_title_color);
-                let mut queue_artist_color = Style::default().fg(queue_artist_color);
-                let mut border_color = Style::default().fg(border_color);
-                let mut duration_color = Style::default().fg(queue_duration_color);
-                let mut pos_color = Style::default().fg(queue_position_color);
-
-                // Apply background highlight for selected song
-                if is_selected {
-                    queue_album_color = queue_album_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    queue_song_title_color = queue_song_title_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    queue_artist_color = queue_artist_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    border_color = border_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    duration_color = duration_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    pos_color = pos_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                }
-
-                // Apply bold-italics to currently playing song content
-                if is_currently_playing {
-                    queue_album_color = queue_album_color.bold().italic();
-                    queue_song_title_color = queue_song_title_color.bold().italic();
-                    queue_artist_color = queue_artist_color.bold().italic();
-                    border_color = border_color.bold().italic();
-                    duration_color = duration_color.bold().italic();
-                    pos_color = pos_color.bold().italic();
-                }
-
                 // Create spans with appropriate styling - use cached position string
                 let num_str = RENDER_CACHE.with(|cache| {
                     let cache = cache.borrow();
                     cache.queue_positions.get(i).to_owned()
                 });
-                let padded_num_str = format!("{:<width$}", num_str, width = max_num_width);
-                let mut spans = vec![Span::styled(padded_num_str, pos_color)];
-
-                // Each field should have its own style, but when selected should be overwritten by the selection styling
-                spans.push(Span::styled(title.clone(), queue_song_title_color));
-                spans.push(Span::styled(" ║ ", border_color));
-                spans.push(Span::styled(artist.clone(), queue_artist_color));
-                spans.push(Span::styled(" ║ ", border_color));
-                spans.push(Span::styled(album.clone(), queue_album_color));
-                spans.push(Span::styled(duration_str.clone(), duration_color));
-
-                // If this row is selected, add padding to fill the entire width
-                if is_selected {
-                    // Calculate the current line width by reconstructing the line content
-                    let line_content = format!(
-                        "{} ║ {} ║ {}{}",
-                        title.clone(),
-                        artist.clone(),
-                        album.clone(),
-                        duration_str.clone()
-                    );
-                    let current_width =
-                        unicode_width::UnicodeWidthStr::width(&line_content as &str)
-                            + max_num_width;
-                    let remaining_width = area.width.saturating_sub(current_width as u16) as usize;
-
-                    if remaining_width > 0 {
-                        // Add spaces to fill the remaining width with the selected background color
-                        let padding = RENDER_CACHE.with(|cache| {
-                            cache.borrow().fillers.spaces(remaining_width).to_owned()
-                        });
-                        spans.push(Span::styled(padding, border_color));
-                    }
+                let marker = if song.priority > 0 {
+                    priority_marker(song.priority)
+                } else {
+                    String::new()
+                };
+                let padded_num_str = format!(
+                    "{:<num_width$}{:<priority_width$}",
+                    num_str,
+                    marker,
+                    num_width = max_num_width,
+                );
+
+                let row = create_song_row(
+                    padded_num_str,
+                    [&song.title, &song.artist, &song.album],
+                    duration_str,
+                    selected == Some(i),
+                    is_currently_playing,
+                    config,
+                    area,
+                );
+                if marked.contains(&i) {
+                    row.reversed()
+                } else {
+                    row
                 }
-
-                ListItem::new(Line::from(spans))
             })
             .collect::<Vec<_>>()
     };
@@ -175,13 +109,119 @@ pub fn create_queue_widget<'a>(
             Block::default()
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
-                .title(Span::styled(
-                    " Queue ",
-                    Style::default().fg(border_title_color),
-                ))
+                .title(Span::styled(title.to_owned(), border_title_style))
                 .border_style(Style::default().fg(border_color)),
         )
         .style(Style::default().fg(border_color))
         .highlight_style(Style::default())
         .repeat_highlight_symbol(true)
 }
+
+fn priority_marker(priority: u8) -> String {
+    format!("▲{} ", priority)
+}
+
+/// Build one row of the queue-style song list: a prefix column (queue position or entry icon),
+/// then title ║ artist ║ album columns and the duration
+pub fn create_song_row<'a>(
+    prefix: String,
+    fields: [&str; 3],
+    duration_str: String,
+    is_selected: bool,
+    is_currently_playing: bool,
+    config: &Config,
+    area: Rect,
+) -> ListItem<'a> {
+    // Calculate available width inside the box (minus borders and padding)
+    let inner_width = area.width.saturating_sub(4) as usize; // 2 for borders, 2 for padding
+    let prefix_width = unicode_width::UnicodeWidthStr::width(prefix.as_str());
+
+    // Calculate available width for entire line using consistent prefix width
+    let separator_width = 3; // " ║ "
+    let duration_display_width = 4; // "M:SS"
+    let remaining_width =
+        inner_width.saturating_sub(prefix_width + separator_width * 2 + duration_display_width);
+
+    // Split remaining width into 3 equal parts for title, artist, album
+    let field_width = remaining_width / 3;
+
+    // Truncate each field to its allocated width using Unicode-aware width with caching
+    let field_width_max = field_width.max(8);
+    let [title, artist, album] = fields;
+    let (title, artist, album) = WIDTH_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        (
+            utils::left_align_cached(&mut cache, title, field_width_max),
+            utils::left_align_cached(&mut cache, artist, field_width_max),
+            utils::left_align_cached(&mut cache, album, field_width_max),
+        )
+    });
+
+    // Create base style
+    let mut queue_album_color = config.colors.style(ColorRole::QueueAlbum);
+    let mut queue_song_title_color = config.colors.style(ColorRole::QueueSongTitle);
+    let mut queue_artist_color = config.colors.style(ColorRole::QueueArtist);
+    let mut border_color = config.colors.style(ColorRole::Border);
+    let mut duration_color = config.colors.style(ColorRole::QueueDuration);
+    let mut pos_color = config.colors.style(ColorRole::QueuePosition);
+
+    // Apply background highlight for selected song
+    if is_selected {
+        queue_album_color = queue_album_color
+            .bg(config.colors.queue_selected_highlight_color())
+            .fg(config.colors.queue_selected_text_color());
+        queue_song_title_color = queue_song_title_color
+            .bg(config.colors.queue_selected_highlight_color())
+            .fg(config.colors.queue_selected_text_color());
+        queue_artist_color = queue_artist_color
+            .bg(config.colors.queue_selected_highlight_color())
+            .fg(config.colors.queue_selected_text_color());
+        border_color = border_color
+            .bg(config.colors.queue_selected_highlight_color())
+            .fg(config.colors.queue_selected_text_color());
+        duration_color = duration_color
+            .bg(config.colors.queue_selected_highlight_color())
+            .fg(config.colors.queue_selected_text_color());
+        pos_color = pos_color
+            .bg(config.colors.queue_selected_highlight_color())
+            .fg(config.colors.queue_selected_text_color());
+    }
+
+    // Apply bold-italics to currently playing song content
+    if is_currently_playing {
+        queue_album_color = queue_album_color.bold().italic();
+        queue_song_title_color = queue_song_title_color.bold().italic();
+        queue_artist_color = queue_artist_color.bold().italic();
+        border_color = border_color.bold().italic();
+        duration_color = duration_color.bold().italic();
+        pos_color = pos_color.bold().italic();
+    }
+
+    let mut spans = vec![Span::styled(prefix, pos_color)];
+
+    // Each field should have its own style, but when selected should be overwritten by the selection styling
+    spans.push(Span::styled(title.clone(), queue_song_title_color));
+    spans.push(Span::styled(" ║ ", border_color));
+    spans.push(Span::styled(artist.clone(), queue_artist_color));
+    spans.push(Span::styled(" ║ ", border_color));
+    spans.push(Span::styled(album.clone(), queue_album_color));
+    spans.push(Span::styled(duration_str.clone(), duration_color));
+
+    // If this row is selected, add padding to fill the entire width
+    if is_selected {
+        // Calculate the current line width by reconstructing the line content
+        let line_content = format!("{} ║ {} ║ {}{}", title, artist, album, duration_str);
+        let current_width =
+            unicode_width::UnicodeWidthStr::width(&line_content as &str) + prefix_width;
+        let remaining_width = area.width.saturating_sub(current_width as u16) as usize;
+
+        if remaining_width > 0 {
+            // Add spaces to fill the remaining width with the selected background color
+            let padding = RENDER_CACHE
+                .with(|cache| cache.borrow().fillers.spaces(remaining_width).to_owned());
+            spans.push(Span::styled(padding, border_color));
+        }
+    }
+
+    ListItem::new(Line::from(spans))
+}
@@ -1,11 +1,12 @@
 use ratatui::{
-    style::{Style, Stylize},
+    style::Stylize,
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
 
 use crate::app::Config;
 use crate::app::SongInfo;
+use crate::app::config::theme::ColorRole;
 use crate::app::ui::RENDER_CACHE;
 
 pub fn create_now_playing_widget<'a>(
@@ -13,23 +14,20 @@ pub fn create_now_playing_widget<'a>(
     config: &'a Config,
 ) -> Paragraph<'a> {
     // Get colors from config
-    let album_color = config.colors.album_color();
-    let artist_color = config.colors.artist_color();
-    let song_title_color = config.colors.song_title_color();
-    let border_title_color = config.colors.border_title_color();
-    let border_color = config.colors.border_color();
+    let album_style = config.colors.style(ColorRole::Album);
+    let artist_style = config.colors.style(ColorRole::Artist);
+    let song_title_style = config.colors.style(ColorRole::SongTitle);
+    let border_title_style = config.colors.style(ColorRole::BorderTitle);
+    let border_style = config.colors.style(ColorRole::Border);
 
     let lines = match current_song {
         Some(song) => {
             vec![
-                Line::from(vec![Span::styled(
-                    &song.title,
-                    Style::default().fg(song_title_color),
-                )]),
+                Line::from(vec![Span::styled(&song.title, song_title_style)]),
                 Line::from(vec![
-                    Span::styled(&song.artist, Style::default().fg(artist_color)),
-                    Span::styled(" - ", Style::default().fg(border_title_color)),
-                    Span::styled(&song.album, Style::default().fg(album_color)),
+                    Span::styled(&song.artist, artist_style),
+                    Span::styled(" - ", border_title_style),
+                    Span::styled(&song.album, album_style),
                 ]),
             ]
         }
@@ -41,11 +39,8 @@ pub fn create_now_playing_widget<'a>(
             Block::default()
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
-                .title(Span::styled(
-                    " Now Playing ",
-                    Style::default().fg(border_title_color),
-                ))
-                .border_style(Style::default().fg(border_color)),
+                .title(Span::styled(" Now Playing ", border_title_style))
+                .border_style(border_style),
         )
         .centered()
 }
@@ -55,8 +50,8 @@ pub fn create_format_widget<'a>(
     current_song: &'a Option<SongInfo>,
     config: &'a Config,
 ) -> Paragraph<'a> {
-    let format_color = config.colors.song_title_color();
-    let accent_color = config.colors.top_accent_color();
+    let format_style = config.colors.style(ColorRole::SongTitle);
+    let accent_style = config.colors.style(ColorRole::TopAccent);
 
     let line = match format {
         Some(f) => {
@@ -83,14 +78,9 @@ pub fn create_format_widget<'a>(
                     };
 
                     // Use yellow accent for consistency with sequence display
-                    let file_type_span = Span::styled(
-                        format!("{}: ", file_type),
-                        Style::default().fg(accent_color),
-                    );
-                    let sample_rate_span = Span::styled(
-                        format!("{:.1}kHz", sample_rate_khz),
-                        Style::default().fg(format_color),
-                    );
+                    let file_type_span = Span::styled(format!("{}: ", file_type), accent_style);
+                    let sample_rate_span =
+                        Span::styled(format!("{:.1}kHz", sample_rate_khz), format_style);
 
                     Line::from(vec![file_type_span, sample_rate_span])
                 } else {
@@ -103,7 +93,5 @@ pub fn create_format_widget<'a>(
         None => Line::from("--"),
     };
 
-    Paragraph::new(line)
-        .style(Style::default().fg(format_color))
-        .left_aligned()
+    Paragraph::new(line).style(format_style).left_aligned()
 }
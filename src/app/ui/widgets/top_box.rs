@@ -1,12 +1,16 @@
 use ratatui::{
-    style::{Style, Stylize},
+    layout::{Margin, Rect},
+    style::Stylize,
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
 
 use crate::app::Config;
+use crate::app::config::theme::ColorRole;
+use crate::app::main_loop::ConnectionStatus;
 use crate::app::ui::MenuMode;
 use crate::app::ui::RENDER_CACHE;
+use crate::app::ui::hit_map::{HitTarget, centered_spans_area, record_hit};
 
 pub fn create_top_box<'a>(
     config: &Config,
@@ -14,59 +18,68 @@ pub fn create_top_box<'a>(
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
     bit_perfect_available: bool,
+    connection_status: &ConnectionStatus,
+    area: Rect,
 ) -> Paragraph<'a> {
-    let border_color = config.colors.border_color();
-    let text_color = config.colors.song_title_color();
-    let playing = config.colors.playing();
-    let paused = config.colors.paused();
-    let stopped = config.colors.stopped();
-    let accent_color = config.colors.top_accent_color();
-    let volume_color = config.colors.volume_color();
-    let volume_empty_color = config.colors.volume_empty_color();
-    let mode_color = config.colors.mode_color();
+    let border_style = config.colors.style(ColorRole::Border);
+    let text_style = config.colors.style(ColorRole::SongTitle);
+    let accent_style = config.colors.style(ColorRole::TopAccent);
+    let volume_style = config.colors.style(ColorRole::Volume);
+    let volume_empty_style = config.colors.style(ColorRole::VolumeEmpty);
+    let mode_style = config.colors.style(ColorRole::Mode);
 
     let mut spans = Vec::new();
+    // Span ranges that react to the mouse
+    let mut volume_spans = 0..0;
+    let mut mode_spans = 0..0;
 
     // Playback status indicators
-    if let Some(status) = mpd_status {
+    if let Some(seconds) = connection_status.seconds_until_retry() {
+        let text = if seconds == 0 {
+            "󰅙 Disconnected — reconnecting...".to_string()
+        } else {
+            format!("󰅙 Disconnected — retrying in {}s", seconds)
+        };
+        spans.push(Span::styled(text, accent_style));
+    } else if let Some(status) = mpd_status {
         // Bit-perfect mode (󰤽 - high quality audio icon) - only show if available
         if bit_perfect_available {
             if bit_perfect_enabled {
-                spans.push(Span::styled("󰟏", Style::default().fg(accent_color).bold()));
+                spans.push(Span::styled("󰟏", accent_style.bold()));
             } else {
-                spans.push(Span::styled("󰟏", Style::default().fg(text_color)));
+                spans.push(Span::styled("󰟏", text_style));
             }
             spans.push(Span::raw(" "));
         }
         // Repeat (󰑖)
         if status.repeat {
-            spans.push(Span::styled("󰑖", Style::default().fg(accent_color).bold()));
+            spans.push(Span::styled("󰑖", accent_style.bold()));
         } else {
-            spans.push(Span::styled("󰑖", Style::default().fg(text_color)));
+            spans.push(Span::styled("󰑖", text_style));
         }
         spans.push(Span::raw(" "));
 
         // Random/Shuffle (󰒝)
         if status.random {
-            spans.push(Span::styled("󰒝", Style::default().fg(accent_color).bold()));
+            spans.push(Span::styled("󰒝", accent_style.bold()));
         } else {
-            spans.push(Span::styled("󰒝", Style::default().fg(text_color)));
+            spans.push(Span::styled("󰒝", text_style));
         }
         spans.push(Span::raw(" "));
 
         // Single (󰒞)
         if status.single != mpd_client::commands::SingleMode::Disabled {
-            spans.push(Span::styled("󰒞", Style::default().fg(accent_color).bold()));
+            spans.push(Span::styled("󰒞", accent_style.bold()));
         } else {
-            spans.push(Span::styled("󰒞", Style::default().fg(text_color)));
+            spans.push(Span::styled("󰒞", text_style));
         }
         spans.push(Span::raw(" "));
 
         // Consume (󰮝)
         if status.consume {
-            spans.push(Span::styled("", Style::default().fg(accent_color).bold()));
+            spans.push(Span::styled("", accent_style.bold()));
         } else {
-            spans.push(Span::styled("", Style::default().fg(text_color)));
+            spans.push(Span::styled("", text_style));
         }
         spans.push(Span::raw(" "));
 
@@ -80,20 +93,14 @@ pub fn create_top_box<'a>(
             mpd_client::responses::PlayState::Paused => "▶",
             mpd_client::responses::PlayState::Stopped => "⏹",
         };
-        let state_color = match status.state {
-            mpd_client::responses::PlayState::Playing => playing,
-            mpd_client::responses::PlayState::Paused => paused,
-            mpd_client::responses::PlayState::Stopped => stopped,
-        };
+        let state_style = config.colors.style(match status.state {
+            mpd_client::responses::PlayState::Playing => ColorRole::Playing,
+            mpd_client::responses::PlayState::Paused => ColorRole::Paused,
+            mpd_client::responses::PlayState::Stopped => ColorRole::Stopped,
+        });
 
-        spans.push(Span::styled(
-            playback_state,
-            Style::default().fg(state_color),
-        ));
-        spans.push(Span::styled(
-            format!(" {} songs", queue_count),
-            Style::default().fg(text_color),
-        ));
+        spans.push(Span::styled(playback_state, state_style));
+        spans.push(Span::styled(format!(" {} songs", queue_count), text_style));
 
         // Volume widget using cached strings
         spans.push(Span::raw("  │  "));
@@ -122,40 +129,51 @@ pub fn create_top_box<'a>(
             )
         });
 
-        spans.push(Span::styled(volume_icon, Style::default().fg(accent_color)));
-        spans.push(Span::styled(" ", Style::default().fg(text_color)));
-        spans.push(Span::styled(filled_str, Style::default().fg(volume_color)));
-        spans.push(Span::styled(
-            empty_str,
-            Style::default().fg(volume_empty_color),
-        ));
-        spans.push(Span::styled(percent_str, Style::default().fg(text_color)));
+        volume_spans.start = spans.len();
+        spans.push(Span::styled(volume_icon, accent_style));
+        spans.push(Span::styled(" ", text_style));
+        spans.push(Span::styled(filled_str, volume_style));
+        spans.push(Span::styled(empty_str, volume_empty_style));
+        spans.push(Span::styled(percent_str, text_style));
+        volume_spans.end = spans.len();
 
         // Menu mode indicator
         spans.push(Span::raw("  │  "));
         let mode_text = match menu_mode {
-            MenuMode::Queue => (" ", accent_color, "Queue", mode_color),
-            MenuMode::Artists => ("󰠃 ", accent_color, "Artists", mode_color),
-            MenuMode::Albums => ("󰀥 ", accent_color, "Albums", mode_color),
+            MenuMode::Queue => (" ", accent_style, "Queue", mode_style),
+            MenuMode::Artists => ("󰠃 ", accent_style, "Artists", mode_style),
+            MenuMode::Albums => ("󰀥 ", accent_style, "Albums", mode_style),
+            MenuMode::Search => ("󰍉 ", accent_style, "Search", mode_style),
+            MenuMode::Playlists => ("󰲸 ", accent_style, "Playlists", mode_style),
+            MenuMode::Browse => ("󰉋 ", accent_style, "Browse", mode_style),
         };
-        spans.push(Span::styled(mode_text.0, Style::default().fg(mode_text.1)));
-        spans.push(Span::styled(mode_text.2, Style::default().fg(mode_text.3)));
+        mode_spans.start = spans.len();
+        spans.push(Span::styled(mode_text.0, mode_text.1));
+        spans.push(Span::styled(mode_text.2, mode_text.3));
+        mode_spans.end = spans.len();
     } else {
-        spans.push(Span::styled(
-            "󰅙 No MPD connection",
-            Style::default().fg(accent_color),
-        ));
+        spans.push(Span::styled("󰅙 No MPD connection", accent_style));
     }
 
     let content = Line::from(spans);
 
+    let inner = area.inner(Margin::new(1, 1));
+    record_hit(
+        centered_spans_area(inner, &content, volume_spans),
+        HitTarget::Volume,
+    );
+    record_hit(
+        centered_spans_area(inner, &content, mode_spans),
+        HitTarget::MenuMode,
+    );
+
     Paragraph::new(content)
         .block(
             Block::default()
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color)),
+                .border_style(border_style),
         )
-        .style(Style::default().fg(text_color))
+        .style(text_style)
         .centered()
 }
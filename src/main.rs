@@ -28,6 +28,16 @@ async fn main() -> color_eyre::Result<()> {
         return Ok(());
     }
 
+    // Handle --purge-library-cache option
+    if args.purge_library_cache {
+        if app::song::library_cache::purge()? {
+            println!("Deleted the library cache");
+        } else {
+            println!("No library cache to delete");
+        }
+        return Ok(());
+    }
+
     // Determine config path for logging later
     let config_path = args.config.clone().unwrap_or_else(|| {
         dirs::config_dir()
@@ -43,6 +53,16 @@ async fn main() -> color_eyre::Result<()> {
         config.mpd.address = addr.clone();
     }
 
+    // Headless commands run without the TUI and exit
+    if let Some(command) = args.command.clone() {
+        // Plain one-line errors, scripts don't need a report
+        if let Err(e) = app::headless::run(command, &config).await {
+            eprintln!("zarumet: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Initialize logger first
     if config.logging.enabled {
         logging::ensure_log_directory()?;